@@ -1,19 +1,16 @@
 #![allow(unused_imports)]
 use notify_rust::{Hint, Notification};
 
-#[cfg(any(target_os = "windows", target_os = "macos"))]
 fn main() {
-    println!("this is a xdg only feature");
-}
-
-#[cfg(all(unix, not(target_os = "macos")))]
-fn main() {
-    Notification::new()
+    let mut notification = Notification::new();
+    notification
         .summary("click me")
         .action("default", "default") // IDENTIFIER, LABEL
         .action("clicked_a", "button a") // IDENTIFIER, LABEL
-        .action("clicked_b", "button b") // IDENTIFIER, LABEL
-        .hint(Hint::Resident(true))
+        .action("clicked_b", "button b"); // IDENTIFIER, LABEL
+    #[cfg(all(unix, not(target_os = "macos")))]
+    notification.hint(Hint::Resident(true));
+    notification
         .show()
         .unwrap()
         .wait_for_action(|action| match action {
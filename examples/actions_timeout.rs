@@ -0,0 +1,17 @@
+use notify_rust::{ActionResult, Notification};
+use std::time::Duration;
+
+fn main() {
+    let result = Notification::new()
+        .summary("click me within 5 seconds")
+        .action("clicked", "click here") // IDENTIFIER, LABEL
+        .show()
+        .unwrap()
+        .wait_for_action_with_timeout(Duration::from_secs(5));
+
+    match result {
+        ActionResult::Action(action) => println!("invoked {action:?}"),
+        ActionResult::Closed(reason) => println!("the notification was closed: {reason:?}"),
+        ActionResult::Timeout => println!("nobody clicked in time"),
+    }
+}
@@ -21,6 +21,6 @@ fn main() {
         .unwrap();
 
     wait_for_keypress("press to close notification");
-    handle.close();
+    handle.close().unwrap();
     wait_for_keypress("press to exit");
 }
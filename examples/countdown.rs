@@ -20,9 +20,9 @@ fn main() {
     for i in 0..11 {
         std::thread::sleep(Duration::from_millis(1_000));
         notification
-            .body(&format!("T-minus {}", 10 - i))
-            .appname(&format!("countdown_{}", 10 - i));
-        notification.update();
+            .body(format!("T-minus {}", 10 - i))
+            .appname(format!("countdown_{}", 10 - i));
+        notification.update().unwrap();
         println!("{}", 10 - i);
     }
 
@@ -0,0 +1,18 @@
+#[cfg(all(unix, not(target_os = "macos")))]
+fn main() {
+    // prints notifications to the terminal if there is no notification server to show them
+    notify_rust::set_embedded_server(true);
+
+    notify_rust::Notification::new()
+        .summary("no notification server")
+        .body("so this is printed by notify-rust's own server")
+        .show()
+        .unwrap();
+
+    println!("{:#?}", notify_rust::get_server_information());
+}
+
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+fn main() {
+    println!("this is a xdg only feature")
+}
@@ -5,13 +5,13 @@ fn main() {
     Notification::new()
         .summary("Formatting")
         .appname("chromium")
-        .body(&format!("This is not chrome, but <b>{}</b>!<br/>", "bold"))
+        .body(format!("This is not chrome, but <b>{}</b>!<br/>", "bold"))
         .icon("chromium")
         .show();
 
     Notification::new()
         .summary("Table Test - will probably not work")
-        .body(&format!(
+        .body(format!(
             "<table><tr><td>{}</td><td>cell 2</td></tr> <tr><td>cell 3</td><td>cell 4</td></tr></table>",
             "cell 1"
         ))
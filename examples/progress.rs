@@ -1,6 +1,19 @@
-#[cfg(any(target_os = "windows", target_os = "macos"))]
+#[cfg(target_os = "macos")]
 fn main() {
-    println!("this is an xdg only feature")
+    println!("this is an xdg and windows only feature")
+}
+
+#[cfg(target_os = "windows")]
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let mut notification = notify_rust::Notification::new()
+        .summary("progress")
+        .progress("download.iso", 0.0, "downloading...")
+        .show()?;
+    for i in 0..=10 {
+        std::thread::sleep(std::time::Duration::from_secs(1));
+        notification.update_progress(i as f32 / 10.0, &format!("{} %", i * 10))?;
+    }
+    Ok(())
 }
 
 #[cfg(all(unix, not(target_os = "macos")))]
@@ -11,10 +24,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     for i in 0..=10 {
         let value = i * 10;
         notification
-            .body(&format!("progress {}%", value))
+            .body(format!("progress {}%", value))
             .hint(notify_rust::Hint::CustomInt("value".to_string(), value));
         std::thread::sleep(std::time::Duration::from_secs(1));
-        notification.update();
+        notification.update()?;
     }
     Ok(())
 }
@@ -9,7 +9,7 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     // use it this way
     for urgency in &[Low, Normal, Critical] {
         Notification::new()
-            .summary(&format!("Urgency {:?}", urgency))
+            .summary(format!("Urgency {:?}", urgency))
             .body("This notification uses hints")
             .icon("firefox")
             .urgency(*urgency)
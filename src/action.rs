@@ -0,0 +1,322 @@
+//! Responses to a notification being acted upon or closed, shared by all platforms.
+
+/// Identifier of the action invoked by clicking the notification itself.
+pub(crate) const DEFAULT_ACTION: &str = "default";
+
+/// The action KDE's servers show the input of a [`reply_input`](`crate::Notification::reply_input`) for.
+#[cfg(all(
+    any(feature = "dbus", feature = "zbus"),
+    unix,
+    not(target_os = "macos")
+))]
+pub(crate) const INLINE_REPLY_ACTION: &str = "inline-reply";
+
+/// An action the user can invoke on a notification.
+///
+/// Depending on the platform it is shown as a button or an entry of a dropdown,
+/// the `"default"` action is invoked by clicking the notification itself.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Action {
+    /// Passed back once the user invokes the action.
+    pub identifier: String,
+    /// Shown to the user.
+    pub label: String,
+    /// Shown instead of the label by servers with action icons,
+    /// see [`Notification::action_with_icon`](`crate::Notification::action_with_icon`).
+    pub icon: Option<String>,
+}
+
+impl Action {
+    /// Creates an action, see [`Notification::action`](`crate::Notification::action`).
+    pub fn new(identifier: impl Into<String>, label: impl Into<String>) -> Self {
+        Action {
+            identifier: identifier.into(),
+            label: label.into(),
+            icon: None,
+        }
+    }
+
+    /// Creates an action shown as the icon named `icon` where possible.
+    pub fn with_icon(
+        identifier: impl Into<String>,
+        label: impl Into<String>,
+        icon: impl Into<String>,
+    ) -> Self {
+        Action {
+            icon: Some(icon.into()),
+            ..Action::new(identifier, label)
+        }
+    }
+
+    /// Pairs up a flat list of `identifier, label, …` as it is sent over D-Bus,
+    /// a trailing identifier without a label is dropped.
+    pub(crate) fn from_pairs(flat: &[String]) -> Vec<Action> {
+        flat.chunks_exact(2)
+            .map(|pair| Action::new(&pair[0], &pair[1]))
+            .collect()
+    }
+}
+
+/// Reason passed to `NotificationClosed` Signal
+///
+/// ## Specification
+/// As listed under [Table 8. `NotificationClosed` Parameters](https://specifications.freedesktop.org/notification-spec/latest/ar01s09.html#idm46350804042704)
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum CloseReason {
+    /// The notification expired
+    Expired,
+    /// The notification was dismissed by the user
+    Dismissed,
+    /// The notification was closed by a call to `CloseNotification`
+    CloseAction,
+    /// Undefined/Reserved reason
+    Other(u32),
+}
+
+impl From<u32> for CloseReason {
+    fn from(raw_reason: u32) -> Self {
+        match raw_reason {
+            1 => CloseReason::Expired,
+            2 => CloseReason::Dismissed,
+            3 => CloseReason::CloseAction,
+            other => CloseReason::Other(other),
+        }
+    }
+}
+
+/// Helper Trait implemented by `Fn()`
+#[cfg(all(unix, not(target_os = "macos")))]
+pub trait ActionResponseHandler {
+    fn call(self, response: &ActionResponse);
+}
+
+// impl<F: Send + Sync + 'static> ActionResponseHandler for F
+#[cfg(all(unix, not(target_os = "macos")))]
+impl<F> ActionResponseHandler for F
+where
+    F: FnOnce(&ActionResponse),
+{
+    fn call(self, res: &ActionResponse) {
+        (self)(res);
+    }
+}
+
+/// Response to an action
+pub enum ActionResponse<'a> {
+    /// Custom Action configured by the Notification.
+    Custom(&'a str),
+
+    /// The Notification was closed.
+    Closed(CloseReason),
+}
+
+impl<'a> From<&'a str> for ActionResponse<'a> {
+    fn from(raw: &'a str) -> Self {
+        Self::Custom(raw)
+    }
+}
+
+/// Owned outcome of waiting on a notification.
+///
+/// Returned by [`NotificationHandle::wait_for_action_result`](crate::NotificationHandle::wait_for_action_result).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ActionResult {
+    /// The user invoked the action with this identifier.
+    Action(String),
+
+    /// The notification was closed.
+    Closed(CloseReason),
+
+    /// Waiting ended before the notification was acted upon or closed,
+    /// see [`NotificationHandle::wait_for_action_with_timeout`](crate::NotificationHandle::wait_for_action_with_timeout).
+    Timeout,
+}
+
+impl From<&ActionResponse<'_>> for ActionResult {
+    fn from(response: &ActionResponse<'_>) -> Self {
+        match *response {
+            ActionResponse::Custom(action) => ActionResult::Action(action.to_owned()),
+            ActionResponse::Closed(reason) => ActionResult::Closed(reason),
+        }
+    }
+}
+
+/// Your handy callback for the `Close` signal of your Notification.
+///
+/// This is implemented by `Fn()` and `Fn(CloseReason)`, so there is probably no good reason for you to manually implement this trait.
+/// Should you find one anyway, please notify me and I'll gladly remove this obviously redundant comment.
+pub trait CloseHandler<T> {
+    /// This is called with the [`CloseReason`].
+    fn call(&self, reason: CloseReason);
+}
+
+impl<F> CloseHandler<CloseReason> for F
+where
+    F: Fn(CloseReason),
+{
+    fn call(&self, reason: CloseReason) {
+        self(reason);
+    }
+}
+
+impl<F> CloseHandler<()> for F
+where
+    F: Fn(),
+{
+    fn call(&self, _: CloseReason) {
+        self();
+    }
+}
+
+/// Callbacks for the actions of a shown notification, one for each action identifier.
+///
+/// Created by `NotificationHandle::on_action`, which is available on all platforms.
+///
+/// ```no_run
+/// # use notify_rust::*;
+/// # fn _doc() -> Result<(), Box<dyn std::error::Error>> {
+/// Notification::new()
+///     .summary("new mail")
+///     .action("open", "Open")
+///     .action("archive", "Archive")
+///     .show()?
+///     .on_action("open", || println!("opening"))
+///     .on_action("archive", || println!("archiving"))
+///     .on_close(|reason: CloseReason| println!("closed: {:?}", reason))
+///     .wait();
+/// # Ok(())
+/// # }
+/// ```
+pub struct ActionCallbacks<'a> {
+    wait: Box<dyn FnOnce() -> ActionResult + 'a>,
+    actions: Vec<(String, Box<dyn FnOnce() + 'a>)>,
+    close: Option<Box<dyn FnOnce(CloseReason) + 'a>>,
+}
+
+impl<'a> ActionCallbacks<'a> {
+    pub(crate) fn new(wait: impl FnOnce() -> ActionResult + 'a) -> Self {
+        ActionCallbacks {
+            wait: Box::new(wait),
+            actions: Vec::new(),
+            close: None,
+        }
+    }
+
+    /// Calls `callback` once the action with this `identifier` is invoked.
+    pub fn on_action(mut self, identifier: &str, callback: impl FnOnce() + 'a) -> Self {
+        self.actions
+            .push((identifier.to_owned(), Box::new(callback)));
+        self
+    }
+
+    /// Calls `callback` once the notification itself is clicked,
+    /// see [`default_action`](`crate::Notification::default_action`).
+    pub fn on_click(self, callback: impl FnOnce() + 'a) -> Self {
+        self.on_action(DEFAULT_ACTION, callback)
+    }
+
+    /// Calls `handler` if the notification is closed without invoking an action.
+    pub fn on_close<A>(mut self, handler: impl CloseHandler<A> + 'a) -> Self {
+        self.close = Some(Box::new(move |reason| handler.call(reason)));
+        self
+    }
+
+    /// Blocks until the user acts upon the notification and calls the matching callback.
+    pub fn wait(self) {
+        match (self.wait)() {
+            ActionResult::Action(action) => {
+                if let Some((_, callback)) = self
+                    .actions
+                    .into_iter()
+                    .find(|(identifier, _)| *identifier == action)
+                {
+                    callback();
+                }
+            }
+            ActionResult::Closed(reason) => {
+                if let Some(close) = self.close {
+                    close(reason);
+                }
+            }
+            ActionResult::Timeout => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::{Cell, RefCell};
+
+    #[test]
+    fn calls_matching_action() {
+        let called = RefCell::new(Vec::new());
+        ActionCallbacks::new(|| ActionResult::Action("archive".into()))
+            .on_action("open", || called.borrow_mut().push("open"))
+            .on_action("archive", || called.borrow_mut().push("archive"))
+            .on_close(|| called.borrow_mut().push("close"))
+            .wait();
+        assert_eq!(*called.borrow(), ["archive"]);
+    }
+
+    #[test]
+    fn click_calls_default_action() {
+        let clicked = Cell::new(false);
+        ActionCallbacks::new(|| ActionResult::Action(DEFAULT_ACTION.into()))
+            .on_action("open", || panic!("\"open\" was not invoked"))
+            .on_click(|| clicked.set(true))
+            .wait();
+        assert!(clicked.get());
+    }
+
+    #[test]
+    fn passes_close_reason() {
+        let reason = Cell::new(None);
+        ActionCallbacks::new(|| ActionResult::Closed(CloseReason::Dismissed))
+            .on_action("open", || panic!("no action was invoked"))
+            .on_close(|closed: CloseReason| reason.set(Some(closed)))
+            .wait();
+        assert_eq!(reason.get(), Some(CloseReason::Dismissed));
+    }
+
+    #[test]
+    fn unmatched_action_calls_nothing() {
+        ActionCallbacks::new(|| ActionResult::Action("delete".into()))
+            .on_action("open", || panic!("\"open\" was not invoked"))
+            .on_close(|| panic!("the notification was not closed"))
+            .wait();
+    }
+
+    #[test]
+    fn timeout_calls_nothing() {
+        ActionCallbacks::new(|| ActionResult::Timeout)
+            .on_action("open", || panic!("\"open\" was not invoked"))
+            .on_close(|| panic!("the notification was not closed"))
+            .wait();
+    }
+
+    #[test]
+    fn pairs_up_flat_actions() {
+        let flat = ["open", "Open", "archive", "Archive", "dangling"].map(String::from);
+        assert_eq!(
+            Action::from_pairs(&flat),
+            [
+                Action::new("open", "Open"),
+                Action::new("archive", "Archive")
+            ]
+        );
+    }
+
+    #[test]
+    fn result_from_response() {
+        assert_eq!(
+            ActionResult::from(&ActionResponse::Custom("open")),
+            ActionResult::Action("open".into())
+        );
+        assert_eq!(
+            ActionResult::from(&ActionResponse::Closed(CloseReason::Other(4))),
+            ActionResult::Closed(CloseReason::Other(4))
+        );
+    }
+}
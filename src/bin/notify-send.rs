@@ -0,0 +1,359 @@
+//! A `notify-send` work-alike on top of notify-rust, for systems without libnotify's.
+
+use notify_rust::{
+    ActionResult, CloseReason, Hint, HintValue, Notification, Priority, Timeout, Urgency,
+};
+
+use std::process::ExitCode;
+
+const USAGE: &str = "\
+Usage:
+  notify-send [OPTION...] <SUMMARY> [BODY]
+
+Options:
+  -u, --urgency=LEVEL           low, normal or critical
+  -t, --expire-time=TIME        timeout in milliseconds
+  -a, --app-name=APP_NAME       application name
+  -i, --icon=ICON               icon name or path
+  -c, --category=TYPE[,TYPE...] notification categories
+  -e, --transient               bypass the server's persistence
+  -h, --hint=TYPE:NAME:VALUE    extra hint, TYPE is int, double, string, byte or boolean
+  -p, --print-id                print the notification's id
+  -r, --replace-id=ID           replace the notification with this id
+  -w, --wait                    wait until an action is invoked or the notification is closed
+  -A, --action=[NAME=]Text      add an action, implies --wait
+  -?, --help                    show this help
+  -v, --version                 print the version
+
+Exit status when waiting:
+  0  an action was invoked, its NAME is printed
+  2  the notification was dismissed or closed
+  3  the notification expired
+  1  the notification could not be shown";
+
+/// Exit status for a notification that was dismissed or closed without invoking an action.
+const CLOSED: u8 = 2;
+
+/// Exit status for a notification that expired.
+const EXPIRED: u8 = 3;
+
+/// What the command line asked for.
+#[derive(Debug, Default, PartialEq)]
+struct Args {
+    summary: String,
+    body: Option<String>,
+    urgency: Option<Urgency>,
+    timeout: Option<i32>,
+    appname: Option<String>,
+    icon: Option<String>,
+    categories: Vec<String>,
+    transient: bool,
+    hints: Vec<Hint>,
+    print_id: bool,
+    replace_id: Option<u32>,
+    wait: bool,
+    actions: Vec<(String, String)>,
+}
+
+/// Help and version short-circuit everything else.
+#[derive(Debug, PartialEq)]
+enum Command {
+    Notify(Box<Args>),
+    Help,
+    Version,
+}
+
+/// Parses `-h TYPE:NAME:VALUE`, well-known names become their [`Hint`], others custom hints of the given type.
+fn parse_hint(hint: &str) -> Result<Hint, String> {
+    let mut parts = hint.splitn(3, ':');
+    let (kind, name, value) = match (parts.next(), parts.next(), parts.next()) {
+        (Some(kind), Some(name), Some(value)) => (kind, name, value),
+        _ => return Err(format!("invalid hint {:?}, expected TYPE:NAME:VALUE", hint)),
+    };
+    if let Ok(hint) = Hint::from_key_val(name, value) {
+        return Ok(hint);
+    }
+    let invalid = || format!("invalid {} value {:?} for hint {:?}", kind, value, name);
+    let value = match kind {
+        "int" => {
+            let int = value.parse().map_err(|_| invalid())?;
+            return Ok(Hint::CustomInt(name.into(), int));
+        }
+        "string" => return Ok(Hint::Custom(name.into(), value.into())),
+        "double" => HintValue::F64(value.parse().map_err(|_| invalid())?),
+        "byte" => HintValue::Byte(value.parse().map_err(|_| invalid())?),
+        "boolean" => HintValue::Bool(value.parse().map_err(|_| invalid())?),
+        _ => {
+            return Err(format!(
+                "invalid hint type {:?}, expected int, double, string, byte or boolean",
+                kind
+            ))
+        }
+    };
+    Ok(Hint::CustomValue(name.into(), value))
+}
+
+fn parse(args: impl IntoIterator<Item = String>) -> Result<Command, String> {
+    let mut parsed = Args::default();
+    let mut positional = Vec::new();
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if arg == "--" {
+            positional.extend(args.by_ref());
+            break;
+        }
+        // split `--name=value` and `-nvalue`
+        let (option, inline) = if let Some(long) = arg.strip_prefix("--") {
+            match long.split_once('=') {
+                Some((name, value)) => (name.to_owned(), Some(value.to_owned())),
+                None => (long.to_owned(), None),
+            }
+        } else if arg.len() > 1 && arg.starts_with('-') {
+            let (flag, rest) = arg[1..].split_at(arg[1..].chars().next().map_or(0, char::len_utf8));
+            (
+                flag.to_owned(),
+                Some(rest.to_owned()).filter(|rest| !rest.is_empty()),
+            )
+        } else {
+            positional.push(arg);
+            continue;
+        };
+        let mut value = || {
+            inline
+                .clone()
+                .or_else(|| args.next())
+                .ok_or_else(|| format!("missing value for {}", arg))
+        };
+        match option.as_str() {
+            "u" | "urgency" => {
+                parsed.urgency =
+                    Some(Urgency::try_from(value()?.as_str()).map_err(|error| error.to_string())?)
+            }
+            "t" | "expire-time" => {
+                parsed.timeout = Some(
+                    value()?
+                        .parse()
+                        .map_err(|_| format!("invalid timeout for {}", arg))?,
+                )
+            }
+            "a" | "app-name" => parsed.appname = Some(value()?),
+            "i" | "icon" => parsed.icon = Some(value()?),
+            "c" | "category" => parsed
+                .categories
+                .extend(value()?.split(',').map(String::from)),
+            "e" | "transient" => parsed.transient = true,
+            "h" | "hint" => parsed.hints.push(parse_hint(&value()?)?),
+            "p" | "print-id" => parsed.print_id = true,
+            "r" | "replace-id" => {
+                parsed.replace_id = Some(
+                    value()?
+                        .parse()
+                        .map_err(|_| format!("invalid id for {}", arg))?,
+                )
+            }
+            "w" | "wait" => parsed.wait = true,
+            "A" | "action" => {
+                let action = value()?;
+                let (name, label) = match action.split_once('=') {
+                    Some((name, label)) => (name.to_owned(), label.to_owned()),
+                    None => (parsed.actions.len().to_string(), action),
+                };
+                parsed.actions.push((name, label));
+            }
+            "?" | "help" => return Ok(Command::Help),
+            "v" | "version" => return Ok(Command::Version),
+            _ => return Err(format!("unknown option {}", arg)),
+        }
+    }
+    let mut positional = positional.into_iter();
+    parsed.summary = positional.next().ok_or("no summary specified")?;
+    parsed.body = positional.next();
+    if let Some(extra) = positional.next() {
+        return Err(format!("unexpected argument {:?}", extra));
+    }
+    Ok(Command::Notify(Box::new(parsed)))
+}
+
+fn notification(args: &Args) -> Notification {
+    let mut notification = Notification::new();
+    notification.summary(&args.summary);
+    if let Some(body) = &args.body {
+        notification.body(body);
+    }
+    if let Some(appname) = &args.appname {
+        notification.appname(appname);
+    }
+    if let Some(icon) = &args.icon {
+        notification.icon(icon);
+    }
+    if let Some(urgency) = args.urgency {
+        notification.priority(match urgency {
+            Urgency::Low => Priority::Low,
+            Urgency::Normal => Priority::Normal,
+            Urgency::Critical => Priority::High,
+        });
+    }
+    if let Some(timeout) = args.timeout {
+        notification.timeout(Timeout::from(timeout));
+    }
+    if let Some(id) = args.replace_id {
+        notification.id(id);
+    }
+    for (name, label) in &args.actions {
+        notification.action(name, label);
+    }
+    #[cfg(all(unix, not(target_os = "macos")))]
+    {
+        for category in &args.categories {
+            notification.hint(Hint::Category(category.clone()));
+        }
+        if args.transient {
+            notification.hint(Hint::Transient(true));
+        }
+        for hint in &args.hints {
+            notification.hint(hint.clone());
+        }
+    }
+    #[cfg(not(all(unix, not(target_os = "macos"))))]
+    if !args.categories.is_empty() || args.transient || !args.hints.is_empty() {
+        eprintln!("notify-send: categories, --transient and hints only work with XDG notification servers");
+    }
+    notification
+}
+
+fn main() -> ExitCode {
+    let args = match parse(std::env::args().skip(1)) {
+        Ok(Command::Notify(args)) => args,
+        Ok(Command::Help) => {
+            println!("{}", USAGE);
+            return ExitCode::SUCCESS;
+        }
+        Ok(Command::Version) => {
+            println!("notify-send (notify-rust) {}", env!("CARGO_PKG_VERSION"));
+            return ExitCode::SUCCESS;
+        }
+        Err(error) => {
+            eprintln!("notify-send: {}\n\n{}", error, USAGE);
+            return ExitCode::FAILURE;
+        }
+    };
+    let handle = match notification(&args).show() {
+        Ok(handle) => handle,
+        Err(error) => {
+            eprintln!("notify-send: {}", error);
+            return ExitCode::FAILURE;
+        }
+    };
+    if args.print_id {
+        println!("{}", handle.id());
+    }
+    if args.wait || !args.actions.is_empty() {
+        let result = handle.wait_for_action_result();
+        if let ActionResult::Action(action) = &result {
+            println!("{}", action);
+        }
+        return ExitCode::from(exit_status(&result));
+    }
+    ExitCode::SUCCESS
+}
+
+/// Tells scripts waiting for the notification what happened to it.
+fn exit_status(result: &ActionResult) -> u8 {
+    match result {
+        ActionResult::Action(_) => 0,
+        ActionResult::Closed(CloseReason::Expired) | ActionResult::Timeout => EXPIRED,
+        ActionResult::Closed(_) => CLOSED,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_args(args: &[&str]) -> Result<Command, String> {
+        parse(args.iter().map(|arg| arg.to_string()))
+    }
+
+    #[test]
+    fn parses_notify_send_flags() {
+        let parsed = parse_args(&[
+            "-u",
+            "critical",
+            "--expire-time=5000",
+            "-abackup",
+            "-i",
+            "drive-harddisk",
+            "-h",
+            "int:x:10",
+            "-h",
+            "string:x-custom:yes",
+            "--category=device,device.error",
+            "-e",
+            "-A",
+            "retry=Retry",
+            "-A",
+            "Ignore",
+            "-p",
+            "disk failed",
+            "sda is gone",
+        ]);
+        let expected = Args {
+            summary: "disk failed".into(),
+            body: Some("sda is gone".into()),
+            urgency: Some(Urgency::Critical),
+            timeout: Some(5000),
+            appname: Some("backup".into()),
+            icon: Some("drive-harddisk".into()),
+            categories: vec!["device".into(), "device.error".into()],
+            transient: true,
+            hints: vec![Hint::X(10), Hint::Custom("x-custom".into(), "yes".into())],
+            print_id: true,
+            actions: vec![
+                ("retry".into(), "Retry".into()),
+                ("1".into(), "Ignore".into()),
+            ],
+            ..Args::default()
+        };
+        assert_eq!(parsed, Ok(Command::Notify(Box::new(expected))));
+    }
+
+    #[test]
+    fn parses_typed_hints() {
+        assert_eq!(
+            parse_hint("double:value:0.5"),
+            Ok(Hint::CustomValue("value".into(), HintValue::F64(0.5)))
+        );
+        assert_eq!(
+            parse_hint("boolean:resident:true"),
+            Ok(Hint::Resident(true))
+        );
+        assert!(parse_hint("byte:level:300").is_err());
+        assert!(parse_hint("string:missing-value").is_err());
+    }
+
+    #[test]
+    fn distinguishes_outcomes() {
+        assert_eq!(exit_status(&ActionResult::Action("retry".into())), 0);
+        assert_eq!(
+            exit_status(&ActionResult::Closed(CloseReason::Dismissed)),
+            CLOSED
+        );
+        assert_eq!(
+            exit_status(&ActionResult::Closed(CloseReason::CloseAction)),
+            CLOSED
+        );
+        assert_eq!(
+            exit_status(&ActionResult::Closed(CloseReason::Expired)),
+            EXPIRED
+        );
+    }
+
+    #[test]
+    fn rejects_bad_invocations() {
+        assert!(parse_args(&[]).is_err());
+        assert!(parse_args(&["--frobnicate", "hi"]).is_err());
+        assert!(parse_args(&["-t"]).is_err());
+        assert_eq!(parse_args(&["--help"]), Ok(Command::Help));
+        assert_eq!(parse_args(&["-v", "ignored"]), Ok(Command::Version));
+    }
+}
@@ -0,0 +1,224 @@
+//! Typed markup for notification bodies, see [`Body`].
+
+use crate::xml::escape_text;
+
+/// A notification body built from pieces of text, instead of a hand written markup string.
+///
+/// Text is escaped, so user provided strings can't break the markup.
+/// [`Notification::rich_body`](`crate::Notification::rich_body`) sends markup
+/// only to servers that render it and plain text to all others.
+///
+/// ```no_run
+/// # use notify_rust::*;
+/// Notification::new()
+///     .summary("build failed")
+///     .rich_body(
+///         Body::new()
+///             .bold("3 tests")
+///             .text(" failed on ")
+///             .italic("main")
+///             .line()
+///             .link("https://ci.example.com/builds/42", "see the log"),
+///     )
+///     .show()?;
+/// # Ok::<(), notify_rust::error::Error>(())
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Body {
+    spans: Vec<Span>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Span {
+    Text(String),
+    Bold(String),
+    Italic(String),
+    Underline(String),
+    Link {
+        url: String,
+        label: String,
+    },
+    /// A link that falls back to the bare `url`, see [`Notification::hyperlink`](`crate::Notification::hyperlink`).
+    #[cfg(all(unix, not(target_os = "macos")))]
+    UrlLink {
+        url: String,
+        label: String,
+    },
+    /// Markup written by hand, sent as it is to every server.
+    #[cfg(all(unix, not(target_os = "macos")))]
+    Markup(String),
+    LineBreak,
+}
+
+impl Body {
+    /// Creates an empty body.
+    pub fn new() -> Body {
+        Body::default()
+    }
+
+    /// Appends plain text.
+    pub fn text(&mut self, text: &str) -> &mut Body {
+        self.push(Span::Text(text.to_owned()))
+    }
+
+    /// Appends bold text.
+    pub fn bold(&mut self, text: &str) -> &mut Body {
+        self.push(Span::Bold(text.to_owned()))
+    }
+
+    /// Appends italic text.
+    pub fn italic(&mut self, text: &str) -> &mut Body {
+        self.push(Span::Italic(text.to_owned()))
+    }
+
+    /// Appends underlined text.
+    pub fn underline(&mut self, text: &str) -> &mut Body {
+        self.push(Span::Underline(text.to_owned()))
+    }
+
+    /// Appends a link to `url` showing `label`.
+    pub fn link(&mut self, url: &str, label: &str) -> &mut Body {
+        self.push(Span::Link {
+            url: url.to_owned(),
+            label: label.to_owned(),
+        })
+    }
+
+    /// Starts a new line, which servers treat as a new paragraph.
+    pub fn line(&mut self) -> &mut Body {
+        self.push(Span::LineBreak)
+    }
+
+    /// Appends a link to `url` showing `label`, which is just the `url` on servers without links.
+    #[cfg(all(unix, not(target_os = "macos")))]
+    pub(crate) fn url_link(&mut self, url: &str, label: &str) -> &mut Body {
+        self.push(Span::UrlLink {
+            url: url.to_owned(),
+            label: label.to_owned(),
+        })
+    }
+
+    /// Appends `markup` as it is.
+    #[cfg(all(unix, not(target_os = "macos")))]
+    pub(crate) fn markup(&mut self, markup: &str) -> &mut Body {
+        self.push(Span::Markup(markup.to_owned()))
+    }
+
+    /// Whether there is [`markup`](`Body::markup`) written by hand, which isn't plain text even without tags.
+    #[cfg(all(unix, not(target_os = "macos")))]
+    pub(crate) fn has_markup(&self) -> bool {
+        self.spans
+            .iter()
+            .any(|span| matches!(span, Span::Markup(_)))
+    }
+
+    fn push(&mut self, span: Span) -> &mut Body {
+        self.spans.push(span);
+        self
+    }
+
+    /// The body as markup, with links for servers advertising `"body-hyperlinks"`
+    /// and `label (url)` for all others.
+    pub fn to_markup(&self, hyperlinks: bool) -> String {
+        let mut markup = String::new();
+        for span in &self.spans {
+            match span {
+                Span::Text(text) => markup.push_str(&escape_text(text)),
+                Span::Bold(text) => tagged(&mut markup, "b", text),
+                Span::Italic(text) => tagged(&mut markup, "i", text),
+                Span::Underline(text) => tagged(&mut markup, "u", text),
+                Span::Link { url, label } if hyperlinks => link(&mut markup, url, label),
+                Span::Link { url, label } => markup.push_str(&escape_text(&plain_link(url, label))),
+                #[cfg(all(unix, not(target_os = "macos")))]
+                Span::UrlLink { url, label } if hyperlinks => link(&mut markup, url, label),
+                #[cfg(all(unix, not(target_os = "macos")))]
+                Span::UrlLink { url, .. } => markup.push_str(&escape_text(url)),
+                #[cfg(all(unix, not(target_os = "macos")))]
+                Span::Markup(raw) => markup.push_str(raw),
+                Span::LineBreak => markup.push('\n'),
+            }
+        }
+        markup
+    }
+
+    /// The body without any markup, for servers that show it literally.
+    pub fn to_plain_text(&self) -> String {
+        let mut text = String::new();
+        for span in &self.spans {
+            match span {
+                Span::Text(plain)
+                | Span::Bold(plain)
+                | Span::Italic(plain)
+                | Span::Underline(plain) => text.push_str(plain),
+                Span::Link { url, label } => text.push_str(&plain_link(url, label)),
+                #[cfg(all(unix, not(target_os = "macos")))]
+                Span::UrlLink { url, .. } => text.push_str(url),
+                #[cfg(all(unix, not(target_os = "macos")))]
+                Span::Markup(raw) => text.push_str(raw),
+                Span::LineBreak => text.push('\n'),
+            }
+        }
+        text
+    }
+}
+
+fn tagged(markup: &mut String, tag: &str, text: &str) {
+    markup.push('<');
+    markup.push_str(tag);
+    markup.push('>');
+    markup.push_str(&escape_text(text));
+    markup.push_str("</");
+    markup.push_str(tag);
+    markup.push('>');
+}
+
+fn link(markup: &mut String, url: &str, label: &str) {
+    markup.push_str("<a href=\"");
+    // attribute values need their quotes escaped as well
+    markup.push_str(&escape_text(url).replace('"', "&quot;"));
+    markup.push_str("\">");
+    markup.push_str(&escape_text(label));
+    markup.push_str("</a>");
+}
+
+fn plain_link(url: &str, label: &str) -> String {
+    if label.is_empty() || label == url {
+        url.to_owned()
+    } else {
+        format!("{} ({})", label, url)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn example() -> Body {
+        Body::new()
+            .bold("3 tests")
+            .text(" failed <again>")
+            .line()
+            .link("https://ci.example.com/?a=1&b=\"2\"", "log")
+            .clone()
+    }
+
+    #[test]
+    fn markup() {
+        assert_eq!(
+            example().to_markup(true),
+            "<b>3 tests</b> failed &lt;again&gt;\n<a href=\"https://ci.example.com/?a=1&amp;b=&quot;2&quot;\">log</a>"
+        );
+        assert_eq!(
+            example().to_markup(false),
+            "<b>3 tests</b> failed &lt;again&gt;\nlog (https://ci.example.com/?a=1&amp;b=\"2\")"
+        );
+    }
+
+    #[test]
+    fn plain_text() {
+        assert_eq!(
+            example().to_plain_text(),
+            "3 tests failed <again>\nlog (https://ci.example.com/?a=1&b=\"2\")"
+        );
+    }
+}
@@ -0,0 +1,276 @@
+//! An owned alternative to the `&mut Notification` chain, see [`NotificationBuilder`].
+
+use std::marker::PhantomData;
+
+use crate::{
+    action::Action, error::*, notification::Notification, sound::Sound, timeout::Timeout,
+    urgency::Priority, NotificationHandle,
+};
+
+#[cfg(all(unix, not(target_os = "macos")))]
+use crate::{hints::Hint, urgency::Urgency};
+
+/// Builds a [`Notification`] by value, created by [`Notification::builder()`].
+///
+/// Unlike the `&mut` chain on [`Notification`] this can end an expression,
+/// so helpers can return a notification without [`finalize()`](`Notification::finalize`) cloning it.
+///
+/// ```no_run
+/// # use notify_rust::*;
+/// fn build_failed(job: &str) -> Notification {
+///     Notification::builder()
+///         .summary("build failed")
+///         .body(format!("{job} did not finish"))
+///         .icon("dialog-error")
+///         .build()
+/// }
+/// build_failed("docs").show()?;
+/// # Ok::<(), notify_rust::error::Error>(())
+/// ```
+///
+/// Platform specific settings are made with [`with()`](`NotificationBuilder::with`).
+///
+/// The type parameter tracks whether a summary was set, see [`Notification::strict_builder()`].
+/// Setting it moves the builder into [`HasSummary`](`state::HasSummary`).
+#[derive(Debug, Clone)]
+#[must_use]
+pub struct NotificationBuilder<S = state::Unchecked> {
+    notification: Notification,
+    state: PhantomData<S>,
+}
+
+/// States of a [`NotificationBuilder`].
+pub mod state {
+    /// Built by [`Notification::builder()`](`crate::Notification::builder`), can be shown at any time.
+    #[derive(Debug, Clone, Copy)]
+    pub enum Unchecked {}
+
+    /// Built by [`Notification::strict_builder()`](`crate::Notification::strict_builder`), needs a summary before it can be shown.
+    #[derive(Debug, Clone, Copy)]
+    pub enum NoSummary {}
+
+    /// The summary is set, so it can be shown.
+    #[derive(Debug, Clone, Copy)]
+    pub enum HasSummary {}
+
+    /// States in which [`build()`](`super::NotificationBuilder::build`) and [`show()`](`super::NotificationBuilder::show`) are available.
+    pub trait Ready: sealed::Sealed {}
+    impl Ready for Unchecked {}
+    impl Ready for HasSummary {}
+
+    mod sealed {
+        pub trait Sealed {}
+        impl Sealed for super::Unchecked {}
+        impl Sealed for super::HasSummary {}
+    }
+}
+
+impl NotificationBuilder {
+    /// Same as [`Notification::builder()`].
+    pub fn new() -> NotificationBuilder {
+        NotificationBuilder::from(Notification::new())
+    }
+}
+
+impl Default for NotificationBuilder {
+    fn default() -> Self {
+        NotificationBuilder::new()
+    }
+}
+
+impl NotificationBuilder<state::NoSummary> {
+    /// Same as [`Notification::strict_builder()`].
+    pub fn strict() -> NotificationBuilder<state::NoSummary> {
+        NotificationBuilder {
+            notification: Notification::new(),
+            state: PhantomData,
+        }
+    }
+}
+
+impl<S> NotificationBuilder<S> {
+    /// See [`Notification::appname()`].
+    pub fn appname(mut self, appname: impl Into<String>) -> NotificationBuilder<S> {
+        self.notification.appname(appname);
+        self
+    }
+
+    /// See [`Notification::summary()`].
+    pub fn summary(mut self, summary: impl Into<String>) -> NotificationBuilder<state::HasSummary> {
+        self.notification.summary(summary);
+        NotificationBuilder {
+            notification: self.notification,
+            state: PhantomData,
+        }
+    }
+
+    /// See [`Notification::subtitle()`].
+    pub fn subtitle(mut self, subtitle: impl Into<String>) -> NotificationBuilder<S> {
+        self.notification.subtitle(subtitle);
+        self
+    }
+
+    /// See [`Notification::body()`].
+    pub fn body(mut self, body: impl Into<String>) -> NotificationBuilder<S> {
+        self.notification.body(body);
+        self
+    }
+
+    /// See [`Notification::icon()`].
+    pub fn icon(mut self, icon: impl Into<String>) -> NotificationBuilder<S> {
+        self.notification.icon(icon);
+        self
+    }
+
+    /// See [`Notification::auto_icon()`].
+    pub fn auto_icon(mut self) -> NotificationBuilder<S> {
+        self.notification.auto_icon();
+        self
+    }
+
+    /// See [`Notification::sound()`].
+    pub fn sound(mut self, sound: Sound) -> NotificationBuilder<S> {
+        self.notification.sound(sound);
+        self
+    }
+
+    /// See [`Notification::timeout()`].
+    pub fn timeout<T: Into<Timeout>>(mut self, timeout: T) -> NotificationBuilder<S> {
+        self.notification.timeout(timeout);
+        self
+    }
+
+    /// See [`Notification::priority()`].
+    pub fn priority(mut self, priority: Priority) -> NotificationBuilder<S> {
+        self.notification.priority(priority);
+        self
+    }
+
+    /// See [`Notification::action()`].
+    pub fn action(
+        mut self,
+        identifier: impl Into<String>,
+        label: impl Into<String>,
+    ) -> NotificationBuilder<S> {
+        self.notification.action(identifier, label);
+        self
+    }
+
+    /// See [`Notification::default_action()`].
+    pub fn default_action(mut self, label: impl Into<String>) -> NotificationBuilder<S> {
+        self.notification.default_action(label);
+        self
+    }
+
+    /// See [`Notification::add_action()`].
+    pub fn add_action(mut self, action: Action) -> NotificationBuilder<S> {
+        self.notification.add_action(action);
+        self
+    }
+
+    /// See [`Notification::id()`].
+    pub fn id(mut self, id: u32) -> NotificationBuilder<S> {
+        self.notification.id(id);
+        self
+    }
+
+    /// See [`Notification::hint()`].
+    #[cfg(all(unix, not(target_os = "macos")))]
+    pub fn hint(mut self, hint: Hint) -> NotificationBuilder<S> {
+        self.notification.hint(hint);
+        self
+    }
+
+    /// See [`Notification::urgency()`].
+    #[cfg(all(unix, not(target_os = "macos")))]
+    pub fn urgency(mut self, urgency: Urgency) -> NotificationBuilder<S> {
+        self.notification.urgency(urgency);
+        self
+    }
+
+    /// Applies any of the `&mut` builder methods, e.g. the platform specific ones.
+    ///
+    /// ```no_run
+    /// # use notify_rust::*;
+    /// let notification = Notification::builder()
+    ///     .summary("backup done")
+    ///     .with(|n| {
+    ///         #[cfg(all(unix, not(target_os = "macos")))]
+    ///         n.transient(true);
+    ///     })
+    ///     .build();
+    /// ```
+    pub fn with(mut self, apply: impl FnOnce(&mut Notification)) -> NotificationBuilder<S> {
+        apply(&mut self.notification);
+        self
+    }
+}
+
+impl<S: state::Ready> NotificationBuilder<S> {
+    /// Hands over the built notification.
+    pub fn build(self) -> Notification {
+        self.notification
+    }
+
+    /// Builds and shows the notification, see [`Notification::show()`].
+    pub fn show(self) -> Result<NotificationHandle> {
+        self.notification.show()
+    }
+}
+
+impl From<Notification> for NotificationBuilder {
+    fn from(notification: Notification) -> Self {
+        NotificationBuilder {
+            notification,
+            state: PhantomData,
+        }
+    }
+}
+
+impl<S: state::Ready> From<NotificationBuilder<S>> for Notification {
+    fn from(builder: NotificationBuilder<S>) -> Self {
+        builder.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn builds_like_the_mut_chain() {
+        let built = Notification::builder()
+            .appname("builder")
+            .summary("summary")
+            .body("body")
+            .icon("icon")
+            .action("open", "Open")
+            .id(7)
+            .with(|n| {
+                n.subtitle("subtitle");
+            })
+            .build();
+
+        let mut chained = Notification::new();
+        chained
+            .appname("builder")
+            .summary("summary")
+            .body("body")
+            .icon("icon")
+            .action("open", "Open")
+            .id(7)
+            .subtitle("subtitle");
+
+        assert_eq!(built, chained);
+    }
+
+    #[test]
+    fn strict_builder_keeps_the_summary() {
+        let strict: NotificationBuilder<state::NoSummary> =
+            Notification::strict_builder().body("body");
+        let built = strict.summary("summary").icon("icon").build();
+        assert_eq!(built.summary, "summary");
+        assert_eq!(built.body, "body");
+        assert_eq!(built.icon, "icon");
+    }
+}
@@ -0,0 +1,320 @@
+//! Records notifications instead of showing them, for testing code that sends notifications, see [`Capture`].
+
+#[cfg(all(unix, not(target_os = "macos")))]
+use crate::{
+    action::{ActionResponse, ActionResponseHandler},
+    error::*,
+};
+use crate::{
+    action::{ActionResult, CloseReason},
+    notification::Notification,
+};
+
+#[cfg(all(unix, not(target_os = "macos")))]
+use std::sync::mpsc;
+use std::{
+    cell::Cell,
+    collections::VecDeque,
+    sync::{Mutex, MutexGuard, PoisonError},
+};
+
+/// What was recorded since [`Capture::start`], `None` while nothing is captured.
+static RECORD: Mutex<Option<Record>> = Mutex::new(None);
+
+/// Held by the running [`Capture`], so tests capturing at the same time take turns.
+static EXCLUSIVE: Mutex<()> = Mutex::new(());
+
+thread_local! {
+    /// Whether this thread holds [`EXCLUSIVE`], waiting for it again would never end.
+    static CAPTURING: Cell<bool> = const { Cell::new(false) };
+}
+
+#[derive(Debug, Default)]
+struct Record {
+    shown: Vec<Notification>,
+    updated: Vec<Notification>,
+    closed: Vec<Notification>,
+    responses: VecDeque<ActionResult>,
+    last_id: u32,
+}
+
+fn record() -> MutexGuard<'static, Option<Record>> {
+    RECORD.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Records every notification shown while it is alive, instead of sending it to the notification server.
+///
+/// Their handles record updates and closing too, and answer waiting for an action
+/// with what was scripted via [`respond_with`](`Capture::respond_with`),
+/// so code sending notifications can be tested without a session bus.
+///
+/// ```
+/// # use notify_rust::*;
+/// let capture = Capture::start();
+/// capture.respond_with(ActionResult::Action("retry".into()));
+///
+/// let handle = Notification::new()
+///     .summary("upload failed")
+///     .action("retry", "Retry")
+///     .show()?;
+/// assert_eq!(handle.wait_for_action_result(), ActionResult::Action("retry".into()));
+///
+/// assert_eq!(capture.shown()[0].summary, "upload failed");
+/// # Ok::<(), notify_rust::error::Error>(())
+/// ```
+///
+/// Capturing is process wide, notifications shown by other threads are recorded as well.
+/// Starting a second capture waits until the first one is dropped,
+/// unless the first one was started by the same thread, which would wait forever, so that panics.
+#[derive(Debug)]
+pub struct Capture {
+    _exclusive: MutexGuard<'static, ()>,
+}
+
+impl Capture {
+    /// Starts recording, until the returned `Capture` is dropped.
+    ///
+    /// # Panics
+    ///
+    /// If this thread is capturing already.
+    pub fn start() -> Capture {
+        assert!(
+            !CAPTURING.with(Cell::get),
+            "this thread is capturing already, drop its Capture before starting another one"
+        );
+        let exclusive = EXCLUSIVE.lock().unwrap_or_else(PoisonError::into_inner);
+        CAPTURING.with(|capturing| capturing.set(true));
+        *record() = Some(Record::default());
+        Capture {
+            _exclusive: exclusive,
+        }
+    }
+
+    /// Every notification shown so far, in order.
+    pub fn shown(&self) -> Vec<Notification> {
+        self.read(|record| record.shown.clone())
+    }
+
+    /// Every notification [updated](`crate::NotificationHandle::update`) so far, as it was updated to.
+    pub fn updated(&self) -> Vec<Notification> {
+        self.read(|record| record.updated.clone())
+    }
+
+    /// Every notification [closed](`crate::NotificationHandle::close`) so far.
+    pub fn closed(&self) -> Vec<Notification> {
+        self.read(|record| record.closed.clone())
+    }
+
+    /// Queues what the next wait for an action on a captured notification returns, as if the user did it.
+    ///
+    /// Once nothing is queued, waiting returns right away with [`CloseReason::Expired`], like the terminal fallback.
+    pub fn respond_with(&self, result: ActionResult) -> &Capture {
+        if let Some(record) = record().as_mut() {
+            record.responses.push_back(result);
+        }
+        self
+    }
+
+    /// Forgets everything recorded so far, including queued responses.
+    pub fn clear(&self) {
+        *record() = Some(Record::default());
+    }
+
+    fn read<T>(&self, read: impl FnOnce(&Record) -> T) -> T {
+        read(record().get_or_insert_with(Record::default))
+    }
+}
+
+impl Drop for Capture {
+    fn drop(&mut self) {
+        *record() = None;
+        CAPTURING.with(|capturing| capturing.set(false));
+    }
+}
+
+/// Whether a [`Capture`] is running.
+#[cfg(all(unix, not(target_os = "macos")))]
+pub(crate) fn is_running() -> bool {
+    record().is_some()
+}
+
+/// Records `notification` if a [`Capture`] is running and returns its id, `None` means it has to be shown for real.
+pub(crate) fn record_shown(notification: &Notification) -> Option<u32> {
+    let mut record = record();
+    let record = record.as_mut()?;
+    #[cfg(all(unix, not(target_os = "macos")))]
+    let id = crate::xdg::tags::replaces_id(notification);
+    #[cfg(not(all(unix, not(target_os = "macos"))))]
+    let id = notification.id.unwrap_or(0);
+    let id = match id {
+        0 => {
+            record.last_id += 1;
+            record.last_id
+        }
+        id => id,
+    };
+    #[cfg(all(unix, not(target_os = "macos")))]
+    crate::xdg::tags::remember(id, notification);
+    record.shown.push(notification.clone());
+    Some(id)
+}
+
+/// Records that a captured notification was updated to `notification`.
+pub(crate) fn record_update(notification: &Notification) {
+    if let Some(record) = record().as_mut() {
+        record.updated.push(notification.clone());
+    }
+}
+
+/// Records that a captured notification was closed.
+pub(crate) fn record_close(notification: &Notification) {
+    if let Some(record) = record().as_mut() {
+        record.closed.push(notification.clone());
+    }
+}
+
+/// What the user did with a captured notification, as queued via [`Capture::respond_with`].
+pub(crate) fn next_response() -> ActionResult {
+    record()
+        .as_mut()
+        .and_then(|record| record.responses.pop_front())
+        .unwrap_or(ActionResult::Closed(CloseReason::Expired))
+}
+
+/// Records `notification` if a [`Capture`] is running, `None` means it has to be shown for real.
+#[cfg(all(unix, not(target_os = "macos")))]
+pub(crate) fn show(notification: &Notification) -> Option<CaptureNotificationHandle> {
+    Some(CaptureNotificationHandle {
+        id: record_shown(notification)?,
+        notification: notification.clone(),
+    })
+}
+
+/// A handle for the notification shown before as `notification`'s id, if a [`Capture`] is running.
+#[cfg(all(unix, not(target_os = "macos")))]
+pub(crate) fn resume(notification: &Notification) -> Option<CaptureNotificationHandle> {
+    if !is_running() {
+        return None;
+    }
+    Some(CaptureNotificationHandle {
+        id: notification.id.unwrap_or(0),
+        notification: notification.clone(),
+    })
+}
+
+/// A notification that was recorded by a [`Capture`].
+#[cfg(all(unix, not(target_os = "macos")))]
+#[derive(Debug)]
+pub(crate) struct CaptureNotificationHandle {
+    pub(crate) id: u32,
+    pub(crate) notification: Notification,
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+impl CaptureNotificationHandle {
+    pub fn wait_for_action(self, invocation_closure: impl ActionResponseHandler) {
+        match next_response() {
+            ActionResult::Action(action) => {
+                invocation_closure.call(&ActionResponse::Custom(&action));
+            }
+            ActionResult::Closed(reason) => {
+                invocation_closure.call(&ActionResponse::Closed(reason));
+            }
+            ActionResult::Timeout => {}
+        }
+    }
+
+    pub fn listen(&self) -> mpsc::Receiver<ActionResult> {
+        let (sender, receiver) = mpsc::channel();
+        let response = next_response();
+        let closed = matches!(response, ActionResult::Closed(_));
+        let _ = sender.send(response);
+        if !closed {
+            let _ = sender.send(ActionResult::Closed(CloseReason::Expired));
+        }
+        receiver
+    }
+
+    pub fn update(&mut self) -> Result<()> {
+        record_update(&self.notification);
+        Ok(())
+    }
+
+    pub fn close(self) -> Result<()> {
+        record_close(&self.notification);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_and_responds() {
+        let capture = Capture::start();
+        capture.respond_with(ActionResult::Action("retry".into()));
+
+        let mut handle = Notification::new().summary("upload failed").show().unwrap();
+        handle.body("3 files left");
+        handle.update().unwrap();
+        let id = handle.id();
+        assert_ne!(id, 0);
+
+        let mut invoked = None;
+        handle.wait_for_action(|action| invoked = Some(action.to_owned()));
+        assert_eq!(invoked.as_deref(), Some("retry"));
+
+        Notification::new()
+            .summary("done")
+            .show()
+            .unwrap()
+            .close()
+            .unwrap();
+
+        let summaries = |notifications: Vec<Notification>| {
+            notifications
+                .into_iter()
+                .map(|notification| notification.summary)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(summaries(capture.shown()), ["upload failed", "done"]);
+        assert_eq!(capture.updated()[0].body, "3 files left");
+        assert_eq!(summaries(capture.closed()), ["done"]);
+
+        capture.clear();
+        assert!(capture.shown().is_empty());
+        assert_eq!(
+            Notification::new().show().unwrap().wait_for_action_result(),
+            ActionResult::Closed(CloseReason::Expired)
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "capturing already")]
+    fn refuses_to_wait_for_itself() {
+        let _capture = Capture::start();
+        let _second = Capture::start();
+    }
+
+    #[test]
+    #[cfg(all(unix, not(target_os = "macos")))]
+    fn resumes_from_token() {
+        let capture = Capture::start();
+        let token = Notification::new()
+            .appname("updater")
+            .summary("installing")
+            .show()
+            .unwrap()
+            .to_token()
+            .unwrap();
+
+        let mut handle = crate::NotificationHandle::from_token(&token).unwrap();
+        handle.summary("installed");
+        handle.update().unwrap();
+        let updated = &capture.updated()[0];
+        assert_eq!(updated.appname, "updater");
+        assert_eq!(updated.summary, "installed");
+        assert_eq!(updated.id, Some(1));
+    }
+}
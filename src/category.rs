@@ -0,0 +1,160 @@
+use std::fmt;
+
+/// The type of event a notification is about, servers may pick icons, sounds or grouping by it.
+///
+/// # Specification
+/// As listed under [Categories](https://specifications.freedesktop.org/notification-spec/latest/categories.html),
+/// a server silently ignores categories it doesn't know, so prefer these over typing them out.
+///
+/// # Example
+/// ```no_run
+/// # use notify_rust::*;
+/// # fn _doc() -> Result<(), Box<dyn std::error::Error>> {
+/// Notification::new()
+///     .summary("new mail")
+///     .category(Category::EmailArrived)
+///     .show()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Eq, PartialEq, Hash, Clone, Debug)]
+pub enum Category {
+    /// `"call"`, a generic audio or video call notification that doesn't fit into any other category.
+    Call,
+    /// `"call.ended"`, an audio or video call was ended.
+    CallEnded,
+    /// `"call.incoming"`, an audio or video call is incoming.
+    CallIncoming,
+    /// `"call.unanswered"`, an incoming audio or video call was not answered.
+    CallUnanswered,
+    /// `"device"`, a generic device-related notification that doesn't fit into any other category.
+    Device,
+    /// `"device.added"`, a device, such as a USB device, was added to the system.
+    DeviceAdded,
+    /// `"device.error"`, a device had some kind of error.
+    DeviceError,
+    /// `"device.removed"`, a device, such as a USB device, was removed from the system.
+    DeviceRemoved,
+    /// `"email"`, a generic e-mail-related notification that doesn't fit into any other category.
+    Email,
+    /// `"email.arrived"`, a new e-mail notification.
+    EmailArrived,
+    /// `"email.bounced"`, a notification stating that an e-mail has bounced.
+    EmailBounced,
+    /// `"im"`, a generic instant message-related notification that doesn't fit into any other category.
+    Im,
+    /// `"im.error"`, an instant message error notification.
+    ImError,
+    /// `"im.received"`, a received instant message notification.
+    ImReceived,
+    /// `"network"`, a generic network notification that doesn't fit into any other category.
+    Network,
+    /// `"network.connected"`, a network connection notification, such as successful sign-on to a network service.
+    NetworkConnected,
+    /// `"network.disconnected"`, a network disconnected notification.
+    NetworkDisconnected,
+    /// `"network.error"`, a network-related or connection-related error.
+    NetworkError,
+    /// `"presence"`, a generic presence change notification that doesn't fit into any other category.
+    Presence,
+    /// `"presence.offline"`, a contact disconnected from the network.
+    PresenceOffline,
+    /// `"presence.online"`, a contact signed on to the network.
+    PresenceOnline,
+    /// `"transfer"`, a generic file transfer or download notification that doesn't fit into any other category.
+    Transfer,
+    /// `"transfer.complete"`, a file transfer or download complete notification.
+    TransferComplete,
+    /// `"transfer.error"`, a file transfer or download error.
+    TransferError,
+    /// Anything not in the specification, usually prefixed with `x-vendor.`.
+    Custom(String),
+}
+
+impl Category {
+    /// The name servers know this category by.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Category::Call => "call",
+            Category::CallEnded => "call.ended",
+            Category::CallIncoming => "call.incoming",
+            Category::CallUnanswered => "call.unanswered",
+            Category::Device => "device",
+            Category::DeviceAdded => "device.added",
+            Category::DeviceError => "device.error",
+            Category::DeviceRemoved => "device.removed",
+            Category::Email => "email",
+            Category::EmailArrived => "email.arrived",
+            Category::EmailBounced => "email.bounced",
+            Category::Im => "im",
+            Category::ImError => "im.error",
+            Category::ImReceived => "im.received",
+            Category::Network => "network",
+            Category::NetworkConnected => "network.connected",
+            Category::NetworkDisconnected => "network.disconnected",
+            Category::NetworkError => "network.error",
+            Category::Presence => "presence",
+            Category::PresenceOffline => "presence.offline",
+            Category::PresenceOnline => "presence.online",
+            Category::Transfer => "transfer",
+            Category::TransferComplete => "transfer.complete",
+            Category::TransferError => "transfer.error",
+            Category::Custom(custom) => custom,
+        }
+    }
+}
+
+impl From<&str> for Category {
+    fn from(name: &str) -> Self {
+        match name {
+            "call" => Category::Call,
+            "call.ended" => Category::CallEnded,
+            "call.incoming" => Category::CallIncoming,
+            "call.unanswered" => Category::CallUnanswered,
+            "device" => Category::Device,
+            "device.added" => Category::DeviceAdded,
+            "device.error" => Category::DeviceError,
+            "device.removed" => Category::DeviceRemoved,
+            "email" => Category::Email,
+            "email.arrived" => Category::EmailArrived,
+            "email.bounced" => Category::EmailBounced,
+            "im" => Category::Im,
+            "im.error" => Category::ImError,
+            "im.received" => Category::ImReceived,
+            "network" => Category::Network,
+            "network.connected" => Category::NetworkConnected,
+            "network.disconnected" => Category::NetworkDisconnected,
+            "network.error" => Category::NetworkError,
+            "presence" => Category::Presence,
+            "presence.offline" => Category::PresenceOffline,
+            "presence.online" => Category::PresenceOnline,
+            "transfer" => Category::Transfer,
+            "transfer.complete" => Category::TransferComplete,
+            "transfer.error" => Category::TransferError,
+            custom => Category::Custom(custom.to_owned()),
+        }
+    }
+}
+
+impl fmt::Display for Category {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trip() {
+        for category in [
+            Category::EmailArrived,
+            Category::DeviceRemoved,
+            Category::Custom("x-example.build".into()),
+        ] {
+            assert_eq!(Category::from(category.as_str()), category);
+        }
+        assert_eq!(Category::ImReceived.to_string(), "im.received");
+    }
+}
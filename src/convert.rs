@@ -0,0 +1,66 @@
+//! Quick conversions into notifications, see [`ToNotification`].
+
+use crate::{error::*, notification::Notification, NotificationHandle};
+
+/// Turns plain text into a [`Notification`], for scripts and examples that don't need the full builder.
+///
+/// A string becomes the summary, a pair of strings summary and body.
+///
+/// ```no_run
+/// use notify_rust::ToNotification;
+///
+/// "hello".show()?;
+/// ("backup", "42 files copied").show()?;
+///
+/// let mut notification = ("backup", "failed").to_notification();
+/// notification.icon("dialog-error").show()?;
+/// # Ok::<(), notify_rust::error::Error>(())
+/// ```
+pub trait ToNotification {
+    /// A new notification with this text.
+    fn to_notification(&self) -> Notification;
+
+    /// Shows the [converted](`ToNotification::to_notification`) notification right away.
+    fn show(&self) -> Result<NotificationHandle> {
+        self.to_notification().show()
+    }
+}
+
+impl ToNotification for str {
+    fn to_notification(&self) -> Notification {
+        let mut notification = Notification::new();
+        notification.summary(self);
+        notification
+    }
+}
+
+impl ToNotification for String {
+    fn to_notification(&self) -> Notification {
+        self.as_str().to_notification()
+    }
+}
+
+impl<S: AsRef<str>, B: AsRef<str>> ToNotification for (S, B) {
+    fn to_notification(&self) -> Notification {
+        let mut notification = Notification::new();
+        notification.summary(self.0.as_ref()).body(self.1.as_ref());
+        notification
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn converts_text() {
+        assert_eq!("hello".to_notification().summary, "hello");
+        assert_eq!(String::from("hello").to_notification().summary, "hello");
+
+        let notification = ("backup", String::from("done")).to_notification();
+        assert_eq!(
+            (notification.summary.as_str(), notification.body.as_str()),
+            ("backup", "done")
+        );
+    }
+}
@@ -0,0 +1,86 @@
+//! Process-wide settings every new notification starts out with, see [`set_defaults`].
+
+use crate::{notification::Notification, timeout::Timeout};
+
+use std::sync::RwLock;
+
+static DEFAULTS: RwLock<Option<Defaults>> = RwLock::new(None);
+
+/// What [`Notification::new()`] fills in, instead of the executable's name and empty fields.
+///
+/// Fields left at `None` keep their usual default.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Defaults {
+    /// Replaces the executable's name, see [`Notification::appname`].
+    pub appname: Option<String>,
+    /// See [`Notification::icon`].
+    pub icon: Option<String>,
+    /// See [`Notification::timeout`].
+    pub timeout: Option<Timeout>,
+}
+
+/// Makes every notification created afterwards start out with `defaults`,
+/// so libraries inside a larger application show notifications under its name and icon.
+///
+/// ```no_run
+/// # use notify_rust::*;
+/// set_defaults(Defaults {
+///     appname: Some("Photo Importer".into()),
+///     icon: Some("camera-photo".into()),
+///     ..Defaults::default()
+/// });
+/// Notification::new().summary("42 photos imported").show()?;
+/// # Ok::<(), notify_rust::error::Error>(())
+/// ```
+///
+/// Setting `Defaults::default()` goes back to the usual defaults.
+pub fn set_defaults(defaults: Defaults) {
+    if let Ok(mut current) = DEFAULTS.write() {
+        *current = Some(defaults).filter(|defaults| *defaults != Defaults::default());
+    }
+}
+
+impl Defaults {
+    fn apply_to(&self, notification: &mut Notification) {
+        if let Some(appname) = &self.appname {
+            notification.appname = appname.clone();
+        }
+        if let Some(icon) = &self.icon {
+            notification.icon = icon.clone();
+        }
+        if let Some(timeout) = self.timeout {
+            notification.timeout = timeout;
+        }
+    }
+}
+
+/// Fills in what [`set_defaults`] was given.
+pub(crate) fn apply(notification: &mut Notification) {
+    if let Ok(defaults) = DEFAULTS.read() {
+        if let Some(defaults) = defaults.as_ref() {
+            defaults.apply_to(notification);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // applies an explicit value, other tests create notifications meanwhile and expect the usual defaults
+    #[test]
+    fn fills_in_what_was_set() {
+        let mut notification = Notification::new();
+        let icon = notification.icon.clone();
+        Defaults {
+            appname: Some("defaults test".into()),
+            timeout: Some(Timeout::Never),
+            ..Defaults::default()
+        }
+        .apply_to(&mut notification);
+
+        assert_eq!(notification.appname, "defaults test");
+        assert_eq!(notification.icon, icon);
+        assert_eq!(notification.timeout, Timeout::Never);
+    }
+}
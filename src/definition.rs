@@ -0,0 +1,120 @@
+//! Notifications described by JSON or TOML documents, see [`Notification::from_reader`].
+
+use crate::{error::*, notification::Notification};
+
+use std::{io::Read, str::FromStr};
+
+impl Notification {
+    /// Reads a notification described by a JSON or TOML document, e.g. an alert template kept by ops tooling.
+    ///
+    /// Documents starting with `{` are JSON, needing the `json` feature, all others TOML, needing `toml`.
+    /// Fields are named like those of [`Notification`], missing ones keep their default.
+    ///
+    /// ```toml
+    /// summary = "disk almost full"
+    /// body = "/var is at 98%"
+    /// icon = "drive-harddisk"
+    /// timeout = "never"
+    /// hints = [{ urgency = "critical" }, { category = "device.error" }]
+    /// actions = [{ identifier = "clean", label = "Clean up" }]
+    /// ```
+    pub fn from_reader<R: Read>(mut reader: R) -> Result<Notification> {
+        let mut document = String::new();
+        reader
+            .read_to_string(&mut document)
+            .map_err(|error| ErrorKind::Conversion(error.to_string()))?;
+        document.parse()
+    }
+}
+
+/// Same as [`Notification::from_reader`].
+impl FromStr for Notification {
+    type Err = Error;
+
+    fn from_str(document: &str) -> Result<Notification> {
+        #[cfg_attr(not(all(unix, not(target_os = "macos"))), allow(unused_mut))]
+        let mut notification = if document.trim_start().starts_with('{') {
+            from_json(document)?
+        } else {
+            from_toml(document)?
+        };
+        // custom hints may be listed with all other hints, they're kept apart
+        #[cfg(all(unix, not(target_os = "macos")))]
+        for hint in std::mem::take(&mut notification.hints) {
+            notification.hint(hint);
+        }
+        Ok(notification)
+    }
+}
+
+#[cfg(feature = "json")]
+fn from_json(document: &str) -> Result<Notification> {
+    serde_json::from_str(document).map_err(|error| ErrorKind::Conversion(error.to_string()).into())
+}
+
+#[cfg(not(feature = "json"))]
+fn from_json(_document: &str) -> Result<Notification> {
+    Err(ErrorKind::Conversion("reading JSON needs the `json` feature".into()).into())
+}
+
+#[cfg(feature = "toml")]
+fn from_toml(document: &str) -> Result<Notification> {
+    toml::from_str(document).map_err(|error| ErrorKind::Conversion(error.to_string()).into())
+}
+
+#[cfg(not(feature = "toml"))]
+fn from_toml(_document: &str) -> Result<Notification> {
+    Err(ErrorKind::Conversion("reading TOML needs the `toml` feature".into()).into())
+}
+
+#[cfg(all(test, any(feature = "json", feature = "toml")))]
+mod tests {
+    use super::*;
+    use crate::Timeout;
+
+    #[test]
+    #[cfg(feature = "json")]
+    fn reads_json() {
+        let notification = Notification::from_reader(
+            r#"{
+                "summary": "disk almost full",
+                "timeout": { "milliseconds": 5000 },
+                "actions": [{ "identifier": "clean", "label": "Clean up" }]
+            }"#
+            .as_bytes(),
+        )
+        .unwrap();
+        assert_eq!(notification.summary, "disk almost full");
+        assert_eq!(notification.timeout, Timeout::Milliseconds(5000));
+        assert_eq!(notification.actions[0].label, "Clean up");
+        assert!("{ \"summary\": 1 }".parse::<Notification>().is_err());
+    }
+
+    #[test]
+    #[cfg(feature = "toml")]
+    fn reads_toml() {
+        let notification: Notification = r#"
+            summary = "disk almost full"
+            body = "/var is at 98%"
+            timeout = "never"
+            hints = [{ urgency = "critical" }, { custom = ["x-example", "1"] }]
+        "#
+        .parse()
+        .unwrap();
+        assert_eq!(notification.body, "/var is at 98%");
+        assert_eq!(notification.timeout, Timeout::Never);
+        #[cfg(all(unix, not(target_os = "macos")))]
+        {
+            use crate::{Hint, Urgency};
+            assert_eq!(
+                notification.hints,
+                [Hint::Urgency(Urgency::Critical)].into()
+            );
+            assert_eq!(
+                notification.get_hints().count(),
+                2,
+                "the custom hint is kept apart"
+            );
+        }
+    }
+}
@@ -0,0 +1,61 @@
+//! Hints for the extensions of [dunst](https://dunst-project.org).
+//!
+//! Other servers ignore them, so they are safe to send anywhere.
+//!
+//! ```no_run
+//! # use notify_rust::*;
+//! Notification::new()
+//!     .summary("copying")
+//!     .hint(dunst::progress(42))
+//!     .hint(dunst::highlight_color("#8ec07c"))
+//!     .hint(dunst::stack_tag("copy"))
+//!     .show()?;
+//! # Ok::<(), notify_rust::error::Error>(())
+//! ```
+
+use crate::Hint;
+
+/// Text color, as `#rrggbb` or `#rrggbbaa`.
+pub fn foreground_color(color: &str) -> Hint {
+    Hint::Custom("fgcolor".into(), color.into())
+}
+
+/// Background color, as `#rrggbb` or `#rrggbbaa`.
+pub fn background_color(color: &str) -> Hint {
+    Hint::Custom("bgcolor".into(), color.into())
+}
+
+/// Frame color, as `#rrggbb` or `#rrggbbaa`.
+pub fn frame_color(color: &str) -> Hint {
+    Hint::Custom("frcolor".into(), color.into())
+}
+
+/// Color of the progress bar, as `#rrggbb` or `#rrggbbaa`.
+pub fn highlight_color(color: &str) -> Hint {
+    Hint::Custom("hlcolor".into(), color.into())
+}
+
+/// Replaces any notification with the same tag, even from other applications,
+/// e.g. one volume notification no matter which tool changed the volume.
+pub fn stack_tag(tag: &str) -> Hint {
+    Hint::Custom("x-dunst-stack-tag".into(), tag.into())
+}
+
+/// Shows a progress bar, `percent` is capped at 100.
+pub fn progress(percent: u8) -> Hint {
+    Hint::CustomInt("value".into(), i32::from(percent.min(100)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hints() {
+        assert_eq!(
+            stack_tag("volume"),
+            Hint::Custom("x-dunst-stack-tag".into(), "volume".into())
+        );
+        assert_eq!(progress(150), Hint::CustomInt("value".into(), 100));
+    }
+}
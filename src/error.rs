@@ -1,6 +1,6 @@
 #![allow(missing_docs)]
 
-#[cfg(all(feature = "images", unix, not(target_os = "macos")))]
+#[cfg(feature = "images")]
 use crate::image::ImageError;
 use std::{fmt, num};
 /// Convenient wrapper around `std::Result`.
@@ -31,16 +31,28 @@ pub enum ErrorKind {
     #[cfg(target_os = "macos")]
     MacNotificationSys(mac_notification_sys::error::Error),
 
+    #[cfg(target_os = "windows")]
+    Windows(windows::core::Error),
+
     Parse(num::ParseIntError),
 
     SpecVersion(String),
 
     Conversion(String),
 
-    #[cfg(all(feature = "images", unix, not(target_os = "macos")))]
+    #[cfg(feature = "images")]
     Image(ImageError),
 
     ImplementationMissing,
+
+    /// The notification failed [`validate()`](`crate::Notification::validate`), for the given reason.
+    Invalid(String),
+
+    /// Features the platform can't show, refused under [`Unsupported::Strict`](`crate::Unsupported::Strict`).
+    Unsupported(String),
+
+    /// There is no session bus to send notifications to, see `is_available()`.
+    NoNotificationService,
 }
 
 impl fmt::Display for Error {
@@ -55,19 +67,35 @@ impl fmt::Display for Error {
             #[cfg(target_os = "macos")]
             ErrorKind::MacNotificationSys(ref e) => write!(f, "{}", e),
 
+            #[cfg(target_os = "windows")]
+            ErrorKind::Windows(ref e) => write!(f, "{}", e),
+
             ErrorKind::Parse(ref e) => write!(f, "Parsing Error: {}", e),
             ErrorKind::Conversion(ref e) => write!(f, "Conversion Error: {}", e),
+            ErrorKind::Invalid(ref e) => write!(f, "Invalid Notification: {}", e),
+            ErrorKind::Unsupported(ref e) => write!(f, "Unsupported on this platform: {}", e),
             ErrorKind::SpecVersion(ref e) | ErrorKind::Msg(ref e) => write!(f, "{}", e),
-            #[cfg(all(feature = "images", unix, not(target_os = "macos")))]
+            #[cfg(feature = "images")]
             ErrorKind::Image(ref e) => write!(f, "{}", e),
             ErrorKind::ImplementationMissing => write!(
                 f,
                 r#"No Dbus implementation available, please compile with either feature ="z" or feature="d""#
             ),
+            ErrorKind::NoNotificationService => write!(
+                f,
+                "No notification service available, neither $DBUS_SESSION_BUS_ADDRESS nor $XDG_RUNTIME_DIR/bus point to a session bus"
+            ),
         }
     }
 }
 
+impl Error {
+    /// What went wrong.
+    pub fn kind(&self) -> &ErrorKind {
+        &self.kind
+    }
+}
+
 impl std::error::Error for Error {}
 
 impl From<&str> for Error {
@@ -105,7 +133,16 @@ impl From<mac_notification_sys::error::Error> for Error {
     }
 }
 
-#[cfg(all(feature = "images", unix, not(target_os = "macos")))]
+#[cfg(target_os = "windows")]
+impl From<windows::core::Error> for Error {
+    fn from(e: windows::core::Error) -> Error {
+        Error {
+            kind: ErrorKind::Windows(e),
+        }
+    }
+}
+
+#[cfg(feature = "images")]
 impl From<ImageError> for Error {
     fn from(e: ImageError) -> Error {
         Error {
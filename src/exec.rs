@@ -0,0 +1,159 @@
+//! Shows notifications by running a `notify-send` compatible program,
+//! a best effort for platforms where neither zbus nor dbus-rs are an option.
+//!
+//! The program is `$NOTIFY_SEND` if set, `notify-send` otherwise.
+
+use crate::{
+    action::{ActionResponse, ActionResponseHandler, CloseReason},
+    error::*,
+    hints::Hint,
+    notification::Notification,
+    timeout::Timeout,
+    urgency::Urgency,
+};
+
+use std::{
+    process::{Command, Stdio},
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+/// Ids for notifications that don't set their own [`Notification::id`].
+static NEXT_ID: AtomicU32 = AtomicU32::new(1);
+
+/// Shows `notification` by running `notify-send` if it is installed,
+/// otherwise passes on the `error` it failed to show with.
+pub(crate) fn fallback(
+    notification: &Notification,
+    error: Error,
+) -> Result<ExecNotificationHandle> {
+    let id = notification
+        .id
+        .unwrap_or_else(|| NEXT_ID.fetch_add(1, Ordering::Relaxed));
+    let handle = ExecNotificationHandle {
+        id,
+        notification: notification.clone(),
+    };
+    match handle.run() {
+        Err(None) => Err(error),
+        Err(Some(failed)) => Err(failed),
+        Ok(()) => Ok(handle),
+    }
+}
+
+/// Whether there is a program to run.
+pub(crate) fn installed() -> bool {
+    Command::new(program())
+        .arg("--version")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .is_ok()
+}
+
+fn program() -> String {
+    std::env::var("NOTIFY_SEND").unwrap_or_else(|_| "notify-send".into())
+}
+
+/// A notification shown by running `notify-send`.
+///
+/// The program exits right after showing the notification,
+/// so it can neither be closed nor acted upon.
+#[derive(Debug)]
+pub(crate) struct ExecNotificationHandle {
+    pub(crate) id: u32,
+    pub(crate) notification: Notification,
+}
+
+impl ExecNotificationHandle {
+    pub fn wait_for_action(self, invocation_closure: impl ActionResponseHandler) {
+        invocation_closure.call(&ActionResponse::Closed(CloseReason::Expired));
+    }
+
+    /// Shows the notification again, `notify-send` can't replace the old one.
+    pub fn update(&mut self) -> Result<()> {
+        self.run()
+            .map_err(|error| error.unwrap_or_else(|| "notify-send is gone".into()))
+    }
+
+    /// Returns `Err(None)` if the program isn't installed.
+    fn run(&self) -> std::result::Result<(), Option<Error>> {
+        let program = program();
+        let output = Command::new(&program)
+            .args(args(&self.notification))
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped())
+            .output()
+            .map_err(|error| match error.kind() {
+                std::io::ErrorKind::NotFound => None,
+                _ => Some(Error::from(error.to_string().as_str())),
+            })?;
+        if output.status.success() {
+            Ok(())
+        } else {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            Err(Some(Error::from(
+                format!("{} failed: {}", program, stderr.trim()).as_str(),
+            )))
+        }
+    }
+}
+
+fn args(notification: &Notification) -> Vec<String> {
+    let mut args = Vec::new();
+    if !notification.appname.is_empty() {
+        args.push(format!("--app-name={}", notification.appname));
+    }
+    if !notification.icon.is_empty() {
+        args.push(format!("--icon={}", notification.icon));
+    }
+    for hint in &notification.hints {
+        match hint {
+            Hint::Urgency(Urgency::Low) => args.push("--urgency=low".into()),
+            Hint::Urgency(Urgency::Normal) => args.push("--urgency=normal".into()),
+            Hint::Urgency(Urgency::Critical) => args.push("--urgency=critical".into()),
+            Hint::Category(category) => args.push(format!("--category={}", category)),
+            _ => {}
+        }
+    }
+    match notification.timeout {
+        Timeout::Default => {}
+        Timeout::Never => args.push("--expire-time=0".into()),
+        Timeout::Milliseconds(ms) => args.push(format!("--expire-time={}", ms)),
+    }
+    // everything after this is text, even if it starts with a dash
+    args.push("--".into());
+    args.push(notification.summary.clone());
+    if !notification.body.is_empty() {
+        args.push(notification.body.clone());
+    }
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn notify_send_args() {
+        let args = args(
+            Notification::new()
+                .appname("mail")
+                .summary("-1 new mail")
+                .body("from: you")
+                .urgency(Urgency::Critical)
+                .timeout(Timeout::Never),
+        );
+        assert_eq!(
+            args,
+            [
+                "--app-name=mail",
+                "--urgency=critical",
+                "--expire-time=0",
+                "--",
+                "-1 new mail",
+                "from: you"
+            ]
+        );
+    }
+}
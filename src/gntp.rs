@@ -0,0 +1,275 @@
+//! Delivers notifications to a remote Growl or Snarl via the
+//! [Growl Network Transport Protocol](http://www.growlforwindows.com/gfw/help/gntp.aspx).
+
+use crate::{error::*, notification::Notification, timeout::Timeout};
+
+use sha2::{Digest, Sha256};
+
+use std::{
+    collections::hash_map::RandomState,
+    fmt::Write as _,
+    hash::{BuildHasher, Hasher},
+    io::{BufRead, BufReader, Write as _},
+    net::TcpStream,
+    time::{Duration, SystemTime},
+};
+
+const DEFAULT_PORT: u16 = 23053;
+
+/// The only notification type we register, GNTP wants to know them up front.
+const NOTIFICATION_NAME: &str = "notify-rust";
+
+/// A Growl or Snarl instance listening for GNTP, usually on another machine.
+///
+/// # Example
+/// ```no_run
+/// # use notify_rust::*;
+/// let workstation = GntpServer::new("workstation.local").password("secret");
+/// workstation.send(Notification::new().summary("build finished"))?;
+/// # Ok::<(), notify_rust::error::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct GntpServer {
+    address: String,
+    password: Option<String>,
+    timeout: Duration,
+}
+
+impl GntpServer {
+    /// Talks to `address`, which is `"host"` or `"host:port"`, the port defaults to 23053.
+    pub fn new(address: &str) -> GntpServer {
+        let address = if address.contains(':') {
+            address.to_owned()
+        } else {
+            format!("{}:{}", address, DEFAULT_PORT)
+        };
+        GntpServer {
+            address,
+            password: None,
+            timeout: Duration::from_secs(5),
+        }
+    }
+
+    /// Authenticates with `password`, which Growl requires for notifications from other machines.
+    pub fn password(mut self, password: &str) -> GntpServer {
+        self.password = Some(password.to_owned());
+        self
+    }
+
+    /// How long to wait for the server to accept and answer, 5 seconds by default.
+    pub fn timeout(mut self, timeout: Duration) -> GntpServer {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Registers the notification's `appname` and shows it.
+    ///
+    /// GNTP needs every application registered before it may notify,
+    /// registering again is harmless, so this does both every time.
+    pub fn send(&self, notification: &Notification) -> Result<()> {
+        self.request(&register_message(notification, self.key().as_deref()))?;
+        self.request(&notify_message(notification, self.key().as_deref()))
+    }
+
+    /// A fresh `"SHA256:<key hash>.<salt>"` for every message.
+    fn key(&self) -> Option<String> {
+        let password = self.password.as_ref()?;
+        Some(key_hash(password, &salt()))
+    }
+
+    fn request(&self, message: &str) -> Result<()> {
+        let to_error = |error: std::io::Error| Error::from(error.to_string().as_str());
+        let mut stream = TcpStream::connect(&self.address).map_err(to_error)?;
+        stream
+            .set_read_timeout(Some(self.timeout))
+            .map_err(to_error)?;
+        stream.write_all(message.as_bytes()).map_err(to_error)?;
+
+        let mut response = String::new();
+        let mut reader = BufReader::new(stream);
+        loop {
+            let mut line = String::new();
+            if reader.read_line(&mut line).map_err(to_error)? == 0 || line.trim_end().is_empty() {
+                break;
+            }
+            response.push_str(&line);
+        }
+        check_response(&response)
+    }
+}
+
+fn register_message(notification: &Notification, key: Option<&str>) -> String {
+    let mut message = request_line("REGISTER", key);
+    header(&mut message, "Application-Name", &notification.appname);
+    header(&mut message, "Notifications-Count", "1");
+    message.push_str("\r\n");
+    header(&mut message, "Notification-Name", NOTIFICATION_NAME);
+    header(&mut message, "Notification-Enabled", "True");
+    message.push_str("\r\n");
+    message
+}
+
+fn notify_message(notification: &Notification, key: Option<&str>) -> String {
+    let mut message = request_line("NOTIFY", key);
+    header(&mut message, "Application-Name", &notification.appname);
+    header(&mut message, "Notification-Name", NOTIFICATION_NAME);
+    header(&mut message, "Notification-Title", &notification.summary);
+    if !notification.body.is_empty() {
+        header(&mut message, "Notification-Text", &notification.body);
+    }
+    // only urls make sense on another machine
+    if notification.icon.starts_with("http://") || notification.icon.starts_with("https://") {
+        header(&mut message, "Notification-Icon", &notification.icon);
+    }
+    if notification.timeout == Timeout::Never {
+        header(&mut message, "Notification-Sticky", "True");
+    }
+    message.push_str("\r\n");
+    message
+}
+
+fn request_line(message_type: &str, key: Option<&str>) -> String {
+    match key {
+        Some(key) => format!("GNTP/1.0 {} NONE {}\r\n", message_type, key),
+        None => format!("GNTP/1.0 {} NONE\r\n", message_type),
+    }
+}
+
+/// Header values may span lines, but must not contain the `CRLF` that ends them.
+fn header(message: &mut String, name: &str, value: &str) {
+    let _ = write!(message, "{}: {}\r\n", name, value.replace('\r', ""));
+}
+
+fn check_response(response: &str) -> Result<()> {
+    if response.starts_with("GNTP/1.0 -OK") {
+        return Ok(());
+    }
+    let description = response
+        .lines()
+        .find_map(|line| line.strip_prefix("Error-Description:"))
+        .map_or("unexpected response", str::trim);
+    Err(Error::from(
+        format!("GNTP server refused: {}", description).as_str(),
+    ))
+}
+
+/// `SHA256(SHA256(password + salt))`, see the "Message Security" part of the spec.
+fn key_hash(password: &str, salt: &[u8]) -> String {
+    let key = Sha256::new()
+        .chain_update(password.as_bytes())
+        .chain_update(salt)
+        .finalize();
+    format!("SHA256:{}.{}", hex(&Sha256::digest(key)), hex(salt))
+}
+
+/// Good enough to keep hashes of the same password apart, nothing here needs to be secret.
+fn salt() -> Vec<u8> {
+    let mut hasher = RandomState::new().build_hasher();
+    if let Ok(since_epoch) = SystemTime::now().duration_since(SystemTime::UNIX_EPOCH) {
+        hasher.write_u128(since_epoch.as_nanos());
+    }
+    hasher.finish().to_be_bytes().to_vec()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().fold(String::new(), |mut hex, byte| {
+        let _ = write!(hex, "{:02X}", byte);
+        hex
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{io::Read, net::TcpListener, thread};
+
+    #[test]
+    fn messages() {
+        let mut notification = Notification::new();
+        notification
+            .appname("ci")
+            .summary("build finished")
+            .body("all green\r\nno warnings")
+            .timeout(Timeout::Never);
+
+        assert_eq!(
+            register_message(&notification, None),
+            "GNTP/1.0 REGISTER NONE\r\n\
+             Application-Name: ci\r\n\
+             Notifications-Count: 1\r\n\
+             \r\n\
+             Notification-Name: notify-rust\r\n\
+             Notification-Enabled: True\r\n\
+             \r\n"
+        );
+        assert_eq!(
+            notify_message(&notification, Some("SHA256:AB.CD")),
+            "GNTP/1.0 NOTIFY NONE SHA256:AB.CD\r\n\
+             Application-Name: ci\r\n\
+             Notification-Name: notify-rust\r\n\
+             Notification-Title: build finished\r\n\
+             Notification-Text: all green\nno warnings\r\n\
+             Notification-Sticky: True\r\n\
+             \r\n"
+        );
+    }
+
+    #[test]
+    fn hashes_password_with_salt() {
+        assert_eq!(
+            key_hash("foo", &[0x01, 0x02]),
+            format!(
+                "SHA256:{}.0102",
+                hex(&Sha256::digest(Sha256::digest(b"foo\x01\x02")))
+            )
+        );
+    }
+
+    #[test]
+    fn reports_server_errors() {
+        assert!(check_response("GNTP/1.0 -OK NONE\r\nResponse-Action: NOTIFY\r\n").is_ok());
+        let error = check_response(
+            "GNTP/1.0 -ERROR NONE\r\nError-Code: 400\r\nError-Description: Not authorized\r\n",
+        )
+        .unwrap_err();
+        assert_eq!(error.to_string(), "GNTP server refused: Not authorized");
+    }
+
+    #[test]
+    fn registers_and_notifies() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap().to_string();
+        let server = thread::spawn(move || {
+            // REGISTER has a second block of headers for the notification type
+            let complete = |request: &str| {
+                request.matches("\r\n\r\n").count()
+                    == if request.starts_with("GNTP/1.0 REGISTER") {
+                        2
+                    } else {
+                        1
+                    }
+            };
+            let mut requests = Vec::new();
+            for stream in listener.incoming().take(2) {
+                let mut stream = stream.unwrap();
+                let mut request = String::new();
+                let mut buf = [0; 1024];
+                while !complete(&request) {
+                    let read = stream.read(&mut buf).unwrap();
+                    request.push_str(std::str::from_utf8(&buf[..read]).unwrap());
+                }
+                stream.write_all(b"GNTP/1.0 -OK NONE\r\n\r\n").unwrap();
+                requests.push(request.lines().next().unwrap().to_owned());
+            }
+            requests
+        });
+
+        GntpServer::new(&address)
+            .send(Notification::new().summary("hi"))
+            .unwrap();
+        assert_eq!(
+            server.join().unwrap(),
+            ["GNTP/1.0 REGISTER NONE", "GNTP/1.0 NOTIFY NONE"]
+        );
+    }
+}
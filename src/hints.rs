@@ -13,16 +13,20 @@ use crate::image::Image;
 use crate::image::image_spec_str;
 use crate::Urgency;
 
-#[cfg(all(feature = "zbus", unix, not(target_os = "macos")))] use crate::notification::Notification;
+#[cfg(all(any(feature = "dbus", feature = "zbus"), unix, not(target_os = "macos")))] use crate::notification::Notification;
 #[cfg(all(feature = "zbus", unix, not(target_os = "macos")))] use std::collections::HashMap;
 
 mod constants;
+mod value;
+
+pub use value::HintValue;
 
 #[cfg(all(unix, not(target_os = "macos")))]
 #[derive(Eq, PartialEq, Hash, Clone, Debug)]
 pub(crate) enum CustomHintType {
     Int,
     String,
+    Value,
 }
 
 /// `Hints` allow you to pass extra information to the server.
@@ -37,6 +41,8 @@ pub(crate) enum CustomHintType {
 ///
 /// you pass these to [`Notification::hint`]
 #[derive(Eq, PartialEq, Hash, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum Hint {
     /// If true, server may interpret action identifiers as named icons and display those.
     ActionIcons(bool),
@@ -89,6 +95,13 @@ pub enum Hint {
     /// A custom numerical (integer) hint
     CustomInt(String, i32),
 
+    /// A custom hint of any type, for server specific extensions.
+    CustomValue(String, HintValue),
+
+    /// Files or URLs the notification is about, e.g. a screenshot that was just taken.
+    /// Plasma lets them be dragged out of the popup, other servers ignore them. (`x-kde-urls`)
+    Urls(Vec<String>),
+
     /// Only used by this `NotificationServer` implementation
     Invalid // TODO find a better solution to this
 }
@@ -138,6 +151,7 @@ impl Hint {
             (constants::TRANSIENT, val)      => val.parse::<bool>().map(Hint::Transient).map_err(|e|e.to_string()),
             (constants::X, val)              => val.parse::<i32>().map(Hint::X).map_err(|e|e.to_string()),
             (constants::Y, val)              => val.parse::<i32>().map(Hint::Y).map_err(|e|e.to_string()),
+            (constants::URLS, val)           => Ok(Hint::Urls(vec![val.to_owned()])),
             _                                => Err(String::from("unknown name"))
         }
     }
@@ -164,12 +178,60 @@ fn test_hints_to_map() {
      });
 }
 
+#[cfg(all(feature = "zbus", unix, not(target_os = "macos")))]
+#[test]
+fn test_urls_to_map() {
+    let screenshot = Notification::new()
+        .hint(Hint::Urls(vec!["file:///tmp/screenshot.png".into()]))
+        .finalize();
+
+    assert_eq!(hints_to_map(&screenshot), maplit::hashmap!{
+        "x-kde-urls" => zvariant::Value::from(vec!["file:///tmp/screenshot.png"])
+    });
+}
+
+#[cfg(all(feature = "zbus", unix, not(target_os = "macos")))]
+#[test]
+fn test_custom_values_to_map() {
+    let n1 = Notification::new()
+        .hint(Hint::CustomValue("progress".into(), HintValue::F64(0.5)))
+        .hint(Hint::CustomValue("tags".into(), vec!["a", "b"].into()))
+        .hint(Hint::CustomValue("tags".into(), vec!["c"].into()))
+        .finalize();
+
+    assert_eq!(hints_to_map(&n1), maplit::hashmap!{
+        "progress" => zvariant::Value::F64(0.5),
+        "tags"     => zvariant::Value::from(vec!["c"])
+    });
+}
+
 #[cfg(all(feature = "zbus", unix, not(target_os = "macos")))]
 pub(crate) fn hints_to_map(notification: &Notification) -> HashMap::<&str, zvariant::Value<'_>> {
-    notification
+    let mut hints: HashMap<_, _> = notification
         .get_hints()
         .map(Into::into)
-        .collect()
+        .collect();
+    if notification.uses_action_icons() {
+        hints.insert(constants::ACTION_ICONS, zvariant::Value::Bool(true));
+    }
+    if let Some(placeholder) = notification.inline_reply_placeholder() {
+        hints.insert(constants::REPLY_PLACEHOLDER, zvariant::Value::Str(placeholder.into()));
+    }
+    hints
+}
+
+/// Hints for what was asked for when building `notification` and the server turned out to support,
+/// sent along with [`Notification::get_hints`].
+#[cfg(all(feature = "dbus", unix, not(target_os = "macos")))]
+pub(crate) fn adapted_hints(notification: &Notification) -> Vec<Hint> {
+    let mut hints = Vec::new();
+    if notification.uses_action_icons() && !notification.hints.contains(&Hint::ActionIcons(true)) {
+        hints.push(Hint::ActionIcons(true));
+    }
+    if let Some(placeholder) = notification.inline_reply_placeholder() {
+        hints.push(Hint::Custom(constants::REPLY_PLACEHOLDER.into(), placeholder.into()));
+    }
+    hints
 }
 
 #[cfg(all(feature = "zbus", unix, not(target_os = "macos")))]
@@ -202,12 +264,33 @@ impl<'a> From<&'a Hint> for (&'a str, zvariant::Value<'a>) {
             Hint::Urgency(value)           => (URGENCY        , zvariant::Value::U8(*value as u8)),
             Hint::Custom(key, val)         => (key.as_str()   , zvariant::Value::Str(val.as_str().into())),
             Hint::CustomInt(key, val)      => (key.as_str()   , zvariant::Value::I32(*val)),
+            Hint::CustomValue(key, val)    => (key.as_str()   , val.into()),
+            Hint::Urls(urls)               => (URLS           , zvariant::Value::from(urls.iter().map(String::as_str).collect::<Vec<_>>())),
             Hint::Invalid                  => (INVALID        , zvariant::Value::Str(INVALID.into()))
         }
     }
 }
 
 
+#[cfg(all(feature = "zbus", unix, not(target_os = "macos")))]
+impl<'a> From<&'a HintValue> for zvariant::Value<'a> {
+    fn from(val: &'a HintValue) -> Self {
+        match val {
+            HintValue::Bool(value)    => zvariant::Value::Bool(*value),
+            HintValue::Byte(value)    => zvariant::Value::U8(*value),
+            HintValue::I32(value)     => zvariant::Value::I32(*value),
+            HintValue::U32(value)     => zvariant::Value::U32(*value),
+            HintValue::I64(value)     => zvariant::Value::I64(*value),
+            HintValue::U64(value)     => zvariant::Value::U64(*value),
+            HintValue::F64(value)     => zvariant::Value::F64(*value),
+            HintValue::Str(value)     => zvariant::Value::Str(value.as_str().into()),
+            HintValue::Bytes(value)   => zvariant::Value::from(value.as_slice()),
+            HintValue::Strings(value) => zvariant::Value::from(value.iter().map(String::as_str).collect::<Vec<_>>()),
+        }
+    }
+}
+
+
 #[cfg(all(feature = "dbus", unix, not(target_os = "macos")))]
 impl<'a, A: dbus::arg::RefArg> From<(&'a String, &'a A)> for Hint {
     fn from(pair: (&String, &A)) -> Self {
@@ -233,6 +316,7 @@ impl<'a, A: dbus::arg::RefArg> From<(&'a String, &'a A)> for Hint {
             (constants::TRANSIENT,      _,        _,       _          ) => Hint::Transient(false),
             (constants::X,              _,        Some(x), _          ) => Hint::X(x as i32),
             (constants::Y,              _,        Some(y), _          ) => Hint::Y(y as i32),
+            (constants::URLS,           _,        _,       _          ) => Hint::Urls(message::strings(variant)),
 
             other => {
                 eprintln!("Invalid Hint {:#?} ", other);
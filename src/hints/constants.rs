@@ -12,6 +12,8 @@ pub const TRANSIENT: &str       = "transient";
 pub const X: &str               = "x";
 pub const Y: &str               = "y";
 pub const URGENCY: &str         = "urgency";
+pub const URLS: &str            = "x-kde-urls";
+pub const REPLY_PLACEHOLDER: &str = "x-kde-reply-placeholder-text";
 
 
 pub const INVALID: &str    = "invalid";
\ No newline at end of file
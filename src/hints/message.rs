@@ -12,7 +12,7 @@
 #![allow(dead_code, unused_imports)]
 
 
-use super::{Hint, constants::*};
+use super::{Hint, HintValue, constants::*};
 use crate ::Urgency;
 
 #[cfg(all(feature = "images", unix, not(target_os = "macos")))]
@@ -74,6 +74,7 @@ impl<'a, A: RefArg> From<(&'a String, &'a A)> for HintMessage {
             (TRANSIENT,      _,        _,       _          ) => Hint::Transient(false),
             (X,              _,        Some(x), _          ) => Hint::X(x as i32),
             (Y,              _,        Some(y), _          ) => Hint::Y(y as i32),
+            (URLS,           _,        _,       _          ) => Hint::Urls(strings(variant)),
 
             other => {
                 eprintln!("Invalid Hint{:#?} ", other);
@@ -104,6 +105,8 @@ impl From<HintMessage> for (MessageItem, MessageItem) {
             Hint::Urgency(value)           => (URGENCY        .to_owned(), MessageItem::Byte(value as u8)),
             Hint::Custom(ref key, ref val) => (key            .to_owned(), MessageItem::Str(val.to_owned ())),
             Hint::CustomInt(ref key, val)  => (key            .to_owned(), MessageItem::Int32(val)),
+            Hint::CustomValue(ref key, ref val) => (key       .to_owned(), val.into()),
+            Hint::Urls(ref urls)           => (URLS           .to_owned(), string_array(urls)),
             Hint::Invalid                  => ("invalid"      .to_owned(), MessageItem::Str("Invalid".to_owned()))
         };
 
@@ -138,13 +141,17 @@ impl From<(&MessageItem, &MessageItem)> for HintMessage {
             Ok(TRANSIENT)       => value.inner().map(Hint::Transient),
             Ok(X)               => value.inner().map(Hint::X),
             Ok(Y)               => value.inner().map(Hint::Y),
+            Ok(URLS)            => value.inner::<&[MessageItem]>().map(|urls| {
+                Hint::Urls(urls.iter().filter_map(|url| url.inner::<&str>().ok()).map(String::from).collect())
+            }),
             Ok(URGENCY)         => value.inner().map(|i| match i {
                 0  => Urgency::Low,
                 2  => Urgency::Critical,
                 _  => Urgency::Normal
             }).map(Hint::Urgency),
             Ok(k) if is_stringy => value.inner::<&str>().map(|v| Hint::Custom(k.to_string(), v.to_string())),
-            Ok(k)               => value.inner().map(|v| Hint::CustomInt(k.to_string(), v)),
+            Ok(k)               => value.inner().map(|v| Hint::CustomInt(k.to_string(), v))
+                                        .or_else(|_| custom_value(value).map(|v| Hint::CustomValue(k.to_string(), v))),
             _ => Err(()),
         }.unwrap_or(Hint::Invalid)
         .into()
@@ -152,8 +159,79 @@ impl From<(&MessageItem, &MessageItem)> for HintMessage {
 }
 
 
+#[cfg(feature = "dbus")]
+impl From<&HintValue> for MessageItem {
+    fn from(value: &HintValue) -> Self {
+        match *value {
+            HintValue::Bool(value)        => MessageItem::Bool(value),
+            HintValue::Byte(value)        => MessageItem::Byte(value),
+            HintValue::I32(value)         => MessageItem::Int32(value),
+            HintValue::U32(value)         => MessageItem::UInt32(value),
+            HintValue::I64(value)         => MessageItem::Int64(value),
+            HintValue::U64(value)         => MessageItem::UInt64(value),
+            HintValue::F64(value)         => MessageItem::Double(value),
+            HintValue::Str(ref value)     => MessageItem::Str(value.clone()),
+            HintValue::Bytes(ref value)   => MessageItem::Array(
+                dbus::arg::messageitem::MessageItemArray::new(value.iter().cloned().map(MessageItem::Byte).collect(), "ay".into())
+                    .expect("bytes make a valid array")
+            ),
+            HintValue::Strings(ref value) => string_array(value),
+        }
+    }
+}
+
+/// A custom hint's value, unless it is of a type [`HintValue`] doesn't cover.
+#[cfg(feature = "dbus")]
+fn custom_value(item: &MessageItem) -> Result<HintValue, ()> {
+    Ok(match *item {
+        MessageItem::Bool(value)   => HintValue::Bool(value),
+        MessageItem::Byte(value)   => HintValue::Byte(value),
+        MessageItem::Int32(value)  => HintValue::I32(value),
+        MessageItem::UInt32(value) => HintValue::U32(value),
+        MessageItem::Int64(value)  => HintValue::I64(value),
+        MessageItem::UInt64(value) => HintValue::U64(value),
+        MessageItem::Double(value) => HintValue::F64(value),
+        MessageItem::Str(ref value) => HintValue::Str(value.clone()),
+        MessageItem::Array(ref items) if items.signature() == "ay" => {
+            HintValue::Bytes(items.iter().filter_map(|item| item.inner::<u8>().ok()).collect())
+        }
+        MessageItem::Array(ref items) if items.signature() == "as" => {
+            HintValue::Strings(items.iter().filter_map(|item| item.inner::<&str>().ok()).map(String::from).collect())
+        }
+        _ => return Err(()),
+    })
+}
+
+/// All strings in `arg`, looking into variants and arrays.
+#[cfg(feature = "dbus")]
+pub(crate) fn strings<A: RefArg + ?Sized>(arg: &A) -> Vec<String> {
+    if let Some(string) = arg.as_str() {
+        return vec![string.to_owned()];
+    }
+    arg.as_iter()
+        .map(|items| items.flat_map(strings).collect())
+        .unwrap_or_default()
+}
+
+#[cfg(feature = "dbus")]
+fn string_array(strings: &[String]) -> MessageItem {
+    let items = strings.iter().cloned().map(MessageItem::Str).collect();
+    MessageItem::Array(dbus::arg::messageitem::MessageItemArray::new(items, "as".into()).expect("strings make a valid array"))
+}
+
+
 #[allow(missing_docs)]
 #[cfg(feature = "dbus")]
 pub(crate) fn hints_from_variants<A: RefArg>(hints: &HashMap<String, A>) -> HashSet<HintMessage> {
     hints.iter().map(Into::into).collect()
 }
+
+#[cfg(feature = "dbus")]
+#[test]
+fn custom_value_round_trip() {
+    for value in [HintValue::F64(0.5), HintValue::U64(42), vec![1u8, 2].into(), vec!["a", "b"].into()] {
+        let hint = Hint::CustomValue("x-test".into(), value);
+        let (key, variant) = HintMessage::from(hint.clone()).into();
+        assert_eq!(*HintMessage::from((&key, &variant)), hint);
+    }
+}
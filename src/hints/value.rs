@@ -0,0 +1,151 @@
+//! Typed values for custom hints, see [`HintValue`].
+
+use std::hash::{Hash, Hasher};
+
+/// Value of a [`Hint::CustomValue`](`super::Hint::CustomValue`),
+/// covering the D-Bus types servers use for their own hints.
+///
+/// ```no_run
+/// # use notify_rust::*;
+/// Notification::new()
+///     .summary("syncing")
+///     .hint(Hint::CustomValue("x-example-progress".into(), HintValue::F64(0.42)))
+///     .hint(Hint::CustomValue("x-example-tags".into(), vec!["sync", "cloud"].into()))
+///     .show()?;
+/// # Ok::<(), notify_rust::error::Error>(())
+/// ```
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum HintValue {
+    /// `b`
+    Bool(bool),
+    /// `y`
+    Byte(u8),
+    /// `i`
+    I32(i32),
+    /// `u`
+    U32(u32),
+    /// `x`
+    I64(i64),
+    /// `t`
+    U64(u64),
+    /// `d`, equal to another one if their bits are
+    F64(f64),
+    /// `s`
+    Str(String),
+    /// `ay`
+    Bytes(Vec<u8>),
+    /// `as`
+    Strings(Vec<String>),
+}
+
+impl PartialEq for HintValue {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (HintValue::Bool(a), HintValue::Bool(b)) => a == b,
+            (HintValue::Byte(a), HintValue::Byte(b)) => a == b,
+            (HintValue::I32(a), HintValue::I32(b)) => a == b,
+            (HintValue::U32(a), HintValue::U32(b)) => a == b,
+            (HintValue::I64(a), HintValue::I64(b)) => a == b,
+            (HintValue::U64(a), HintValue::U64(b)) => a == b,
+            (HintValue::F64(a), HintValue::F64(b)) => a.to_bits() == b.to_bits(),
+            (HintValue::Str(a), HintValue::Str(b)) => a == b,
+            (HintValue::Bytes(a), HintValue::Bytes(b)) => a == b,
+            (HintValue::Strings(a), HintValue::Strings(b)) => a == b,
+            _ => false,
+        }
+    }
+}
+
+impl Eq for HintValue {}
+
+impl Hash for HintValue {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        std::mem::discriminant(self).hash(state);
+        match self {
+            HintValue::Bool(value) => value.hash(state),
+            HintValue::Byte(value) => value.hash(state),
+            HintValue::I32(value) => value.hash(state),
+            HintValue::U32(value) => value.hash(state),
+            HintValue::I64(value) => value.hash(state),
+            HintValue::U64(value) => value.hash(state),
+            HintValue::F64(value) => value.to_bits().hash(state),
+            HintValue::Str(value) => value.hash(state),
+            HintValue::Bytes(value) => value.hash(state),
+            HintValue::Strings(value) => value.hash(state),
+        }
+    }
+}
+
+impl From<bool> for HintValue {
+    fn from(value: bool) -> Self {
+        HintValue::Bool(value)
+    }
+}
+
+impl From<u8> for HintValue {
+    fn from(value: u8) -> Self {
+        HintValue::Byte(value)
+    }
+}
+
+impl From<i32> for HintValue {
+    fn from(value: i32) -> Self {
+        HintValue::I32(value)
+    }
+}
+
+impl From<u32> for HintValue {
+    fn from(value: u32) -> Self {
+        HintValue::U32(value)
+    }
+}
+
+impl From<i64> for HintValue {
+    fn from(value: i64) -> Self {
+        HintValue::I64(value)
+    }
+}
+
+impl From<u64> for HintValue {
+    fn from(value: u64) -> Self {
+        HintValue::U64(value)
+    }
+}
+
+impl From<f64> for HintValue {
+    fn from(value: f64) -> Self {
+        HintValue::F64(value)
+    }
+}
+
+impl From<&str> for HintValue {
+    fn from(value: &str) -> Self {
+        HintValue::Str(value.to_owned())
+    }
+}
+
+impl From<String> for HintValue {
+    fn from(value: String) -> Self {
+        HintValue::Str(value)
+    }
+}
+
+impl From<Vec<u8>> for HintValue {
+    fn from(value: Vec<u8>) -> Self {
+        HintValue::Bytes(value)
+    }
+}
+
+impl From<Vec<String>> for HintValue {
+    fn from(value: Vec<String>) -> Self {
+        HintValue::Strings(value)
+    }
+}
+
+impl From<Vec<&str>> for HintValue {
+    fn from(value: Vec<&str>) -> Self {
+        HintValue::Strings(value.into_iter().map(String::from).collect())
+    }
+}
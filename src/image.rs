@@ -1,15 +1,35 @@
-#[cfg(feature = "dbus")]
+#[cfg(all(feature = "dbus", unix, not(target_os = "macos")))]
 use dbus::arg::messageitem::{MessageItem, MessageItemArray};
 pub use image::DynamicImage;
 
+#[cfg(all(
+    any(feature = "dbus", feature = "zbus"),
+    unix,
+    not(target_os = "macos")
+))]
 use std::cmp::Ordering;
 use std::convert::TryFrom;
 use std::error::Error;
 use std::fmt;
 use std::path::Path;
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+use std::{
+    hash::{DefaultHasher, Hash, Hasher},
+    path::PathBuf,
+};
 
+#[cfg(all(
+    any(feature = "dbus", feature = "zbus"),
+    unix,
+    not(target_os = "macos")
+))]
 use crate::miniver::Version;
 
+#[cfg(all(
+    any(feature = "dbus", feature = "zbus"),
+    unix,
+    not(target_os = "macos")
+))]
 mod constants {
     pub const IMAGE_DATA: &str = "image-data";
     pub const IMAGE_DATA_1_1: &str = "image_data";
@@ -18,6 +38,7 @@ mod constants {
 
 /// Image representation for images. Send via `Notification::image_data()`
 #[derive(PartialEq, Eq, Debug, Clone, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Image {
     width: i32,
     height: i32,
@@ -77,7 +98,12 @@ impl Image {
         Image::try_from(dyn_img)
     }
 
-    #[cfg(all(feature = "images", feature = "zbus"))]
+    /// How many bytes of pixel data the image holds.
+    pub(crate) fn data_len(&self) -> usize {
+        self.data.len()
+    }
+
+    #[cfg(all(feature = "zbus", unix, not(target_os = "macos")))]
     pub(crate) fn to_tuple(&self) -> (i32, i32, i32, bool, i32, i32, Vec<u8>) {
         (
             self.width,
@@ -91,6 +117,71 @@ impl Image {
     }
 }
 
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+impl Image {
+    /// Writes the image to a png in the temp directory, for platforms that only show images from files.
+    ///
+    /// The file is named after the image's contents, so showing the same image twice reuses it.
+    /// Writing a new file removes all but the [`KEPT_TEMP_FILES`] most recent ones.
+    pub(crate) fn to_temp_file(&self) -> Result<PathBuf, ImageError> {
+        let mut hasher = DefaultHasher::new();
+        self.hash(&mut hasher);
+        let temp_dir = std::env::temp_dir();
+        let path = temp_dir.join(format!("{}{:x}.png", TEMP_FILE_PREFIX, hasher.finish()));
+        if !path.exists() {
+            remove_old_temp_files(&temp_dir);
+            let color_type = if self.alpha {
+                image::ColorType::Rgba8
+            } else {
+                image::ColorType::Rgb8
+            };
+            image::save_buffer(
+                &path,
+                &self.data,
+                self.width as u32,
+                self.height as u32,
+                color_type,
+            )?;
+        }
+        Ok(path)
+    }
+}
+
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+const TEMP_FILE_PREFIX: &str = "notify-rust-";
+
+/// How many pngs written by [`Image::to_temp_file`] are kept around for notifications still on screen.
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+const KEPT_TEMP_FILES: usize = 16;
+
+/// Removes the oldest pngs written by [`Image::to_temp_file`], making room for another one.
+#[cfg(any(target_os = "macos", target_os = "windows"))]
+fn remove_old_temp_files(temp_dir: &Path) {
+    let entries = match std::fs::read_dir(temp_dir) {
+        Ok(entries) => entries,
+        Err(_) => return,
+    };
+    let mut temp_files = entries
+        .filter_map(|entry| {
+            let entry = entry.ok()?;
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            if !(name.starts_with(TEMP_FILE_PREFIX) && name.ends_with(".png")) {
+                return None;
+            }
+            Some((entry.metadata().ok()?.modified().ok()?, entry.path()))
+        })
+        .collect::<Vec<_>>();
+    if temp_files.len() < KEPT_TEMP_FILES {
+        return;
+    }
+    temp_files.sort_unstable_by(|(a, _), (b, _)| b.cmp(a));
+    // keep one less, the file about to be written takes the last spot
+    for (_, path) in temp_files.into_iter().skip(KEPT_TEMP_FILES - 1) {
+        let _ = std::fs::remove_file(path);
+    }
+}
+
 impl TryFrom<DynamicImage> for Image {
     type Error = ImageError;
 
@@ -126,7 +217,7 @@ impl TryFrom<image::RgbaImage> for Image {
 /// Errors that can occur when creating an Image
 #[derive(Debug)]
 pub enum ImageError {
-    /// The given image is too big. DBus only has 32 bits for width / height
+    /// The given image is too big. D-Bus only has 32 bits for width / height
     TooBig,
     /// The given bytes don't match the width, height and channel count
     WrongDataSize,
@@ -165,7 +256,7 @@ impl fmt::Display for ImageError {
 }
 
 /// matching image data key for each spec version
-#[cfg(feature = "dbus")]
+#[cfg(all(feature = "dbus", unix, not(target_os = "macos")))]
 pub(crate) fn image_spec(version: Version) -> String {
     match version.cmp(&Version::new(1, 1)) {
         Ordering::Less => constants::IMAGE_DATA_1_0.to_owned(),
@@ -175,7 +266,7 @@ pub(crate) fn image_spec(version: Version) -> String {
 }
 
 /// matching image data key for each spec version
-#[cfg(feature = "zbus")]
+#[cfg(all(feature = "zbus", unix, not(target_os = "macos")))]
 pub(crate) fn image_spec_str(version: Version) -> &'static str {
     match version.cmp(&Version::new(1, 1)) {
         Ordering::Less => constants::IMAGE_DATA_1_0,
@@ -184,10 +275,10 @@ pub(crate) fn image_spec_str(version: Version) -> &'static str {
     }
 }
 
-#[cfg(feature = "dbus")]
+#[cfg(all(feature = "dbus", unix, not(target_os = "macos")))]
 pub struct ImageMessage(Image);
 
-#[cfg(feature = "dbus")]
+#[cfg(all(feature = "dbus", unix, not(target_os = "macos")))]
 impl From<Image> for ImageMessage {
     fn from(hint: Image) -> Self {
         ImageMessage(hint)
@@ -200,7 +291,7 @@ impl From<image::ImageError> for ImageError {
     }
 }
 
-#[cfg(feature = "dbus")]
+#[cfg(all(feature = "dbus", unix, not(target_os = "macos")))]
 impl std::ops::Deref for ImageMessage {
     type Target = Image;
 
@@ -209,7 +300,7 @@ impl std::ops::Deref for ImageMessage {
     }
 }
 
-#[cfg(feature = "dbus")]
+#[cfg(all(feature = "dbus", unix, not(target_os = "macos")))]
 impl From<ImageMessage> for MessageItem {
     fn from(img_msg: ImageMessage) -> Self {
         let img = img_msg.0;
@@ -12,6 +12,9 @@
 //! in these cases you will have to add platform specific toggles to your code.
 //! For more see [platform differences](#platform-differences)
 //!
+//! On `wasm32` notifications are shown through the browser's Notification API,
+//! after the user granted permission via `request_permission()`.
+//!
 //! # Examples
 //!
 //! ## Example 1: Simple Notification
@@ -85,27 +88,75 @@
 //! |---------------------|-------|-------|---------|
 //! |  `fn appname(...)`  |  ✔︎    |       |        |
 //! |  `fn summary(...)`  |  ✔︎    | ✔︎     |  ✔︎    |
+//! |  `fn summary_text(...)`|  ✔︎  | ✔︎     |  ✔︎    |
 //! |  `fn subtitle(...)` |       | ✔︎     |  ✔︎    |
 //! |  `fn body(...)`     |  ✔︎    | ✔︎     |  ✔︎    |
+//! |  `fn body_text(...)`|  ✔︎    | ✔︎     |  ✔︎    |
+//! |  `fn rich_body(...)`|  ✔︎    | ✔︎     |  ✔︎    |
+//! |  `fn hyperlink(...)`|  ✔︎    | ✔︎     |  ✔︎    |
+//! |  `fn reply_input(...)`|  ✔︎  | ✔︎     |  ✔︎    |
 //! |  `fn icon(...)`     |  ✔︎    |       |        |
 //! |  `fn auto_icon(...)`|  ✔︎    |       |        |
+//! |  `fn image(...)`    |  ✔︎    | ✔︎     |  ✔︎    |
+//! |  `fn image_data(...)`|  ✔︎    | ✔︎     |  ✔︎    |
 //! |  `fn hint(...)`     |  ✔︎    | ❌    | ❌    |
+//! |  `fn message_bus(...)`|  ✔︎  | ❌    | ❌    |
 //! |  `fn timeout(...)`  |  ✔︎    |       |  ✔︎    |
 //! |  `fn urgency(...)`  |  ✔︎    | ❌    | ❌    |
-//! |  `fn action(...)`   |  ✔︎    |       |        |
-//! |  `fn id(...)`       |  ✔︎    |       |        |
+//! |  `fn category(...)` |  ✔︎    | ❌    | ❌    |
+//! |  `fn desktop_entry(...)`|  ✔︎ | ❌    | ❌    |
+//! |  `fn transient(...)`|  ✔︎    | ❌    | ❌    |
+//! |  `fn resident(...)` |  ✔︎    | ❌    | ❌    |
+//! |  `fn suppress_sound(...)`|  ✔︎ | ❌    | ❌    |
+//! |  `fn sound_file(...)`|  ✔︎   | ❌    | ❌    |
+//! |  `fn priority(...)` |  ✔︎    | ✔︎     |  ✔︎    |
+//! |  `fn sound(...)`    |  ✔︎    | ✔︎     |  ✔︎    |
+//! |  `fn progress(...)` |  ✔︎    | ❌    |  ✔︎    |
+//! |  `fn action(...)`   |  ✔︎    | ✔︎     |  ✔︎    |
+//! |  `fn default_action(...)`|  ✔︎ | ✔︎     |  ✔︎    |
+//! |  `fn action_with_icon(...)`|  ✔︎ |       |        |
+//! |  `fn id(...)`       |  ✔︎    | ✔︎     |  ✔︎    |
+//! |  `fn tag(...)`      |  ✔︎    | ✔︎     |  ✔︎    |
+//! |  `fn group(...)`    |       | ✔︎     |  ✔︎    |
+//! |  `fn badge(...)`    |       | ✔︎     |        |
+//! |  `fn on_unsupported(...)`|  ✔︎ | ✔︎     |  ✔︎    |
+//! |  `fn validate(...)` |  ✔︎    | ✔︎     |  ✔︎    |
 //! |  `fn finalize(...)` |  ✔︎    | ✔︎     |  ✔︎    |
+//! |  `fn builder(...)`  |  ✔︎    | ✔︎     |  ✔︎    |
+//! |  `fn strict_builder(...)`|  ✔︎ | ✔︎     |  ✔︎    |
 //! |  `fn show(...)`     |  ✔︎    | ✔︎     |  ✔︎    |
+//! |  `fn show_no_wait(...)`|  ✔︎  | ✔︎     |  ✔︎    |
+//! |  `fn show_all(...)` |  ✔︎    | ✔︎     |  ✔︎    |
+//! |  `fn show_at(...)`  |  ✔︎    | ✔︎     |  ✔︎    |
+//! |  `fn show_after(...)`|  ✔︎   | ✔︎     |  ✔︎    |
+//! |  `fn show_with_connection(...)`|  ✔︎ | ❌ | ❌  |
+//! |  `fn show_in_terminal(...)`|  ✔︎ | ✔︎     |  ✔︎    |
+//! |  `fn show_debug(...)` |  ✔︎    | ✔︎     |  ✔︎    |
 //!
 //! ## `NotificationHandle`
 //!
-//! | method                   | XDG | macOS | windows |
-//! |--------------------------|-----|-------|---------|
-//! | `fn wait_for_action(...)`|  ✔︎  |  ❌  |   ❌   |
-//! | `fn close(...)`          |  ✔︎  |  ❌  |   ❌   |
-//! | `fn on_close(...)`       |  ✔︎  |  ❌  |   ❌   |
-//! | `fn update(...)`         |  ✔︎  |  ❌  |   ❌   |
-//! | `fn id(...)`             |  ✔︎  |  ❌  |   ❌   |
+//! | method                                 | XDG | macOS | windows |
+//! |----------------------------------------|-----|-------|---------|
+//! | `fn wait_for_action(...)`              |  ✔︎  |   ✔︎   |   ✔︎    |
+//! | `fn wait_for_action_result(...)`       |  ✔︎  |   ✔︎   |   ✔︎    |
+//! | `fn wait_for_action_with_timeout(...)` |  ✔︎  |   ✔︎   |   ✔︎    |
+//! | `fn wait_for_reply(...)`               |  ✔︎  |   ✔︎   |   ✔︎    |
+//! | `fn close(...)`                        |  ✔︎  |   ✔︎   |   ✔︎    |
+//! | `fn on_close(...)`                     |  ✔︎  |   ✔︎   |   ✔︎    |
+//! | `fn update(...)`                       |  ✔︎  |   ✔︎   |   ✔︎    |
+//! | `fn update_progress(...)`              |  ❌  |  ❌  |   ✔︎    |
+//! | `fn id(...)`                           |  ✔︎  |   ✔︎   |   ✔︎    |
+//! | `fn tag(...)`                          |  ✔︎  |   ✔︎   |   ✔︎    |
+//! | `fn group(...)`                        |  ❌  |   ✔︎   |   ✔︎    |
+//! | `fn supports_update(...)`              |  ✔︎  |   ✔︎   |   ✔︎    |
+//! | `fn supports_close(...)`               |  ✔︎  |   ✔︎   |   ✔︎    |
+//! | `fn to_token(...)`                     |  ✔︎  |  ❌  |   ❌   |
+//! | `fn from_token(...)`                   |  ✔︎  |  ❌  |   ❌   |
+//!
+//! Every platform's `NotificationHandle` has all of these methods,
+//! the ones marked with ❌ return `None` or an error.
+//! `update` only works on macOS for notifications delivered through the `UserNotifications` framework,
+//! check [`supports_update`](`NotificationHandle::supports_update`).
 //!
 //! ## Functions
 //!
@@ -115,6 +166,23 @@
 //! | `fn get_server_information(...)`           | ✔︎   |   ❌ |  ❌    |
 //! | `fn set_application(...)`                  | ❌  |   ✔︎  |  ❌    |
 //! | `fn get_bundle_identifier_or_default(...)` | ❌  |   ✔︎  |  ❌    |
+//! | `fn set_application_auto(...)`             | ❌  |   ✔︎  |  ❌    |
+//! | `fn detect_bundle_identifier(...)`         | ❌  |   ✔︎  |  ❌    |
+//! | `fn set_badge_count(...)`                  | ❌  |   ✔︎  |  ❌    |
+//! | `fn request_authorization(...)`            | ❌  |   ✔︎  |  ❌    |
+//! | `fn set_terminal_fallback(...)`            | ✔︎   |   ❌ |  ❌    |
+//! | `fn set_call_timeout(...)`                 | ✔︎   |   ❌ |  ❌    |
+//! | `fn set_dbus_namespace(...)`               | ✔︎   |   ❌ |  ❌    |
+//! | `fn set_retry_policy(...)`                 | ✔︎   |   ❌ |  ❌    |
+//! | `fn set_adapt_body(...)`                   | ✔︎   |   ❌ |  ❌    |
+//! | `fn set_server_quirks(...)`                | ✔︎   |   ❌ |  ❌    |
+//! | `fn set_defaults(...)`                     | ✔︎   |   ✔︎  |  ✔︎    |
+//! | `fn install_panic_hook(...)`               | ✔︎   |   ✔︎  |  ✔︎    |
+//! | `Capture::start()`                         | ✔︎   |   ✔︎  |  ✔︎    |
+//! | `Notifier::new()`                          | ✔︎   |   ✔︎  |  ✔︎    |
+//! | `fn is_available(...)`                     | ✔︎   |   ✔︎  |  ✔︎    |
+//! | `fn is_server_running(...)`                | ✔︎   |   ❌ |  ❌    |
+//! | `fn set_embedded_server(...)`              | ✔︎   |   ❌ |  ❌    |
 //!
 //!
 //! ### Toggles
@@ -153,63 +221,176 @@ extern crate dbus;
 #[cfg(target_os = "macos")]
 extern crate mac_notification_sys;
 
-#[cfg(target_os = "windows")]
-extern crate winrt_notification;
-
 #[macro_use]
 #[cfg(all(feature = "images", unix, not(target_os = "macos")))]
 extern crate lazy_static;
 
+mod action;
+mod body;
+pub mod builder;
+#[cfg(not(target_arch = "wasm32"))]
+mod capture;
+mod category;
+mod convert;
+mod defaults;
+mod definition;
 pub mod error;
 mod hints;
+mod macros;
+mod manager;
 mod miniver;
 mod notification;
+#[cfg(not(target_arch = "wasm32"))]
+mod notifier;
+mod panic;
+#[cfg(not(target_arch = "wasm32"))]
+mod schedule;
+mod sound;
+mod template;
+mod terminal;
 mod timeout;
+mod trace;
+mod unsupported;
 pub(crate) mod urgency;
+mod validate;
 
 #[cfg(target_os = "macos")]
 mod macos;
 
+#[cfg(any(target_os = "macos", test))]
+mod plist;
+
 #[cfg(target_os = "windows")]
 mod windows;
 
 #[cfg(all(unix, not(target_os = "macos")))]
 mod xdg;
 
-#[cfg(all(feature = "images", unix, not(target_os = "macos")))]
+#[cfg(all(unix, not(target_os = "macos")))]
+pub mod dunst;
+
+#[cfg(target_os = "linux")]
+mod wsl;
+
+#[cfg(target_os = "android")]
+mod termux;
+
+#[cfg(all(feature = "exec", unix, not(target_os = "macos")))]
+mod exec;
+
+#[cfg(target_arch = "wasm32")]
+mod web;
+
+mod xml;
+
+#[cfg(feature = "images")]
 mod image;
 
+#[cfg(feature = "gntp")]
+mod gntp;
+
+#[cfg(feature = "logger")]
+mod logger;
+
+#[cfg(feature = "push")]
+mod push;
+
 #[cfg(target_os = "macos")]
 pub use mac_notification_sys::{get_bundle_identifier_or_default, set_application};
 
 #[cfg(target_os = "macos")]
-pub use macos::NotificationHandle;
+pub use macos::{
+    detect_bundle_identifier, is_available, request_authorization, set_application_auto,
+    set_badge_count, Authorization, InterruptionLevel, NotificationHandle,
+};
+
+#[cfg(all(feature = "async", target_os = "macos"))]
+pub use macos::request_authorization_async;
+
+#[cfg(target_os = "windows")]
+pub use crate::windows::{is_available, register_app, IconCrop, NotificationHandle, Scenario};
+
+#[cfg(target_arch = "wasm32")]
+pub use crate::web::{is_available, request_permission, NotificationHandle};
+
+#[cfg(all(unix, not(target_os = "macos")))]
+pub use crate::xdg::{
+    dbus_stack, get_capabilities, get_server_information, handle_action, is_available,
+    is_server_running, set_adapt_body, set_call_timeout, set_dbus_namespace, set_retry_policy,
+    set_server_quirks, Capability, DbusNamespace, DbusStack, MessageBus, NotificationHandle,
+    ProgressNotification, RetryPolicy, ServerCapabilities, ServerKind,
+};
+
+pub use crate::action::{
+    Action, ActionCallbacks, ActionResponse, ActionResult, CloseHandler, CloseReason,
+};
+
+#[cfg(all(feature = "zbus", unix, not(target_os = "macos")))]
+pub use crate::xdg::ActionDispatcher;
 
 #[cfg(all(
-    any(feature = "dbus", feature = "zbus"),
+    feature = "embedded_server",
+    feature = "zbus",
     unix,
     not(target_os = "macos")
 ))]
-pub use crate::xdg::{
-    dbus_stack, get_capabilities, get_server_information, handle_action, ActionResponse,
-    CloseHandler, CloseReason, DbusStack, NotificationHandle,
-};
+pub use crate::xdg::set_embedded_server;
 
 // #[cfg(all(feature = "server", unix, not(target_os = "macos")))]
 // pub use crate::xdg::stop_server;
 
-pub use crate::hints::Hint;
+pub use crate::body::Body;
 
-#[cfg(all(feature = "images", unix, not(target_os = "macos")))]
+#[cfg(not(target_arch = "wasm32"))]
+pub use crate::capture::Capture;
+
+pub use crate::builder::NotificationBuilder;
+
+pub use crate::category::Category;
+
+pub use crate::convert::ToNotification;
+
+pub use crate::defaults::{set_defaults, Defaults};
+
+pub use crate::hints::{Hint, HintValue};
+
+pub use crate::manager::NotificationManager;
+
+pub use crate::panic::install_panic_hook;
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use crate::notifier::{Duplicates, Notifier, Outcome, SentNotification};
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use crate::schedule::ScheduledNotification;
+
+pub use crate::template::NotificationTemplate;
+
+#[cfg(feature = "images")]
 pub use crate::image::{Image, ImageError};
 
+#[cfg(feature = "gntp")]
+pub use crate::gntp::GntpServer;
+
+#[cfg(feature = "push")]
+pub use crate::push::PushServer;
+
+#[cfg(feature = "logger")]
+pub use crate::logger::NotificationLogger;
+
 #[cfg_attr(
     target_os = "macos",
     deprecated(note = "Urgency is not supported on macOS")
 )]
 pub use crate::urgency::Urgency;
 
-pub use crate::{notification::Notification, timeout::Timeout};
+pub use crate::urgency::Priority;
+
+pub use crate::terminal::{set_terminal_fallback, TerminalStyle};
+
+pub use crate::{
+    notification::Notification, sound::Sound, timeout::Timeout, unsupported::Unsupported,
+};
 
 #[cfg(all(feature = "images", unix, not(target_os = "macos")))]
 lazy_static! {
@@ -0,0 +1,234 @@
+//! A [`log`](https://docs.rs/log) logger turning important records into notifications, see [`NotificationLogger`].
+
+use crate::{notification::Notification, urgency::Priority};
+
+use log::{Level, LevelFilter, Log, Metadata, Record, SetLoggerError};
+
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+/// Shows log records at or above a level as notifications,
+/// so long running tools surface their errors without a terminal anyone looks at.
+///
+/// At most one notification is shown per [`interval`](`NotificationLogger::interval`),
+/// records in between are counted and mentioned by the next notification.
+/// Records can be passed on to another logger as well, see [`forward_to`](`NotificationLogger::forward_to`).
+///
+/// # Example
+/// ```no_run
+/// # use notify_rust::*;
+/// NotificationLogger::new(log::Level::Error)
+///     .appname("backup")
+///     .init()
+///     .unwrap();
+///
+/// log::error!("can't reach the backup server");
+/// ```
+///
+/// Records logged by notify-rust itself are never shown, that would only ever make things worse.
+#[derive(Debug)]
+pub struct NotificationLogger {
+    level: Level,
+    interval: Duration,
+    appname: Option<String>,
+    icon: Option<String>,
+    forward: Option<Forward>,
+    limiter: Mutex<Limiter>,
+}
+
+struct Forward(Box<dyn Log>);
+
+impl std::fmt::Debug for Forward {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("Forward")
+    }
+}
+
+#[derive(Debug, Default)]
+struct Limiter {
+    last_shown: Option<Instant>,
+    suppressed: usize,
+}
+
+impl Limiter {
+    /// Whether to show a record now, and how many were suppressed before it if so.
+    fn admit(&mut self, now: Instant, interval: Duration) -> Option<usize> {
+        if let Some(last_shown) = self.last_shown {
+            if now.duration_since(last_shown) < interval {
+                self.suppressed += 1;
+                return None;
+            }
+        }
+        self.last_shown = Some(now);
+        Some(std::mem::take(&mut self.suppressed))
+    }
+}
+
+impl NotificationLogger {
+    /// Shows records at `level` and above, at most one every 10 seconds.
+    pub fn new(level: Level) -> NotificationLogger {
+        NotificationLogger {
+            level,
+            interval: Duration::from_secs(10),
+            appname: None,
+            icon: None,
+            forward: None,
+            limiter: Mutex::default(),
+        }
+    }
+
+    /// How long to wait after showing a notification before showing the next.
+    pub fn interval(mut self, interval: Duration) -> NotificationLogger {
+        self.interval = interval;
+        self
+    }
+
+    /// Shows notifications under this name, see [`Notification::appname`].
+    pub fn appname(mut self, appname: &str) -> NotificationLogger {
+        self.appname = Some(appname.to_owned());
+        self
+    }
+
+    /// Shows notifications with this icon, see [`Notification::icon`].
+    pub fn icon(mut self, icon: &str) -> NotificationLogger {
+        self.icon = Some(icon.to_owned());
+        self
+    }
+
+    /// Passes every record on to `logger` as well, e.g. one writing to stderr or a file.
+    pub fn forward_to(mut self, logger: impl Log + 'static) -> NotificationLogger {
+        self.forward = Some(Forward(Box::new(logger)));
+        self
+    }
+
+    /// Installs this as the global logger, see [`log::set_boxed_logger`].
+    ///
+    /// Sets the maximum level to this logger's level, or [`LevelFilter::Trace`] if records are forwarded,
+    /// lower it via [`log::set_max_level`] as needed.
+    pub fn init(self) -> Result<(), SetLoggerError> {
+        let max_level = if self.forward.is_some() {
+            LevelFilter::Trace
+        } else {
+            self.level.to_level_filter()
+        };
+        log::set_boxed_logger(Box::new(self))?;
+        log::set_max_level(max_level);
+        Ok(())
+    }
+
+    fn shows(&self, metadata: &Metadata<'_>) -> bool {
+        metadata.level() <= self.level && !metadata.target().starts_with("notify_rust")
+    }
+
+    fn notification(&self, record: &Record<'_>, suppressed: usize) -> Notification {
+        let mut notification = Notification::new();
+        if let Some(appname) = &self.appname {
+            notification.appname(appname);
+        }
+        if let Some(icon) = &self.icon {
+            notification.icon(icon);
+        }
+        let mut body = record.args().to_string();
+        if suppressed > 0 {
+            body.push_str(&format!(
+                "\n({} more since the last notification)",
+                suppressed
+            ));
+        }
+        notification
+            .summary(format!("{} in {}", record.level(), record.target()))
+            .body(body)
+            .priority(if record.level() == Level::Error {
+                Priority::High
+            } else {
+                Priority::Normal
+            });
+        notification
+    }
+}
+
+impl Log for NotificationLogger {
+    fn enabled(&self, metadata: &Metadata<'_>) -> bool {
+        self.shows(metadata)
+            || self
+                .forward
+                .as_ref()
+                .map_or(false, |forward| forward.0.enabled(metadata))
+    }
+
+    fn log(&self, record: &Record<'_>) {
+        if let Some(forward) = &self.forward {
+            forward.0.log(record);
+        }
+        if !self.shows(record.metadata()) {
+            return;
+        }
+        let suppressed = match self.limiter.lock() {
+            Ok(mut limiter) => limiter.admit(Instant::now(), self.interval),
+            Err(_) => return,
+        };
+        // the lock is released, showing may log again
+        if let Some(suppressed) = suppressed {
+            let _ = self.notification(record, suppressed).show_no_wait();
+        }
+    }
+
+    fn flush(&self) {
+        if let Some(forward) = &self.forward {
+            forward.0.flush();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn limits_rate() {
+        let mut limiter = Limiter::default();
+        let start = Instant::now();
+        let interval = Duration::from_secs(10);
+        assert_eq!(limiter.admit(start, interval), Some(0));
+        assert_eq!(
+            limiter.admit(start + Duration::from_secs(1), interval),
+            None
+        );
+        assert_eq!(
+            limiter.admit(start + Duration::from_secs(2), interval),
+            None
+        );
+        assert_eq!(
+            limiter.admit(start + Duration::from_secs(12), interval),
+            Some(2)
+        );
+    }
+
+    #[test]
+    fn describes_record() {
+        let logger = NotificationLogger::new(Level::Warn).appname("backup");
+        let notification = logger.notification(
+            &Record::builder()
+                .level(Level::Error)
+                .target("backup::upload")
+                .args(format_args!("connection refused"))
+                .build(),
+            3,
+        );
+        assert_eq!(notification.appname, "backup");
+        assert_eq!(notification.summary, "ERROR in backup::upload");
+        assert_eq!(
+            notification.body,
+            "connection refused\n(3 more since the last notification)"
+        );
+        assert!(!logger.shows(&Metadata::builder().level(Level::Info).build()));
+        assert!(!logger.shows(
+            &Metadata::builder()
+                .level(Level::Error)
+                .target("notify_rust::xdg")
+                .build()
+        ));
+    }
+}
@@ -1,21 +1,362 @@
-use crate::{error::*, notification::Notification};
+use crate::{
+    action::{
+        ActionCallbacks, ActionResponse, ActionResult, CloseHandler, CloseReason, DEFAULT_ACTION,
+    },
+    error::*,
+    notification::Notification,
+    plist,
+    timeout::Timeout,
+    urgency::Priority,
+};
 
 pub use mac_notification_sys::error::{ApplicationError, Error as MacOsError, NotificationError};
+use mac_notification_sys::{MainButton, NotificationResponse};
+// mac-notification-sys delivers through the same deprecated API, so that is where they are found
+#[allow(deprecated)]
+use objc2_foundation::{NSUserNotification, NSUserNotificationCenter};
 
-use std::ops::{Deref, DerefMut};
+use block2::{DynBlock, RcBlock};
+use objc2::runtime::Bool;
+use objc2_foundation::{NSArray, NSError, NSInteger, NSNumber, NSString, NSURL};
+use objc2_user_notifications::{
+    UNAuthorizationOptions, UNAuthorizationStatus, UNMutableNotificationContent,
+    UNNotificationAttachment, UNNotificationInterruptionLevel, UNNotificationRequest,
+    UNNotificationSetting, UNNotificationSettings, UNNotificationSound,
+    UNTimeIntervalNotificationTrigger, UNUserNotificationCenter,
+};
+
+use std::{
+    env, fs,
+    ops::{Deref, DerefMut},
+    process,
+    ptr::NonNull,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        mpsc, Arc, Mutex,
+    },
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+#[cfg(feature = "async")]
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll, Waker},
+};
+
+/// Identifies the notifications shown by this process, unless they were given an [`id`](`Notification::id`).
+static NEXT_ID: AtomicU32 = AtomicU32::new(1);
+
+/// Decides whether a notification breaks through Focus, see [`Notification::interruption_level`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum InterruptionLevel {
+    /// Shows up in the Notification Center without lighting up the screen or playing a sound.
+    Passive,
+    /// A regular notification.
+    #[default]
+    Active,
+    /// Breaks through Focus, if the user allowed time sensitive notifications for this application.
+    TimeSensitive,
+    /// Breaks through Focus and the mute switch,
+    /// only for applications with the `com.apple.developer.usernotifications.critical-alerts` entitlement.
+    Critical,
+}
+
+impl InterruptionLevel {
+    fn of(notification: &Notification) -> InterruptionLevel {
+        match (notification.interruption_level, notification.priority) {
+            (InterruptionLevel::Active, Priority::Low) => InterruptionLevel::Passive,
+            (InterruptionLevel::Active, Priority::High) => InterruptionLevel::TimeSensitive,
+            (level, _) => level,
+        }
+    }
+}
+
+impl From<InterruptionLevel> for UNNotificationInterruptionLevel {
+    fn from(level: InterruptionLevel) -> UNNotificationInterruptionLevel {
+        match level {
+            InterruptionLevel::Passive => UNNotificationInterruptionLevel::Passive,
+            InterruptionLevel::Active => UNNotificationInterruptionLevel::Active,
+            InterruptionLevel::TimeSensitive => UNNotificationInterruptionLevel::TimeSensitive,
+            InterruptionLevel::Critical => UNNotificationInterruptionLevel::Critical,
+        }
+    }
+}
 
 /// A handle to a shown notification.
 ///
-/// This keeps a connection alive to ensure actions work on certain desktops.
+/// macOS reports back how the user reacted to a notification with [`action`](`Notification::action`)s
+/// before `show()` returns, this handle holds on to that response.
 #[derive(Debug)]
 pub struct NotificationHandle {
+    id: u32,
     notification: Notification,
+    response: NotificationResponse,
+    // scheduled notifications are delivered after `schedule_at()` returned, nobody waits for them
+    scheduled: bool,
+    // set if the notification was requested through the UserNotifications framework
+    request_identifier: Option<String>,
+    // set if a `Capture` recorded the notification instead of delivering it
+    captured: bool,
 }
 
 impl NotificationHandle {
     #[allow(missing_docs)]
     pub fn new(notification: Notification) -> NotificationHandle {
-        NotificationHandle { notification }
+        NotificationHandle::with_response(notification, NotificationResponse::None)
+    }
+
+    fn with_response(
+        notification: Notification,
+        response: NotificationResponse,
+    ) -> NotificationHandle {
+        NotificationHandle {
+            id: next_id(&notification),
+            notification,
+            response,
+            scheduled: false,
+            request_identifier: None,
+            captured: false,
+        }
+    }
+
+    pub(crate) fn captured(id: u32, notification: Notification) -> NotificationHandle {
+        NotificationHandle {
+            id,
+            notification,
+            response: NotificationResponse::None,
+            scheduled: false,
+            request_identifier: None,
+            captured: true,
+        }
+    }
+
+    fn scheduled(notification: Notification) -> NotificationHandle {
+        NotificationHandle {
+            id: next_id(&notification),
+            notification,
+            response: NotificationResponse::None,
+            scheduled: true,
+            request_identifier: None,
+            captured: false,
+        }
+    }
+
+    /// Calls `invocation_closure` with the identifier of the action the user picked.
+    ///
+    /// Clicking the notification itself is reported as `"default"`,
+    /// `"__closed"` is passed if the notification was closed without picking an action,
+    /// just like on xdg.
+    ///
+    /// Scheduled notifications can't report back, `invocation_closure` is never called for them.
+    pub fn wait_for_action<F>(self, invocation_closure: F)
+    where
+        F: FnOnce(&str),
+    {
+        if self.captured {
+            return match crate::capture::next_response() {
+                ActionResult::Action(action) => invocation_closure(&action),
+                ActionResult::Closed(_reason) => invocation_closure("__closed"), // FIXME: remove backward compatibility with 5.0
+                ActionResult::Timeout => {}
+            };
+        }
+        match self.action() {
+            Some(ActionResponse::Custom(action)) => invocation_closure(action),
+            Some(ActionResponse::Closed(_reason)) => invocation_closure("__closed"), // FIXME: remove backward compatibility with 5.0
+            None => {}
+        }
+    }
+
+    /// Returns how the user reacted to the notification.
+    ///
+    /// Scheduled notifications can't report back, this returns [`ActionResult::Timeout`] for them.
+    pub fn wait_for_action_result(self) -> ActionResult {
+        if self.captured {
+            return crate::capture::next_response();
+        }
+        self.action()
+            .as_ref()
+            .map_or(ActionResult::Timeout, ActionResult::from)
+    }
+
+    /// Registers a `callback` for the action with this `identifier`,
+    /// chain more of them and [`wait`](`crate::ActionCallbacks::wait`) for the user to pick one.
+    pub fn on_action<'a>(
+        self,
+        identifier: &str,
+        callback: impl FnOnce() + 'a,
+    ) -> ActionCallbacks<'a> {
+        ActionCallbacks::new(move || self.wait_for_action_result()).on_action(identifier, callback)
+    }
+
+    /// Registers a `callback` for clicking the notification itself,
+    /// see [`default_action`](`crate::Notification::default_action`).
+    pub fn on_click<'a>(self, callback: impl FnOnce() + 'a) -> ActionCallbacks<'a> {
+        self.on_action(DEFAULT_ACTION, callback)
+    }
+
+    /// Same as [`wait_for_action_result`](`NotificationHandle::wait_for_action_result`),
+    /// the response is already known by the time `show()` returns.
+    pub fn wait_for_action_with_timeout(self, _timeout: Duration) -> ActionResult {
+        self.wait_for_action_result()
+    }
+
+    /// Returns the text the user typed into the field added by
+    /// [`reply_input`](`Notification::reply_input`).
+    ///
+    /// Returns `None` if the notification went away without a reply.
+    pub fn wait_for_reply(self) -> Option<String> {
+        match self.response {
+            NotificationResponse::Reply(reply) => Some(reply),
+            _ => None,
+        }
+    }
+
+    /// Calls `handler` if the notification was closed without picking an action.
+    pub fn on_close<A>(self, handler: impl CloseHandler<A>) {
+        if self.captured {
+            if let ActionResult::Closed(reason) = crate::capture::next_response() {
+                handler.call(reason);
+            }
+        } else if let Some(ActionResponse::Closed(reason)) = self.action() {
+            handler.call(reason);
+        }
+    }
+
+    /// Returns the id passed to [`Notification::id`], or one unique to this process.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Which API delivered the notification, for logging.
+    pub(crate) fn backend(&self) -> &'static str {
+        if self.captured {
+            "capture"
+        } else if self.request_identifier.is_some() {
+            "UserNotifications"
+        } else {
+            "NSUserNotificationCenter"
+        }
+    }
+
+    /// The notification's [`tag`](`Notification::tag`), which identifies its request in the Notification Center.
+    pub fn tag(&self) -> Option<&str> {
+        self.notification.tag.as_deref()
+    }
+
+    /// The notification's [`group`](`Notification::group`), which is its thread in the Notification Center.
+    pub fn group(&self) -> Option<&str> {
+        self.notification.group.as_deref()
+    }
+
+    /// Replaces the notification with an updated version, delivered right away.
+    ///
+    /// Only notifications delivered through the `UserNotifications` framework can be replaced,
+    /// this fails for all others, see [`supports_update`](`NotificationHandle::supports_update`).
+    pub fn update(&mut self) -> Result<()> {
+        if self.captured {
+            crate::capture::record_update(&self.notification);
+            return Ok(());
+        }
+        let trace = crate::trace::start("update", Some(self.id), &self.notification);
+        let result = match &self.request_identifier {
+            Some(identifier) => request(&self.notification, identifier, None),
+            None => Err("only notifications delivered through the UserNotifications framework can be updated".into()),
+        };
+        trace.finish(&result);
+        result
+    }
+
+    /// Fails, macOS notifications have no progress bar.
+    pub fn update_progress(&mut self, _value: f32, _status: &str) -> Result<()> {
+        Err("macOS notifications have no progress bar".into())
+    }
+
+    /// Whether [`update`](`NotificationHandle::update`) can replace this notification,
+    /// which only works if it was delivered through the `UserNotifications` framework.
+    pub fn supports_update(&self) -> bool {
+        self.captured || self.request_identifier.is_some()
+    }
+
+    /// Removes the notification from the Notification Center,
+    /// or withdraws it if it was scheduled and not delivered yet.
+    ///
+    /// mac-notification-sys doesn't tell which notification `show()` delivered, so unless it was
+    /// requested through the `UserNotifications` framework, this removes every one of this
+    /// application's notifications with the same summary, subtitle and body.
+    /// Notifications the user already interacted with are gone already, closing them does nothing.
+    pub fn close(self) -> Result<()> {
+        if self.captured {
+            crate::capture::record_close(&self.notification);
+            return Ok(());
+        }
+        let trace = crate::trace::start("close", Some(self.id), &self.notification);
+        let result = self.remove();
+        trace.finish(&result);
+        result
+    }
+
+    #[allow(deprecated)]
+    fn remove(&self) -> Result<()> {
+        if let Some(identifier) = &self.request_identifier {
+            let identifiers = NSArray::from_retained_slice(&[NSString::from_str(identifier)]);
+            let center = UNUserNotificationCenter::currentNotificationCenter();
+            center.removeDeliveredNotificationsWithIdentifiers(&identifiers);
+            center.removePendingNotificationRequestsWithIdentifiers(&identifiers);
+            return Ok(());
+        }
+        let center = NSUserNotificationCenter::defaultUserNotificationCenter();
+        for delivered in center.deliveredNotifications().to_vec() {
+            if self.shows(&delivered) {
+                center.removeDeliveredNotification(&delivered);
+            }
+        }
+        for scheduled in center.scheduledNotifications().to_vec() {
+            if self.shows(&scheduled) {
+                center.removeScheduledNotification(&scheduled);
+            }
+        }
+        Ok(())
+    }
+
+    /// Whether `close` can remove the shown notification on this platform.
+    pub fn supports_close(&self) -> bool {
+        true
+    }
+
+    #[allow(deprecated)]
+    fn shows(&self, delivered: &NSUserNotification) -> bool {
+        fn text(text: Option<impl ToString>) -> String {
+            text.map(|text| text.to_string()).unwrap_or_default()
+        }
+        text(delivered.title()) == self.notification.summary
+            && text(delivered.subtitle())
+                == self.notification.subtitle.as_deref().unwrap_or_default()
+            && text(delivered.informativeText()) == self.notification.body
+    }
+
+    /// Maps the response back onto the identifiers passed to [`Notification::action`].
+    ///
+    /// Returns `None` for scheduled notifications, whose response is unknown.
+    fn action(&self) -> Option<ActionResponse<'_>> {
+        if self.scheduled {
+            return None;
+        }
+        Some(match &self.response {
+            NotificationResponse::ActionButton(label) => self
+                .notification
+                .actions
+                .iter()
+                .find(|action| action.label == *label)
+                .map_or(ActionResponse::Custom(label), |action| {
+                    ActionResponse::Custom(&action.identifier)
+                }),
+            NotificationResponse::Click => ActionResponse::Custom(DEFAULT_ACTION),
+            NotificationResponse::Reply(_) => ActionResponse::Custom("reply"),
+            NotificationResponse::CloseButton(_) => ActionResponse::Closed(CloseReason::Dismissed),
+            NotificationResponse::None => ActionResponse::Closed(CloseReason::Expired),
+        })
     }
 }
 
@@ -34,28 +375,403 @@ impl DerefMut for NotificationHandle {
     }
 }
 
-pub(crate) fn show_notification(notification: &Notification) -> Result<NotificationHandle> {
-    mac_notification_sys::Notification::default()
-        .title(notification.summary.as_str())
-        .message(&notification.body)
-        .maybe_subtitle(notification.subtitle.as_deref())
-        .maybe_sound(notification.sound_name.as_deref())
-        .send()?;
+/// Reads the `CFBundleIdentifier` of the `.app` bundle the current executable lives in.
+///
+/// Returns `None` for executables outside of a bundle, e.g. while running via `cargo run`.
+pub fn detect_bundle_identifier() -> Option<String> {
+    let exe = env::current_exe().ok()?;
+    let macos_dir = exe.parent()?;
+    if macos_dir.file_name()? != "MacOS" {
+        return None;
+    }
+    let info_plist = fs::read_to_string(macos_dir.parent()?.join("Info.plist")).ok()?;
+    plist::bundle_identifier(&info_plist)
+}
+
+/// Like [`set_application`](`crate::set_application`), but uses the
+/// [detected](`detect_bundle_identifier`) bundle identifier, falling back to `default`.
+pub fn set_application_auto(default: &str) -> Result<()> {
+    let bundle_ident = detect_bundle_identifier().unwrap_or_else(|| default.to_owned());
+    mac_notification_sys::set_application(&bundle_ident)?;
+    Ok(())
+}
 
-    Ok(NotificationHandle::new(notification.clone()))
+pub(crate) fn show_notification(notification: &Notification) -> Result<NotificationHandle> {
+    notification.check_supported(unsupported)?;
+    if uses_user_notifications(notification) {
+        let mut handle = NotificationHandle::new(notification.clone());
+        let identifier = request_identifier(notification, handle.id);
+        request(notification, &identifier, None)?;
+        handle.request_identifier = Some(identifier);
+        return Ok(handle);
+    }
+    let response = send(notification, None)?;
+    Ok(NotificationHandle::with_response(
+        notification.clone(),
+        response,
+    ))
 }
 
+/// Scheduled notifications are delivered after this returned,
+/// so they can't have actions or a reply input anyone would wait for.
 pub(crate) fn schedule_notification(
     notification: &Notification,
     delivery_date: f64,
 ) -> Result<NotificationHandle> {
-    mac_notification_sys::Notification::default()
+    if !notification.actions.is_empty() || notification.reply_placeholder.is_some() {
+        return Err("scheduled notifications can't report responses, they can't have actions or a reply input".into());
+    }
+    notification.check_supported(unsupported)?;
+    let mut handle = NotificationHandle::scheduled(notification.clone());
+    if uses_user_notifications(notification) {
+        let identifier = request_identifier(notification, handle.id);
+        request(notification, &identifier, Some(delivery_date))?;
+        handle.request_identifier = Some(identifier);
+    } else {
+        send(notification, Some(delivery_date))?;
+    }
+    Ok(handle)
+}
+
+/// Whether the notification needs anything only the `UserNotifications` framework offers.
+///
+/// The framework only works inside a `.app` bundle and can't wait for the user's response,
+/// every other notification is delivered by mac-notification-sys.
+fn uses_user_notifications(notification: &Notification) -> bool {
+    (notification.group.is_some()
+        || notification.tag.is_some()
+        || InterruptionLevel::of(notification) != InterruptionLevel::Active)
+        && notification.actions.is_empty()
+        && notification.reply_placeholder.is_none()
+        && detect_bundle_identifier().is_some()
+}
+
+/// What `notification` asks for that won't be shown.
+///
+/// Grouping, tags, badges and interruption levels need the `UserNotifications` framework,
+/// which isn't used for notifications with actions or outside of a `.app` bundle.
+fn unsupported(notification: &Notification) -> Vec<&'static str> {
+    let mut unsupported = Vec::new();
+    if !notification.icon.is_empty() {
+        unsupported.push("icon");
+    }
+    if notification.timeout != Timeout::Default {
+        unsupported.push("timeout");
+    }
+    if !uses_user_notifications(notification) {
+        if notification.group.is_some() {
+            unsupported.push("group");
+        }
+        if notification.tag.is_some() {
+            unsupported.push("tag");
+        }
+        if notification.badge.is_some() {
+            unsupported.push("badge");
+        }
+        if InterruptionLevel::of(notification) != InterruptionLevel::Active {
+            unsupported.push("interruption level");
+        }
+    }
+    unsupported
+}
+
+fn next_id(notification: &Notification) -> u32 {
+    notification
+        .id
+        .unwrap_or_else(|| NEXT_ID.fetch_add(1, Ordering::Relaxed))
+}
+
+/// The notification's [`tag`](`Notification::tag`), or an identifier unique to it.
+fn request_identifier(notification: &Notification, id: u32) -> String {
+    notification
+        .tag
+        .clone()
+        .unwrap_or_else(|| format!("notify-rust-{}-{}", process::id(), id))
+}
+
+/// Requests the notification from the `UserNotifications` framework,
+/// replacing any earlier request with the same `identifier`.
+fn request(
+    notification: &Notification,
+    identifier: &str,
+    delivery_date: Option<f64>,
+) -> Result<()> {
+    let notification = notification.with_image_file()?;
+
+    let content = UNMutableNotificationContent::new();
+    content.setTitle(&NSString::from_str(&notification.summary));
+    content.setBody(&NSString::from_str(&notification.body));
+    if let Some(subtitle) = &notification.subtitle {
+        content.setSubtitle(&NSString::from_str(subtitle));
+    }
+    if let Some(group) = &notification.group {
+        content.setThreadIdentifier(&NSString::from_str(group));
+    }
+    if let Some(badge) = notification.badge {
+        content.setBadge(Some(&NSNumber::new_u32(badge)));
+    }
+    content.setInterruptionLevel(InterruptionLevel::of(&notification).into());
+    if let Some(sound_name) = &notification.sound_name {
+        let sound = match sound_name.as_str() {
+            "NSUserNotificationDefaultSoundName" => UNNotificationSound::defaultSound(),
+            name => UNNotificationSound::soundNamed(&NSString::from_str(name)),
+        };
+        content.setSound(Some(&sound));
+    }
+    if let Some(image_path) = &notification.path_to_image {
+        let url = NSURL::fileURLWithPath(&NSString::from_str(image_path));
+        // SAFETY: no options are passed, so there are none of the wrong type
+        #[allow(unsafe_code)]
+        let attachment = unsafe {
+            UNNotificationAttachment::attachmentWithIdentifier_URL_options_error(
+                &NSString::from_str("image"),
+                &url,
+                None,
+            )
+        }
+        .map_err(|error| Error::from(error.localizedDescription().to_string().as_str()))?;
+        content.setAttachments(&NSArray::from_retained_slice(&[attachment]));
+    }
+
+    let trigger = delivery_date.map(|delivery_date| {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs_f64();
+        // the framework rejects intervals that aren't positive
+        UNTimeIntervalNotificationTrigger::triggerWithTimeInterval_repeats(
+            (delivery_date - now).max(1.0),
+            false,
+        )
+    });
+    let request = UNNotificationRequest::requestWithIdentifier_content_trigger(
+        &NSString::from_str(identifier),
+        &content,
+        trigger.as_deref().map(|trigger| &**trigger),
+    );
+
+    let added = completes(|completion_handler| {
+        UNUserNotificationCenter::currentNotificationCenter()
+            .addNotificationRequest_withCompletionHandler(&request, Some(completion_handler));
+    });
+    if !added {
+        return Err("the Notification Center refused the notification, is this application authorized to show notifications?".into());
+    }
+    Ok(())
+}
+
+/// Delivers the notification, waiting for the user's response if it has actions or a reply field.
+///
+/// The `"default"` action is invoked by clicking the notification itself, all others become buttons,
+/// more than one of them are collected in a dropdown.
+/// A reply field takes the place of the buttons.
+fn send(notification: &Notification, delivery_date: Option<f64>) -> Result<NotificationResponse> {
+    let notification = notification.with_image_file()?;
+    let labels = notification
+        .actions
+        .iter()
+        .filter(|action| action.identifier != DEFAULT_ACTION)
+        .map(|action| action.label.as_str())
+        .collect::<Vec<_>>();
+    let wait_for_click = notification
+        .actions
+        .iter()
+        .any(|action| action.identifier == DEFAULT_ACTION);
+
+    let mut mac_notification = mac_notification_sys::Notification::default();
+    mac_notification
         .title(notification.summary.as_str())
         .message(&notification.body)
         .maybe_subtitle(notification.subtitle.as_deref())
         .maybe_sound(notification.sound_name.as_deref())
-        .delivery_date(delivery_date)
-        .send()?;
+        .wait_for_click(wait_for_click);
+    if let Some(image_path) = &notification.path_to_image {
+        mac_notification.content_image(image_path);
+    }
+    match (&notification.reply_placeholder, labels.as_slice()) {
+        (Some(placeholder), _) => {
+            mac_notification.main_button(MainButton::Response(placeholder));
+        }
+        (None, []) => {}
+        (None, [label]) => {
+            mac_notification.main_button(MainButton::SingleAction(label));
+        }
+        (None, [first, ..]) => {
+            mac_notification.main_button(MainButton::DropdownActions(first, &labels));
+        }
+    }
+    if let Some(delivery_date) = delivery_date {
+        mac_notification.delivery_date(delivery_date);
+    }
+    Ok(mac_notification.send()?)
+}
+
+/// Checks whether notifications can be shown, which on macOS they always can.
+///
+/// Exists so code shared with linux and BSD doesn't need to `cfg` this away.
+pub fn is_available() -> bool {
+    true
+}
+
+/// Sets the number shown on the application's icon, `0` removes it.
+///
+/// Use [`Notification::badge`] to update it alongside a notification instead.
+/// Only works for applications inside a `.app` bundle on macOS 14 or newer.
+pub fn set_badge_count(count: u32) -> Result<()> {
+    if detect_bundle_identifier().is_none() {
+        return Err("the badge count can only be set by applications inside a .app bundle".into());
+    }
+    let set = completes(|completion_handler| {
+        UNUserNotificationCenter::currentNotificationCenter()
+            .setBadgeCount_withCompletionHandler(count as NSInteger, Some(completion_handler));
+    });
+    if !set {
+        return Err("the badge count could not be set".into());
+    }
+    Ok(())
+}
+
+/// Passes a completion handler to `call` and blocks until it reports whether it succeeded.
+fn completes(call: impl FnOnce(&DynBlock<dyn Fn(*mut NSError)>)) -> bool {
+    let (sender, completed) = mpsc::channel();
+    let completion_handler = RcBlock::new(move |error: *mut NSError| {
+        let _ = sender.send(error.is_null());
+    });
+    call(&completion_handler);
+    completed.recv() == Ok(true)
+}
+
+/// What the user allowed this application to do, as reported by [`request_authorization`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Authorization {
+    /// Whether the application may show notifications at all.
+    pub granted: bool,
+    /// Whether notifications are shown as banners or alerts.
+    pub alert: bool,
+    /// Whether notifications may play a sound.
+    pub sound: bool,
+    /// Whether notifications may set the [`badge`](`Notification::badge`).
+    pub badge: bool,
+    /// Whether [time sensitive](`InterruptionLevel::TimeSensitive`) notifications break through Focus.
+    pub time_sensitive: bool,
+    /// Whether [critical](`InterruptionLevel::Critical`) notifications break through Focus and the mute switch.
+    pub critical: bool,
+}
+
+impl Authorization {
+    fn from_settings(settings: &UNNotificationSettings) -> Authorization {
+        let enabled = |setting: UNNotificationSetting| setting == UNNotificationSetting::Enabled;
+        Authorization {
+            granted: matches!(
+                settings.authorizationStatus(),
+                UNAuthorizationStatus::Authorized | UNAuthorizationStatus::Provisional
+            ),
+            alert: enabled(settings.alertSetting()),
+            sound: enabled(settings.soundSetting()),
+            badge: enabled(settings.badgeSetting()),
+            time_sensitive: enabled(settings.timeSensitiveSetting()),
+            critical: enabled(settings.criticalAlertSetting()),
+        }
+    }
+}
+
+/// Asks the user to allow this application to show notifications and reports what they allowed.
+///
+/// The user is only asked once, later calls report the settings they picked back then,
+/// including any changes they made in the System Settings since.
+/// Only notifications delivered through the `UserNotifications` framework need this, see
+/// [`Notification::group`], [`Notification::interruption_level`] and [`Notification::badge`].
+/// Only works for applications inside a `.app` bundle.
+pub fn request_authorization() -> Result<Authorization> {
+    let (sender, authorized) = mpsc::channel();
+    let sender = Mutex::new(sender);
+    authorize(move |authorization| {
+        if let Ok(sender) = sender.lock() {
+            let _ = sender.send(authorization);
+        }
+    });
+    authorized
+        .recv()
+        .unwrap_or(Err(AUTHORIZATION_FAILED))
+        .map_err(Error::from)
+}
+
+/// Async version of [`request_authorization`].
+#[cfg(feature = "async")]
+pub async fn request_authorization_async() -> Result<Authorization> {
+    let pending = Arc::new(Mutex::new(PendingAuthorization::default()));
+    authorize({
+        let pending = Arc::clone(&pending);
+        move |authorization| {
+            if let Ok(mut pending) = pending.lock() {
+                pending.authorization = Some(authorization);
+                if let Some(waker) = pending.waker.take() {
+                    waker.wake();
+                }
+            }
+        }
+    });
+    AuthorizationFuture(pending).await.map_err(Error::from)
+}
+
+const AUTHORIZATION_FAILED: &str = "requesting authorization to show notifications failed";
 
-    Ok(NotificationHandle::new(notification.clone()))
+/// Requests authorization and passes the resulting settings to `done`, on another thread.
+fn authorize(
+    done: impl Fn(std::result::Result<Authorization, &'static str>) + Send + Sync + 'static,
+) {
+    if detect_bundle_identifier().is_none() {
+        return done(Err(
+            "authorization can only be requested by applications inside a .app bundle",
+        ));
+    }
+    let done = Arc::new(done);
+    let completion_handler = RcBlock::new(move |_granted: Bool, error: *mut NSError| {
+        if !error.is_null() {
+            return done(Err(AUTHORIZATION_FAILED));
+        }
+        let done = Arc::clone(&done);
+        let settings_handler = RcBlock::new(move |settings: NonNull<UNNotificationSettings>| {
+            // SAFETY: the framework passes a valid settings object that outlives the handler
+            #[allow(unsafe_code)]
+            let settings = unsafe { settings.as_ref() };
+            done(Ok(Authorization::from_settings(settings)));
+        });
+        UNUserNotificationCenter::currentNotificationCenter()
+            .getNotificationSettingsWithCompletionHandler(&settings_handler);
+    });
+    UNUserNotificationCenter::currentNotificationCenter()
+        .requestAuthorizationWithOptions_completionHandler(
+            UNAuthorizationOptions::Alert
+                | UNAuthorizationOptions::Sound
+                | UNAuthorizationOptions::Badge,
+            &completion_handler,
+        );
+}
+
+#[cfg(feature = "async")]
+#[derive(Default)]
+struct PendingAuthorization {
+    authorization: Option<std::result::Result<Authorization, &'static str>>,
+    waker: Option<Waker>,
+}
+
+#[cfg(feature = "async")]
+struct AuthorizationFuture(Arc<Mutex<PendingAuthorization>>);
+
+#[cfg(feature = "async")]
+impl Future for AuthorizationFuture {
+    type Output = std::result::Result<Authorization, &'static str>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut pending = match self.0.lock() {
+            Ok(pending) => pending,
+            Err(_) => return Poll::Ready(Err(AUTHORIZATION_FAILED)),
+        };
+        if let Some(authorization) = pending.authorization.take() {
+            return Poll::Ready(authorization);
+        }
+        pending.waker = Some(cx.waker().clone());
+        Poll::Pending
+    }
 }
@@ -0,0 +1,125 @@
+//! The [`notification!`](`crate::notification!`) macro.
+
+/// Builds a [`Notification`](`crate::Notification`) in one expression.
+///
+/// Every `name: value` calls the builder method of that name.
+/// `summary`, `body`, `subtitle`, `appname` and `icon` take `format!` arguments,
+/// `urgency` and `priority` take the name of the level.
+/// Fields are separated by `;`, or by `,` unless the `,` belongs to the value.
+///
+/// ```no_run
+/// # use notify_rust::*;
+/// # let err = "exit code 1";
+/// notification!(summary: "Build failed", body: "{}", err; priority: High; action: "log", "Show log")
+///     .show()?;
+///
+/// let job = "docs";
+/// let notification = notification!(summary: "{job} done", timeout: Timeout::Never);
+/// # Ok::<(), notify_rust::error::Error>(())
+/// ```
+#[macro_export]
+macro_rules! notification {
+    ($($fields:tt)*) => {{
+        #[allow(unused_mut)]
+        let mut notification = $crate::Notification::new();
+        $crate::__notification_fields!(notification [] $($fields)*);
+        notification
+    }};
+}
+
+/// Splits the fields of [`notification!`](`crate::notification!`), collecting a field's tokens until the next one starts.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __notification_fields {
+    ($n:ident []) => {};
+    ($n:ident [] $key:ident : $($rest:tt)*) => {
+        $crate::__notification_fields!($n [$key] $($rest)*);
+    };
+    ($n:ident [$key:ident $($value:tt)*] $(,)? $(;)?) => {
+        $crate::__notification_field!($n $key $($value)*);
+    };
+    ($n:ident [$key:ident $($value:tt)*] , $next:ident : $($rest:tt)*) => {
+        $crate::__notification_field!($n $key $($value)*);
+        $crate::__notification_fields!($n [$next] $($rest)*);
+    };
+    ($n:ident [$key:ident $($value:tt)*] ; $next:ident : $($rest:tt)*) => {
+        $crate::__notification_field!($n $key $($value)*);
+        $crate::__notification_fields!($n [$next] $($rest)*);
+    };
+    ($n:ident [$($field:tt)*] $token:tt $($rest:tt)*) => {
+        $crate::__notification_fields!($n [$($field)* $token] $($rest)*);
+    };
+}
+
+/// Calls the builder method for one field of [`notification!`](`crate::notification!`).
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __notification_field {
+    (@text $n:ident $method:ident $format:literal $($args:tt)*) => {
+        $n.$method(format!($format $($args)*));
+    };
+    (@text $n:ident $method:ident $value:expr) => {
+        $n.$method($value);
+    };
+    ($n:ident summary $($value:tt)+) => {
+        $crate::__notification_field!(@text $n summary $($value)+);
+    };
+    ($n:ident body $($value:tt)+) => {
+        $crate::__notification_field!(@text $n body $($value)+);
+    };
+    ($n:ident subtitle $($value:tt)+) => {
+        $crate::__notification_field!(@text $n subtitle $($value)+);
+    };
+    ($n:ident appname $($value:tt)+) => {
+        $crate::__notification_field!(@text $n appname $($value)+);
+    };
+    ($n:ident icon $($value:tt)+) => {
+        $crate::__notification_field!(@text $n icon $($value)+);
+    };
+    ($n:ident urgency $level:ident) => {
+        $n.urgency($crate::Urgency::$level);
+    };
+    ($n:ident priority $level:ident) => {
+        $n.priority($crate::Priority::$level);
+    };
+    ($n:ident $method:ident $($value:tt)*) => {
+        $n.$method($($value)*);
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::Timeout;
+
+    #[test]
+    fn expands_to_builder_calls() {
+        let err = "exit code 1";
+        let notification = crate::notification!(
+            summary: "Build failed", body: "{}: {}", "make", err;
+            priority: High, timeout: Timeout::Never,
+            action: "log", "Show log";
+        );
+        assert_eq!(notification.summary, "Build failed");
+        assert_eq!(notification.body, "make: exit code 1");
+        assert_eq!(notification.timeout, Timeout::Never);
+        assert_eq!(notification.actions.len(), 1);
+        assert_eq!(notification.actions[0].label, "Show log");
+        #[cfg(all(unix, not(target_os = "macos")))]
+        assert_eq!(
+            notification.hints,
+            [crate::Hint::Urgency(crate::Urgency::Critical)].into()
+        );
+        #[cfg(not(all(unix, not(target_os = "macos"))))]
+        assert_eq!(notification.priority, crate::Priority::High);
+
+        let summary = String::from("owned");
+        assert_eq!(crate::notification!(summary: summary).summary, "owned");
+        assert_eq!(crate::notification!().summary, "");
+
+        #[cfg(all(unix, not(target_os = "macos")))]
+        assert_eq!(
+            crate::notification!(summary: "Build failed", body: "{}", err; urgency: Low).hints,
+            [crate::Hint::Urgency(crate::Urgency::Low)].into()
+        );
+    }
+}
@@ -0,0 +1,126 @@
+//! Bookkeeping for applications that show many notifications over a long time.
+
+use crate::{error::*, notification::Notification, NotificationHandle};
+
+use std::{
+    borrow::Borrow,
+    collections::{hash_map::Entry, HashMap},
+    hash::Hash,
+};
+
+/// Keeps track of the notifications an application has shown, by a key of its choosing.
+///
+/// Showing a notification under a key that is already in use updates the notification
+/// shown before instead of adding another one, which is what long running daemons usually want,
+/// e.g. one notification per download or per failing service.
+///
+/// # Example
+/// ```no_run
+/// # use notify_rust::*;
+/// let mut notifications = NotificationManager::new();
+/// notifications.show("backup", Notification::new().summary("backup").body("started"))?;
+/// notifications.show("backup", Notification::new().summary("backup").body("50%"))?;
+/// // on shutdown
+/// notifications.close_all()?;
+/// # Ok::<(), notify_rust::error::Error>(())
+/// ```
+///
+/// The manager doesn't notice when the user dismisses a notification,
+/// it only forgets about notifications it was asked to close.
+#[derive(Debug)]
+pub struct NotificationManager<K> {
+    handles: HashMap<K, NotificationHandle>,
+}
+
+impl<K: Eq + Hash> Default for NotificationManager<K> {
+    fn default() -> Self {
+        NotificationManager {
+            handles: HashMap::new(),
+        }
+    }
+}
+
+impl<K: Eq + Hash> NotificationManager<K> {
+    /// Creates a manager that hasn't shown anything yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Shows `notification` under `key`, replacing the notification shown under that key before.
+    pub fn show(&mut self, key: K, notification: &Notification) -> Result<&mut NotificationHandle> {
+        match self.handles.entry(key) {
+            Entry::Occupied(entry) => {
+                let handle = entry.into_mut();
+                **handle = notification.clone();
+                handle.update()?;
+                Ok(handle)
+            }
+            Entry::Vacant(entry) => Ok(entry.insert(notification.show()?)),
+        }
+    }
+
+    /// The handle of the notification shown under `key`.
+    pub fn get<Q>(&self, key: &Q) -> Option<&NotificationHandle>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.handles.get(key)
+    }
+
+    /// The handle of the notification shown under `key`, e.g. to change and [`update`](`NotificationHandle::update`) it.
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut NotificationHandle>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.handles.get_mut(key)
+    }
+
+    /// All notifications that were shown and not closed via the manager, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &NotificationHandle)> {
+        self.handles.iter()
+    }
+
+    /// How many notifications the manager keeps track of.
+    pub fn len(&self) -> usize {
+        self.handles.len()
+    }
+
+    /// Whether the manager keeps track of no notifications at all.
+    pub fn is_empty(&self) -> bool {
+        self.handles.is_empty()
+    }
+
+    /// Stops keeping track of the notification under `key` without closing it.
+    pub fn forget<Q>(&mut self, key: &Q) -> Option<NotificationHandle>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        self.handles.remove(key)
+    }
+
+    /// Closes the notification shown under `key`, if there is one.
+    pub fn close<Q>(&mut self, key: &Q) -> Result<()>
+    where
+        K: Borrow<Q>,
+        Q: Eq + Hash + ?Sized,
+    {
+        match self.handles.remove(key) {
+            Some(handle) => handle.close(),
+            None => Ok(()),
+        }
+    }
+
+    /// Closes all notifications, e.g. on shutdown.
+    ///
+    /// Tries to close every one of them, even if closing some fails,
+    /// and returns the first error.
+    pub fn close_all(&mut self) -> Result<()> {
+        self.handles
+            .drain()
+            .map(|(_, handle)| handle.close())
+            .fold(Ok(()), Result::and)
+    }
+}
@@ -2,23 +2,49 @@
 use crate::{
     hints::{CustomHintType, Hint},
     urgency::Urgency,
-    xdg,
+    xdg::{self, Capability},
 };
 
-#[cfg(all(unix, not(target_os = "macos"), feature = "images"))]
+#[cfg(feature = "images")]
 use crate::image::Image;
 
 #[cfg(all(unix, target_os = "macos"))]
 use crate::macos;
+#[cfg(target_arch = "wasm32")]
+use crate::web;
 #[cfg(target_os = "windows")]
 use crate::windows;
 
-use crate::{error::*, timeout::Timeout};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::schedule::{self, ScheduledNotification};
+
+use crate::{
+    action::{Action, DEFAULT_ACTION},
+    body::Body,
+    builder::{self, NotificationBuilder},
+    defaults,
+    error::*,
+    sound::Sound,
+    timeout::Timeout,
+    unsupported::Unsupported,
+    urgency::Priority,
+    NotificationHandle,
+};
+
+use std::{
+    fmt,
+    hash::{Hash, Hasher},
+};
 
 #[cfg(all(unix, not(target_os = "macos")))]
 use std::collections::{HashMap, HashSet};
+#[cfg(target_os = "macos")]
+use std::time::UNIX_EPOCH;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::{Duration, SystemTime};
 
 // Returns the name of the current executable, used as a default for `Notification.appname`.
+#[cfg(not(target_arch = "wasm32"))]
 fn exe_name() -> String {
     std::env::current_exe()
         .unwrap()
@@ -43,7 +69,11 @@ fn exe_name() -> String {
 /// # Ok(())
 /// # }
 /// ```
-#[derive(Debug, Clone)]
+///
+/// Notifications are equal if all their settings are, including hints and [`id`](`Notification::id`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
 #[non_exhaustive]
 pub struct Notification {
     /// Filled by default with executable name.
@@ -65,16 +95,20 @@ pub struct Notification {
     /// Check out `Hint`
     ///
     /// # warning
-    /// this does not hold all hints, [`Hint::Custom`] and [`Hint::CustomInt`] are held elsewhere,
+    /// this does not hold all hints, [`Hint::Custom`], [`Hint::CustomInt`] and [`Hint::CustomValue`] are held elsewhere,
     // /// please access hints via [`Notification::get_hints`].
     #[cfg(all(unix, not(target_os = "macos")))]
     pub hints: HashSet<Hint>,
 
     #[cfg(all(unix, not(target_os = "macos")))]
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "custom_hints", with = "custom_hints")
+    )]
     pub(crate) hints_unique: HashMap<(String, CustomHintType), Hint>,
 
-    /// See `Notification::actions()` and `Notification::action()`
-    pub actions: Vec<String>,
+    /// See `Notification::action()` and `Notification::add_action()`
+    pub actions: Vec<Action>,
 
     #[cfg(target_os = "macos")]
     pub(crate) sound_name: Option<String>,
@@ -82,20 +116,76 @@ pub struct Notification {
     #[cfg(target_os = "windows")]
     pub(crate) sound_name: Option<String>,
 
-    #[cfg(target_os = "windows")]
+    #[cfg(any(target_os = "macos", target_os = "windows"))]
     pub(crate) path_to_image: Option<String>,
 
+    #[cfg(all(feature = "images", any(target_os = "macos", target_os = "windows")))]
+    pub(crate) image_data: Option<Image>,
+
+    #[cfg(target_os = "windows")]
+    pub(crate) hero_image: Option<String>,
+
+    #[cfg(target_os = "windows")]
+    pub(crate) app_logo: Option<(String, windows::IconCrop)>,
+
+    #[cfg(target_os = "windows")]
+    pub(crate) attribution: Option<String>,
+
+    #[cfg(target_os = "windows")]
+    pub(crate) scenario: windows::Scenario,
+
+    #[cfg(any(target_os = "macos", target_os = "windows", target_arch = "wasm32"))]
+    pub(crate) priority: Priority,
+
+    #[cfg(target_os = "macos")]
+    pub(crate) interruption_level: macos::InterruptionLevel,
+
+    #[cfg(target_os = "macos")]
+    pub(crate) badge: Option<u32>,
+
+    #[cfg(target_os = "windows")]
+    pub(crate) sound_loop: bool,
+
+    #[cfg(any(target_os = "windows", target_arch = "wasm32"))]
+    pub(crate) silent: bool,
+
     #[cfg(target_os = "windows")]
     pub(crate) app_id: Option<String>,
 
+    pub(crate) tag: Option<String>,
+
+    #[cfg(any(target_os = "macos", target_os = "windows"))]
+    pub(crate) group: Option<String>,
+
+    #[cfg(any(
+        target_os = "macos",
+        target_os = "windows",
+        all(unix, not(target_os = "macos"))
+    ))]
+    pub(crate) reply_placeholder: Option<String>,
+
+    #[cfg(target_os = "windows")]
+    pub(crate) progress: Option<windows::ToastProgress>,
+
+    /// What [`rich_body`](`Notification::rich_body`) asked for, markup is only sent to servers rendering it.
+    #[cfg(all(unix, not(target_os = "macos")))]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) body_markup: Option<Body>,
+
     #[cfg(all(unix, not(target_os = "macos")))]
+    #[cfg_attr(feature = "serde", serde(skip))]
     pub(crate) bus: xdg::NotificationBus,
 
+    #[cfg(all(unix, not(target_os = "macos")))]
+    pub(crate) message_bus: xdg::MessageBus,
+
     /// Lifetime of the Notification in ms. Often not respected by server, sorry.
     pub timeout: Timeout, // both gnome and galago want allow for -1
 
     /// Only to be used on the receive end. Use Notification hand for updating.
     pub(crate) id: Option<u32>,
+
+    pub(crate) on_unsupported: Unsupported,
 }
 
 impl Notification {
@@ -108,6 +198,31 @@ impl Notification {
         Notification::default()
     }
 
+    /// Starts an owned [`NotificationBuilder`], which can end an expression with [`build()`](`NotificationBuilder::build`).
+    pub fn builder() -> NotificationBuilder {
+        NotificationBuilder::new()
+    }
+
+    /// Starts an owned [`NotificationBuilder`] that only offers `build()` and `show()` once a summary is set,
+    /// as some servers silently drop notifications without one.
+    ///
+    /// ```no_run
+    /// # use notify_rust::*;
+    /// Notification::strict_builder()
+    ///     .body("the summary may come later")
+    ///     .summary("but it has to come")
+    ///     .show()?;
+    /// # Ok::<(), notify_rust::error::Error>(())
+    /// ```
+    ///
+    /// ```compile_fail
+    /// # use notify_rust::*;
+    /// Notification::strict_builder().body("no summary").show();
+    /// ```
+    pub fn strict_builder() -> NotificationBuilder<builder::state::NoSummary> {
+        NotificationBuilder::strict()
+    }
+
     /// This is for testing purposes only and will not work with actual implementations.
     #[cfg(all(unix, not(target_os = "macos")))]
     #[doc(hidden)]
@@ -126,24 +241,32 @@ impl Notification {
     ///
     /// # Platform Support
     /// Please note that this method has no effect on macOS. Here you can only set the application via [`set_application()`](fn.set_application.html)
-    pub fn appname(&mut self, appname: &str) -> &mut Notification {
-        appname.clone_into(&mut self.appname);
+    pub fn appname(&mut self, appname: impl Into<String>) -> &mut Notification {
+        self.appname = appname.into();
         self
     }
 
     /// Set the `summary`.
     ///
     /// Often acts as title of the notification. For more elaborate content use the `body` field.
-    pub fn summary(&mut self, summary: &str) -> &mut Notification {
-        summary.clone_into(&mut self.summary);
+    pub fn summary(&mut self, summary: impl Into<String>) -> &mut Notification {
+        self.summary = summary.into();
         self
     }
 
+    /// Set the `summary` to `text` as it is, the counterpart of [`body_text`](`Notification::body_text`).
+    ///
+    /// The specification has servers show the summary as plain text, so unlike the body it is never escaped,
+    /// this is the same as [`summary`](`Notification::summary`).
+    pub fn summary_text(&mut self, text: &str) -> &mut Notification {
+        self.summary(text)
+    }
+
     /// Set the `subtitle`.
     ///
     /// This is only useful on macOS, it's not part of the XDG specification and will therefore be eaten by gremlins under your CPU 😈🤘.
-    pub fn subtitle(&mut self, subtitle: &str) -> &mut Notification {
-        self.subtitle = Some(subtitle.to_owned());
+    pub fn subtitle(&mut self, subtitle: impl Into<String>) -> &mut Notification {
+        self.subtitle = Some(subtitle.into());
         self
     }
 
@@ -154,27 +277,199 @@ impl Notification {
         self
     }
 
+    /// Shows `image` like [`image_path`](`Notification::image_path`),
+    /// via a png written to the temp directory.
+    ///
+    /// The png is reused for the same image, only the 16 most recently written ones are kept,
+    /// older ones are removed.
+    #[cfg(all(feature = "images", any(target_os = "macos", target_os = "windows")))]
+    pub fn image_data(&mut self, image: Image) -> &mut Notification {
+        self.image_data = Some(image);
+        self
+    }
+
     /// Wrapper for `Hint::ImagePath`
     #[cfg(all(unix, not(target_os = "macos")))]
-    pub fn image_path(&mut self, path: &str) -> &mut Notification {
-        self.hint(Hint::ImagePath(path.to_string()));
+    pub fn image_path(&mut self, path: impl Into<String>) -> &mut Notification {
+        self.hint(Hint::ImagePath(path.into()));
+        self
+    }
+
+    /// Shows the image at `path` inline, underneath the toast's text.
+    ///
+    /// On macOS it is shown as the notification's content image, `path` may also be a URL.
+    #[cfg(any(target_os = "macos", target_os = "windows"))]
+    pub fn image_path(&mut self, path: impl Into<String>) -> &mut Notification {
+        self.path_to_image = Some(path.into());
         self
     }
 
-    /// Wrapper for `NotificationHint::ImagePath`
+    /// Shows the image at `path` prominently across the top of the toast.
     #[cfg(target_os = "windows")]
-    pub fn image_path(&mut self, path: &str) -> &mut Notification {
-        self.path_to_image = Some(path.to_string());
+    pub fn hero_image(&mut self, path: impl Into<String>) -> &mut Notification {
+        self.hero_image = Some(path.into());
+        self
+    }
+
+    /// Replaces the app's logo on the toast with the image at `path`, optionally cropped to a circle.
+    #[cfg(target_os = "windows")]
+    pub fn app_logo(
+        &mut self,
+        path: impl Into<String>,
+        crop: windows::IconCrop,
+    ) -> &mut Notification {
+        self.app_logo = Some((path.into(), crop));
         self
     }
 
     /// app's System.AppUserModel.ID
+    ///
+    /// Defaults to the id passed to [`register_app`](`crate::register_app`).
     #[cfg(target_os = "windows")]
-    pub fn app_id(&mut self, app_id: &str) -> &mut Notification {
-        self.app_id = Some(app_id.to_string());
+    pub fn app_id(&mut self, app_id: impl Into<String>) -> &mut Notification {
+        self.app_id = Some(app_id.into());
         self
     }
 
+    /// Set the notification's `tag`, e.g. `"download-42"`.
+    ///
+    /// Showing a notification with the same tag as a previous one replaces it,
+    /// without keeping track of the previous one's [`id`](`Notification::id`) or handle.
+    ///
+    /// # Platform support
+    /// On xdg the tag stands for the id the server gave the last notification shown with it by this process,
+    /// an explicit [`id`](`Notification::id`) takes precedence.
+    /// On windows it is the toast's tag, toasts are replaced if their [`group`](`Notification::group`) matches as well.
+    /// It defaults to a tag unique to each `show()`.
+    /// On macOS it is the identifier of the notification request, which needs the `UserNotifications` framework,
+    /// see [`Notification::group`].
+    /// Browsers replace notifications with the same tag as well.
+    pub fn tag(&mut self, tag: impl Into<String>) -> &mut Notification {
+        self.tag = Some(tag.into());
+        self
+    }
+
+    /// Set the notification's `group`.
+    ///
+    /// # Platform support
+    /// On windows toasts are grouped by it in the Action Center, it defaults to `"notify-rust"`.
+    ///
+    /// On macOS it becomes the `threadIdentifier`, notifications of the same thread are stacked
+    /// together in the Notification Center.
+    /// Only the `UserNotifications` framework knows about threads, so this only takes effect for
+    /// applications inside a `.app` bundle and for notifications without actions or a reply input.
+    #[cfg(any(target_os = "macos", target_os = "windows"))]
+    pub fn group(&mut self, group: impl Into<String>) -> &mut Notification {
+        self.group = Some(group.into());
+        self
+    }
+
+    /// Labels where the toast's content comes from, e.g. `"via RSS"`.
+    ///
+    /// Shown in small print underneath the toast's text.
+    #[cfg(target_os = "windows")]
+    pub fn attribution(&mut self, text: impl Into<String>) -> &mut Notification {
+        self.attribution = Some(text.into());
+        self
+    }
+
+    /// Set the toast's [`Scenario`](`crate::Scenario`).
+    ///
+    /// Alarms, reminders and incoming calls stay on screen until the user acts upon them,
+    /// alarms and incoming calls also loop their sound unless a [`sound_name`](`Notification::sound_name`) is set.
+    /// Windows only honors these scenarios for toasts with at least one [`action`](`Notification::action`).
+    #[cfg(target_os = "windows")]
+    pub fn scenario(&mut self, scenario: windows::Scenario) -> &mut Notification {
+        self.scenario = scenario;
+        self
+    }
+
+    /// Set the notification's [`InterruptionLevel`](`crate::InterruptionLevel`),
+    /// which decides whether it breaks through Focus.
+    ///
+    /// Defaults to what the [`priority`](`Notification::priority`) maps to.
+    /// Interruption levels are part of the `UserNotifications` framework, so this only takes effect for
+    /// applications inside a `.app` bundle and for notifications without actions or a reply input.
+    #[cfg(target_os = "macos")]
+    pub fn interruption_level(&mut self, level: macos::InterruptionLevel) -> &mut Notification {
+        self.interruption_level = level;
+        self
+    }
+
+    /// Sets the number shown on the application's icon once the notification is delivered,
+    /// `0` removes it.
+    ///
+    /// Use [`set_badge_count`](`crate::set_badge_count`) to change it without a notification.
+    /// Badges are part of the `UserNotifications` framework, so this only takes effect for
+    /// applications inside a `.app` bundle and for notifications without actions or a reply input.
+    #[cfg(target_os = "macos")]
+    pub fn badge(&mut self, count: u32) -> &mut Notification {
+        self.badge = Some(count);
+        self
+    }
+
+    /// Adds a text input field to the notification.
+    ///
+    /// Once the user sends a reply, it can be retrieved via
+    /// [`NotificationHandle::wait_for_reply`](`crate::NotificationHandle::wait_for_reply`).
+    /// Sending a reply invokes the `"reply"` action.
+    ///
+    /// # Platform support
+    /// On macOS the reply button replaces any other [`action`](`Notification::action`)s.
+    /// On XDG only servers advertising the `"inline-reply"` capability, like Plasma, show an input,
+    /// the server is asked for its capabilities when the notification is shown.
+    #[cfg(any(
+        target_os = "macos",
+        target_os = "windows",
+        all(unix, not(target_os = "macos"))
+    ))]
+    pub fn reply_input(&mut self, placeholder: impl Into<String>) -> &mut Notification {
+        self.reply_placeholder = Some(placeholder.into());
+        self
+    }
+
+    /// The placeholder of the [`reply_input`](`Notification::reply_input`), if the server can show one.
+    #[cfg(all(
+        any(feature = "dbus", feature = "zbus"),
+        unix,
+        not(target_os = "macos")
+    ))]
+    pub(crate) fn inline_reply_placeholder(&self) -> Option<&str> {
+        self.reply_placeholder.as_deref().filter(|_| {
+            xdg::known_capabilities().map_or(false, |capabilities| {
+                capabilities.supports(Capability::InlineReply)
+            })
+        })
+    }
+
+    /// Adds a progress bar to the toast.
+    ///
+    /// `value` ranges from `0.0` to `1.0`, `status` is shown underneath the bar.
+    /// Use [`NotificationHandle::update_progress`](`crate::NotificationHandle::update_progress`)
+    /// to move the bar without re-posting the toast.
+    #[cfg(target_os = "windows")]
+    pub fn progress(
+        &mut self,
+        title: impl Into<String>,
+        value: f32,
+        status: impl Into<String>,
+    ) -> &mut Notification {
+        self.progress = Some(windows::ToastProgress {
+            title: title.into(),
+            value,
+            status: status.into(),
+        });
+        self
+    }
+
+    /// Shows a progress bar at `percent`, which is capped at 100, via the `"value"` hint.
+    ///
+    /// To move it as a task goes on, see [`ProgressNotification`](`crate::ProgressNotification`).
+    #[cfg(all(unix, not(target_os = "macos")))]
+    pub fn progress(&mut self, percent: u8) -> &mut Notification {
+        self.hint(Hint::CustomInt("value".into(), i32::from(percent.min(100))))
+    }
+
     /// Wrapper for `Hint::ImageData`
     #[cfg(all(feature = "images", unix, not(target_os = "macos")))]
     pub fn image<T: AsRef<std::path::Path> + Sized>(
@@ -186,17 +481,166 @@ impl Notification {
         Ok(self)
     }
 
+    /// Shows the image at `path` like [`image_path`](`Notification::image_path`),
+    /// after making sure it exists.
+    #[cfg(all(feature = "images", any(target_os = "macos", target_os = "windows")))]
+    pub fn image<T: AsRef<std::path::Path> + Sized>(
+        &mut self,
+        path: T,
+    ) -> Result<&mut Notification> {
+        std::fs::metadata(&path).map_err(|error| {
+            crate::image::ImageError::CantOpen(image::ImageError::IoError(error))
+        })?;
+        self.path_to_image = Some(path.as_ref().to_string_lossy().into_owned());
+        self.image_data = None;
+        Ok(self)
+    }
+
+    /// Resolves [`image_data`](`Notification::image_data`) into a file to be shown like an
+    /// [`image_path`](`Notification::image_path`).
+    #[cfg(any(target_os = "macos", target_os = "windows"))]
+    pub(crate) fn with_image_file(&self) -> Result<std::borrow::Cow<'_, Notification>> {
+        #[cfg(feature = "images")]
+        if let Some(image) = &self.image_data {
+            let mut notification = self.clone();
+            notification.path_to_image = Some(image.to_temp_file()?.to_string_lossy().into_owned());
+            return Ok(std::borrow::Cow::Owned(notification));
+        }
+        Ok(std::borrow::Cow::Borrowed(self))
+    }
+
     /// Wrapper for `Hint::SoundName`
     #[cfg(all(unix, not(target_os = "macos")))]
-    pub fn sound_name(&mut self, name: &str) -> &mut Notification {
-        self.hint(Hint::SoundName(name.to_owned()));
+    pub fn sound_name(&mut self, name: impl Into<String>) -> &mut Notification {
+        self.hint(Hint::SoundName(name.into()));
         self
     }
 
+    /// Wrapper for `Hint::SuppressSound`
+    ///
+    /// Keeps the server from playing any sound, including one set by [`sound_file`](`Notification::sound_file`).
+    #[cfg(all(unix, not(target_os = "macos")))]
+    pub fn suppress_sound(&mut self, suppress: bool) -> &mut Notification {
+        self.hints
+            .retain(|hint| !matches!(hint, Hint::SuppressSound(_)));
+        self.hint(Hint::SuppressSound(suppress))
+    }
+
+    /// Wrapper for `Hint::SoundFile`
+    ///
+    /// Fails if there is no file at `path`, otherwise sends it as an absolute path,
+    /// since the server doesn't know the working directory.
+    #[cfg(all(unix, not(target_os = "macos")))]
+    pub fn sound_file<T: AsRef<std::path::Path>>(&mut self, path: T) -> Result<&mut Notification> {
+        let path = path.as_ref();
+        let absolute = std::fs::canonicalize(path)
+            .ok()
+            .filter(|absolute| absolute.is_file())
+            .ok_or_else(|| Error::from(format!("no sound file at {}", path.display()).as_str()))?;
+        self.hints
+            .retain(|hint| !matches!(hint, Hint::SoundFile(_)));
+        self.hint(Hint::SoundFile(absolute.to_string_lossy().into_owned()));
+        Ok(self)
+    }
+
+    /// Wrapper for `Hint::Transient`
+    ///
+    /// A transient notification bypasses the server's persistence, so it isn't kept in a history
+    /// once it expired. GNOME, Plasma and dunst honor it.
+    #[cfg(all(unix, not(target_os = "macos")))]
+    pub fn transient(&mut self, transient: bool) -> &mut Notification {
+        self.hints
+            .retain(|hint| !matches!(hint, Hint::Transient(_)));
+        self.hint(Hint::Transient(transient))
+    }
+
+    /// Wrapper for `Hint::Resident`
+    ///
+    /// A resident notification isn't removed when one of its actions is invoked,
+    /// only when the user dismisses it. GNOME and Plasma honor it,
+    /// on other servers [`timeout(0)`](`Notification::timeout`) comes closest.
+    #[cfg(all(unix, not(target_os = "macos")))]
+    pub fn resident(&mut self, resident: bool) -> &mut Notification {
+        self.hints.retain(|hint| !matches!(hint, Hint::Resident(_)));
+        self.hint(Hint::Resident(resident))
+    }
+
     /// Set the `sound_name` for the `NSUserNotification`
     #[cfg(any(target_os = "macos", target_os = "windows"))]
-    pub fn sound_name(&mut self, name: &str) -> &mut Notification {
-        self.sound_name = Some(name.to_owned());
+    pub fn sound_name(&mut self, name: impl Into<String>) -> &mut Notification {
+        self.sound_name = Some(name.into());
+        self
+    }
+
+    /// Repeat the toast's sound for as long as it is shown.
+    ///
+    /// This keeps the toast on screen for the long duration, best combined with one of the
+    /// `"Alarm"` or `"Call"` [`sound_name`](`Notification::sound_name`)s.
+    #[cfg(target_os = "windows")]
+    pub fn sound_loop(&mut self, sound_loop: bool) -> &mut Notification {
+        self.sound_loop = sound_loop;
+        self
+    }
+
+    /// Show the toast without playing any sound, regardless of [`sound_name`](`Notification::sound_name`)
+    /// or [`scenario`](`Notification::scenario`).
+    #[cfg(target_os = "windows")]
+    pub fn silent(&mut self, silent: bool) -> &mut Notification {
+        self.silent = silent;
+        self
+    }
+
+    /// Set the [`Sound`] played when the notification is shown, available on all platforms.
+    pub fn sound(&mut self, sound: Sound) -> &mut Notification {
+        #[cfg(all(unix, not(target_os = "macos")))]
+        {
+            self.hints.retain(|hint| {
+                !matches!(
+                    hint,
+                    Hint::SuppressSound(_) | Hint::SoundName(_) | Hint::SoundFile(_)
+                )
+            });
+        }
+        #[cfg(all(unix, not(target_os = "macos")))]
+        match sound {
+            Sound::Default => {}
+            Sound::Silent => {
+                self.hint(Hint::SuppressSound(true));
+            }
+            Sound::Named(name) => {
+                self.hint(Hint::SoundName(name));
+            }
+            Sound::File(path) => {
+                self.hint(Hint::SoundFile(path));
+            }
+        }
+
+        #[cfg(target_os = "macos")]
+        {
+            self.sound_name = match sound {
+                Sound::Default => Some("NSUserNotificationDefaultSoundName".to_owned()),
+                Sound::Silent => None,
+                Sound::Named(name) => Some(name),
+                Sound::File(path) => std::path::Path::new(&path)
+                    .file_stem()
+                    .map(|stem| stem.to_string_lossy().into_owned()),
+            };
+        }
+
+        #[cfg(target_os = "windows")]
+        {
+            self.silent = sound == Sound::Silent;
+            self.sound_name = match sound {
+                Sound::Named(name) => Some(name),
+                Sound::Default | Sound::Silent | Sound::File(_) => Some("Default".to_owned()),
+            };
+        }
+
+        #[cfg(target_arch = "wasm32")]
+        {
+            self.silent = sound == Sound::Silent;
+        }
+
         self
     }
 
@@ -205,8 +649,85 @@ impl Notification {
     /// Multiline textual content of the notification.
     /// Each line should be treated as a paragraph.
     /// Simple html markup should be supported, depending on the server implementation.
-    pub fn body(&mut self, body: &str) -> &mut Notification {
-        body.clone_into(&mut self.body);
+    pub fn body(&mut self, body: impl Into<String>) -> &mut Notification {
+        self.body = body.into();
+        #[cfg(all(unix, not(target_os = "macos")))]
+        {
+            self.body_markup = None;
+        }
+        self
+    }
+
+    /// Set the `body` to `text` as it is, even on servers that render markup.
+    ///
+    /// `<`, `>` and `&` in user provided strings like file names or chat messages
+    /// would otherwise break the markup or smuggle in links.
+    /// On XDG they are escaped when the notification is shown, if the server advertises `"body-markup"`,
+    /// other servers and platforms show the body as plain text anyway.
+    ///
+    /// ```no_run
+    /// # use notify_rust::*;
+    /// let sender = "<b>eve</b>";
+    /// Notification::new()
+    ///     .summary("new message")
+    ///     .body_text(&format!("from {}", sender))
+    ///     .show()?;
+    /// # Ok::<(), notify_rust::error::Error>(())
+    /// ```
+    pub fn body_text(&mut self, text: &str) -> &mut Notification {
+        self.rich_body(Body::new().text(text))
+    }
+
+    /// The body as pieces to build markup from, if the `body` still holds their plain text.
+    #[cfg(all(unix, not(target_os = "macos")))]
+    pub(crate) fn markup(&self) -> Option<&Body> {
+        self.body_markup
+            .as_ref()
+            .filter(|markup| markup.to_plain_text() == self.body)
+    }
+
+    /// Set the `body` from a [`Body`], as markup if the server renders it and as plain text otherwise.
+    ///
+    /// The `body` field holds the plain text, on XDG the server is asked for its capabilities
+    /// when the notification is shown. Other platforms always get plain text.
+    pub fn rich_body(&mut self, body: &Body) -> &mut Notification {
+        self.body(body.to_plain_text());
+        #[cfg(all(unix, not(target_os = "macos")))]
+        {
+            self.body_markup = Some(body.clone());
+        }
+        self
+    }
+
+    /// Appends a link to `url` showing `label` to the body, e.g. to a build log or a ticket.
+    ///
+    /// Only servers advertising `"body-hyperlinks"` get an `<a href>`, all others the bare `url`,
+    /// which many of them still let you click or copy.
+    /// The `body` field holds the bare `url`, on XDG the server is asked for its capabilities
+    /// when the notification is shown.
+    #[cfg_attr(not(all(unix, not(target_os = "macos"))), allow(unused_variables))]
+    pub fn hyperlink(&mut self, url: &str, label: &str) -> &mut Notification {
+        let separator = match self.body.chars().last() {
+            Some(last) if !last.is_whitespace() => " ",
+            _ => "",
+        };
+        #[cfg(all(unix, not(target_os = "macos")))]
+        {
+            // a body set as a string is kept as it is
+            let mut markup = self.markup().cloned().unwrap_or_else(|| {
+                let mut markup = Body::new();
+                markup.markup(&self.body);
+                markup
+            });
+            markup.text(separator).url_link(url, label);
+            self.body = markup.to_plain_text();
+            self.body_markup = Some(markup);
+        }
+        #[cfg(not(all(unix, not(target_os = "macos"))))]
+        {
+            self.body.push_str(separator);
+            self.body.push_str(url);
+        }
         self
     }
 
@@ -218,8 +739,8 @@ impl Notification {
     ///
     /// # Platform support
     /// macOS does not have support manually setting the icon. However you can pretend to be another app using [`set_application()`](fn.set_application.html)
-    pub fn icon(&mut self, icon: &str) -> &mut Notification {
-        icon.clone_into(&mut self.icon);
+    pub fn icon(&mut self, icon: impl Into<String>) -> &mut Notification {
+        self.icon = icon.into();
         self
     }
 
@@ -263,27 +784,45 @@ impl Notification {
     /// Most of these hints don't even have an effect on the big XDG Desktops, they are completely tossed on macOS.
     #[cfg(all(unix, not(target_os = "macos")))]
     pub fn hint(&mut self, hint: Hint) -> &mut Notification {
-        match hint {
-            Hint::CustomInt(k, v) => {
-                self.hints_unique
-                    .insert((k.clone(), CustomHintType::Int), Hint::CustomInt(k, v));
-            }
-            Hint::Custom(k, v) => {
-                self.hints_unique
-                    .insert((k.clone(), CustomHintType::String), Hint::Custom(k, v));
+        match unique_key(&hint) {
+            Some(key) => {
+                self.hints_unique.insert(key, hint);
             }
-            _ => {
+            None => {
                 self.hints.insert(hint);
             }
         }
         self
     }
 
-    #[cfg(all(unix, not(target_os = "macos")))]
+    #[cfg(all(
+        unix,
+        not(target_os = "macos"),
+        any(feature = "dbus", feature = "zbus")
+    ))]
     pub(crate) fn get_hints(&self) -> impl Iterator<Item = &Hint> {
         self.hints.iter().chain(self.hints_unique.values())
     }
 
+    /// Sends the notification via another bus than the user's session bus.
+    ///
+    /// ```no_run
+    /// # use notify_rust::*;
+    /// Notification::new()
+    ///     .summary("backup done")
+    ///     .message_bus(MessageBus::Address("tcp:host=192.168.0.2,port=55556".into()))
+    ///     .show()?;
+    /// # Ok::<(), notify_rust::error::Error>(())
+    /// ```
+    ///
+    /// # Platform support
+    /// XDG only, actions, updates and closing go through the same bus.
+    #[cfg(all(unix, not(target_os = "macos")))]
+    pub fn message_bus(&mut self, message_bus: xdg::MessageBus) -> &mut Notification {
+        self.message_bus = message_bus;
+        self
+    }
+
     /// Set the `timeout`.
     ///
     /// Accepts multiple types that implement `Into<Timeout>`.
@@ -317,7 +856,8 @@ impl Notification {
     /// ```
     ///
     /// # Platform support
-    /// This only works on XDG Desktops, macOS does not support manually setting the timeout.
+    /// This only works on XDG Desktops and Windows, macOS does not support manually setting the timeout.
+    /// Windows only knows short and long toasts, but removes them from the Action Center once the timeout passed.
     pub fn timeout<T: Into<Timeout>>(&mut self, timeout: T) -> &mut Notification {
         self.timeout = timeout.into();
         self
@@ -336,6 +876,43 @@ impl Notification {
         self
     }
 
+    /// Set the desktop entry of the sending application, its file name without `.desktop`,
+    /// e.g. `"org.example.App"` for `org.example.App.desktop`.
+    ///
+    /// # Platform support
+    /// GNOME and Plasma show the entry's icon and name and group notifications by it.
+    #[cfg(all(unix, not(target_os = "macos")))]
+    pub fn desktop_entry(&mut self, entry: impl Into<String>) -> &mut Notification {
+        self.hint(Hint::DesktopEntry(entry.into()))
+    }
+
+    /// Set the `category`, see [`Category`](`crate::Category`) for the standard ones.
+    ///
+    /// # Platform support
+    /// Servers may pick an icon or sound by it, or group notifications of the same category.
+    #[cfg(all(unix, not(target_os = "macos")))]
+    pub fn category(&mut self, category: crate::Category) -> &mut Notification {
+        self.hint(Hint::Category(category.to_string()))
+    }
+
+    /// Set the `priority`, available on all platforms.
+    ///
+    /// # Platform support
+    /// On xdg this sets the [`urgency`](`Notification::urgency`),
+    /// on windows a high priority toast stays on screen longer and uses the `"urgent"` [`Scenario`](`crate::Scenario`),
+    /// unless another one was set.
+    /// On macOS it picks the [`InterruptionLevel`](`crate::InterruptionLevel`) unless another one was set,
+    /// a high priority notification is time sensitive and a low priority one passive.
+    pub fn priority(&mut self, priority: Priority) -> &mut Notification {
+        #[cfg(all(unix, not(target_os = "macos")))]
+        self.urgency(priority.into());
+        #[cfg(any(target_os = "macos", target_os = "windows", target_arch = "wasm32"))]
+        {
+            self.priority = priority;
+        }
+        self
+    }
+
     /// Set `actions`.
     ///
     /// To quote <http://www.galago-project.org/specs/notification/0.9/x408.html#command-notify>
@@ -344,13 +921,13 @@ impl Notification {
     /// >  Each even element in the list (starting at index 0) represents the identifier for the action.
     /// >  Each odd element in the list is the localized string that will be displayed to the user.y
     ///
-    /// There is nothing fancy going on here yet.
+    /// These pairs become [`Action`]s, a trailing identifier without a label is dropped.
     /// **Careful! This replaces the internal list of actions!**
     ///
     /// (xdg only)
     #[deprecated(note = "please use .action() only")]
     pub fn actions(&mut self, actions: Vec<String>) -> &mut Notification {
-        self.actions = actions;
+        self.actions = Action::from_pairs(&actions);
         self
     }
 
@@ -358,20 +935,122 @@ impl Notification {
     ///
     /// This adds a single action to the internal list of actions.
     ///
-    /// (xdg only)
-    pub fn action(&mut self, identifier: &str, label: &str) -> &mut Notification {
-        self.actions.push(identifier.to_owned());
-        self.actions.push(label.to_owned());
+    /// # Platform support
+    /// On windows every action is rendered as a button on the toast.
+    /// On macOS all actions beyond the first are collected in a dropdown,
+    /// and `show()` only returns once the user responded to the notification.
+    /// On both, the `"default"` action is invoked by clicking the notification itself instead of a button.
+    pub fn action(
+        &mut self,
+        identifier: impl Into<String>,
+        label: impl Into<String>,
+    ) -> &mut Notification {
+        self.add_action(Action::new(identifier, label))
+    }
+
+    /// Add the action invoked by clicking the notification itself, instead of one of its buttons.
+    ///
+    /// Servers like GNOME show no button for it, but may show the `label` elsewhere,
+    /// react to it with [`on_click`](`crate::NotificationHandle::on_click`).
+    ///
+    /// ```no_run
+    /// # use notify_rust::*;
+    /// Notification::new()
+    ///     .summary("new mail")
+    ///     .default_action("Open")
+    ///     .show()?
+    ///     .on_click(|| println!("opening"))
+    ///     .wait();
+    /// # Ok::<(), notify_rust::error::Error>(())
+    /// ```
+    pub fn default_action(&mut self, label: impl Into<String>) -> &mut Notification {
+        self.action(DEFAULT_ACTION, label)
+    }
+
+    /// Add an action shown as the icon named `icon`, on servers that advertise
+    /// [`Capability::ActionIcons`](`crate::Capability::ActionIcons`), e.g. for the buttons of a media player.
+    ///
+    /// The icon name is sent in place of the `identifier`, invoking the action still reports the `identifier`.
+    /// Everywhere else this is the same as [`action`](`Notification::action`) and the `label` is shown.
+    ///
+    /// ```no_run
+    /// # use notify_rust::*;
+    /// Notification::new()
+    ///     .summary("Now playing")
+    ///     .action_with_icon("previous", "Previous", "media-skip-backward")
+    ///     .action_with_icon("pause", "Pause", "media-playback-pause")
+    ///     .action_with_icon("next", "Next", "media-skip-forward")
+    ///     .show()?;
+    /// # Ok::<(), notify_rust::error::Error>(())
+    /// ```
+    pub fn action_with_icon(
+        &mut self,
+        identifier: impl Into<String>,
+        label: impl Into<String>,
+        icon: impl Into<String>,
+    ) -> &mut Notification {
+        self.add_action(Action::with_icon(identifier, label, icon))
+    }
+
+    /// Add an [`Action`], same as [`action`](`Notification::action`)
+    /// or [`action_with_icon`](`Notification::action_with_icon`) if it has an icon.
+    pub fn add_action(&mut self, action: Action) -> &mut Notification {
+        self.actions.push(action);
         self
     }
 
+    /// Whether the icons of actions are sent in place of their identifiers,
+    /// because of [`Hint::ActionIcons`] or an [`action_with_icon`](`Notification::action_with_icon`)
+    /// and a server that can show them.
+    #[cfg(all(unix, not(target_os = "macos")))]
+    pub(crate) fn uses_action_icons(&self) -> bool {
+        self.hints.contains(&Hint::ActionIcons(true))
+            || self.actions.iter().any(|action| action.icon.is_some())
+                && xdg::known_capabilities().map_or(false, |capabilities| {
+                    capabilities.supports(Capability::ActionIcons)
+                })
+    }
+
+    /// The actions as the flat list of `identifier, label, …` the server expects.
+    #[cfg(all(
+        any(feature = "dbus", feature = "zbus"),
+        unix,
+        not(target_os = "macos")
+    ))]
+    pub(crate) fn actions_to_pairs(&self) -> Vec<&str> {
+        let icons = self.uses_action_icons();
+        let mut pairs = self
+            .actions
+            .iter()
+            .flat_map(|action| {
+                let identifier = match &action.icon {
+                    Some(icon) if icons => icon,
+                    _ => &action.identifier,
+                };
+                [identifier.as_str(), action.label.as_str()]
+            })
+            .collect::<Vec<_>>();
+        if self.inline_reply_placeholder().is_some()
+            && !self
+                .actions
+                .iter()
+                .any(|action| action.identifier == crate::action::INLINE_REPLY_ACTION)
+        {
+            pairs.extend([crate::action::INLINE_REPLY_ACTION, "Reply"]);
+        }
+        pairs
+    }
+
     /// Set an Id ahead of time
     ///
     /// Setting the id ahead of time allows overriding a known other notification.
     /// Though if you want to update a notification, it is easier to use the `update()` method of
     /// the `NotificationHandle` object that `show()` returns.
     ///
-    /// (xdg only)
+    /// # Platform support
+    /// On xdg the notification server replaces the notification with this id.
+    /// On windows and macOS it is only reported back by [`NotificationHandle::id`](`crate::NotificationHandle::id`),
+    /// on windows it also makes up the default [`tag`](`Notification::tag`).
     pub fn id(&mut self, id: u32) -> &mut Notification {
         self.id = Some(id);
         self
@@ -391,7 +1070,7 @@ impl Notification {
     pub fn schedule<T: chrono::TimeZone>(
         &self,
         delivery_date: chrono::DateTime<T>,
-    ) -> Result<macos::NotificationHandle> {
+    ) -> Result<NotificationHandle> {
         macos::schedule_notification(self, delivery_date.timestamp() as f64)
     }
 
@@ -401,34 +1080,199 @@ impl Notification {
     /// This is a raw `f64`, if that is a bit too raw for you please activate the feature `"chrono"`,
     /// then you can use `Notification::schedule()` instead, which accepts a `chrono::DateTime<T>`.
     #[cfg(target_os = "macos")]
-    pub fn schedule_raw(&self, timestamp: f64) -> Result<macos::NotificationHandle> {
+    pub fn schedule_raw(&self, timestamp: f64) -> Result<NotificationHandle> {
         macos::schedule_notification(self, timestamp)
     }
 
+    /// Schedules a Notification
+    ///
+    /// Hands the Notification to the OS to be delivered at `delivery_time`,
+    /// even if your process has exited by then.
+    ///
+    /// Since nobody waits for the delivery, the returned handle can't report how the user reacted,
+    /// scheduling a notification with [`action`](`Notification::action`)s or a
+    /// [`reply_input`](`Notification::reply_input`) fails.
+    #[cfg(target_os = "macos")]
+    pub fn schedule_at(&self, delivery_time: SystemTime) -> Result<NotificationHandle> {
+        let timestamp = delivery_time
+            .duration_since(UNIX_EPOCH)
+            .map_err(|_| "delivery time is before the unix epoch")?;
+        macos::schedule_notification(self, timestamp.as_secs_f64())
+    }
+
+    /// Schedules a Notification
+    ///
+    /// Same as [`schedule_at`](`Notification::schedule_at`), `delay` from now.
+    #[cfg(target_os = "macos")]
+    pub fn schedule_in(&self, delay: Duration) -> Result<NotificationHandle> {
+        self.schedule_at(SystemTime::now() + delay)
+    }
+
+    /// Shows the Notification at `delivery_time` instead of right away.
+    ///
+    /// macOS and windows deliver it themselves, even if your process has exited by then.
+    /// On xdg a background thread waits for it, so it is lost if your process exits earlier.
+    /// A `delivery_time` in the past shows the notification right away.
+    ///
+    /// Since nobody waits for the delivery the user's reaction can't be reported,
+    /// scheduling a notification with [`action`](`Notification::action`)s or a
+    /// [`reply_input`](`Notification::reply_input`) fails.
+    ///
+    /// ```no_run
+    /// # use notify_rust::*;
+    /// # use std::time::{Duration, SystemTime};
+    /// let reminder = Notification::new()
+    ///     .summary("Stand up")
+    ///     .show_at(SystemTime::now() + Duration::from_secs(45 * 60))?;
+    /// # reminder.cancel()?;
+    /// # Ok::<(), Box<dyn std::error::Error>>(())
+    /// ```
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn show_at(&self, delivery_time: SystemTime) -> Result<ScheduledNotification> {
+        schedule::schedule(self, delivery_time)
+    }
+
+    /// Shows the Notification `delay` from now, see [`show_at`](`Notification::show_at`).
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn show_after(&self, delay: Duration) -> Result<ScheduledNotification> {
+        self.show_at(SystemTime::now() + delay)
+    }
+
     /// Sends Notification to D-Bus.
     ///
     /// Returns a handle to a notification
+    ///
+    /// Inside WSL, where there is usually no notification server,
+    /// the notification is shown as a toast on the windows host instead.
+    /// On Android it is shown via `termux-notification`, if Termux:API is installed.
+    /// With the `exec` feature `notify-send` is run as a last resort.
+    ///
+    /// Prints the notification to the terminal instead if it can't be sent and a
+    /// [terminal fallback](`crate::set_terminal_fallback`) is configured.
+    ///
+    /// While a [`Capture`](`crate::Capture`) is running the notification is only recorded.
     #[cfg(all(unix, not(target_os = "macos")))]
-    pub fn show(&self) -> Result<xdg::NotificationHandle> {
-        xdg::show_notification(self)
+    pub fn show(&self) -> Result<NotificationHandle> {
+        if let Some(handle) = crate::capture::show(self) {
+            return Ok(handle.into());
+        }
+        let trace = crate::trace::start("show", self.id, self);
+        let shown = self.show_fallback(xdg::show_notification(self));
+        if let Ok(handle) = &shown {
+            trace.id(handle.id());
+        }
+        trace.finish(&shown);
+        shown
     }
 
-    /// Sends Notification to D-Bus.
+    /// Shows all `notifications`, returning their handles in the same order.
     ///
-    /// Returns a handle to a notification
+    /// With zbus all of them are sent before waiting for the first reply,
+    /// instead of one round trip after another, e.g. when replaying a log.
+    /// Otherwise, or if they don't all go via the same [`message_bus`](`Notification::message_bus`),
+    /// this is the same as calling [`show`](`Notification::show`) on each.
+    #[cfg(all(unix, not(target_os = "macos")))]
+    pub fn show_all(notifications: &[Notification]) -> Result<Vec<NotificationHandle>> {
+        if crate::capture::is_running() {
+            return notifications.iter().map(Notification::show).collect();
+        }
+        xdg::show_notifications(notifications)
+    }
+
+    /// Calls [`show`](`Notification::show`) on each of `notifications`.
+    #[cfg(not(all(unix, not(target_os = "macos"))))]
+    pub fn show_all(notifications: &[Notification]) -> Result<Vec<NotificationHandle>> {
+        notifications.iter().map(Notification::show).collect()
+    }
+
+    /// Sends Notification to D-Bus without waiting for the server to answer.
+    ///
+    /// This saves a round trip per notification, e.g. for frequent status updates,
+    /// but there is no handle to update, close or wait for the notification,
+    /// and if the server fails to show it nobody notices.
+    /// The same fallbacks as for [`show`](`Notification::show`) apply if it can't be sent.
+    #[cfg(all(unix, not(target_os = "macos")))]
+    pub fn show_no_wait(&self) -> Result<()> {
+        if crate::capture::show(self).is_some() {
+            return Ok(());
+        }
+        xdg::send_notification(self).or_else(|error| self.show_fallback(Err(error)).map(drop))
+    }
+
+    #[cfg(all(unix, not(target_os = "macos")))]
+    fn show_fallback(&self, shown: Result<NotificationHandle>) -> Result<NotificationHandle> {
+        #[cfg(target_os = "linux")]
+        let shown = shown.or_else(|error| crate::wsl::bridge(self, error).map(Into::into));
+        #[cfg(target_os = "android")]
+        let shown = shown.or_else(|error| crate::termux::bridge(self, error).map(Into::into));
+        #[cfg(feature = "exec")]
+        let shown = shown.or_else(|error| crate::exec::fallback(self, error).map(Into::into));
+        shown.or_else(|error| crate::terminal::fallback(self, error).map(Into::into))
+    }
+
+    /// Sends Notification to D-Bus without blocking.
+    ///
+    /// Returns a handle to a notification, which can also be awaited via
+    /// [`wait_for_action_async`](`xdg::NotificationHandle::wait_for_action_async`)
+    /// and [`on_close_async`](`xdg::NotificationHandle::on_close_async`).
+    ///
+    /// ```no_run
+    /// # use notify_rust::*;
+    /// # async fn _doc() -> Result<(), Box<dyn std::error::Error>> {
+    /// Notification::new()
+    ///     .summary("async notification")
+    ///     .show_async()
+    ///     .await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// (zbus only)
     #[cfg(all(unix, not(target_os = "macos")))]
     #[cfg(all(feature = "async", feature = "zbus"))]
-    pub async fn show_async(&self) -> Result<xdg::NotificationHandle> {
+    pub async fn show_async(&self) -> Result<NotificationHandle> {
+        if let Some(handle) = crate::capture::show(self) {
+            return Ok(handle.into());
+        }
         xdg::show_notification_async(self).await
     }
 
+    /// Sends Notification over a connection the application already has,
+    /// instead of the one shared by all other notifications.
+    ///
+    /// [`message_bus`](`Notification::message_bus`) is ignored, and so are the fallbacks of
+    /// [`show`](`Notification::show`), since the connection is already there.
+    /// The connection has to come from the same version of zbus as the one notify-rust uses.
+    ///
+    /// (zbus only)
+    #[cfg(all(unix, not(target_os = "macos")))]
+    #[cfg(feature = "zbus")]
+    pub fn show_with_connection(
+        &self,
+        connection: &zbus::Connection,
+    ) -> Result<NotificationHandle> {
+        xdg::show_notification_with_connection(self, connection)
+    }
+
+    /// Async version of [`show_with_connection`](`Notification::show_with_connection`).
+    ///
+    /// (zbus only)
+    #[cfg(all(unix, not(target_os = "macos")))]
+    #[cfg(all(feature = "async", feature = "zbus"))]
+    pub async fn show_with_connection_async(
+        &self,
+        connection: &zbus::Connection,
+    ) -> Result<NotificationHandle> {
+        xdg::show_notification_with_connection_async(self, connection).await
+    }
+
     /// Sends Notification to D-Bus.
     ///
     /// Returns a handle to a notification
     #[cfg(all(unix, not(target_os = "macos")))]
-    #[cfg(feature = "async")]
+    #[cfg(all(feature = "async", feature = "zbus"))]
     // #[cfg(test)]
-    pub async fn show_async_at_bus(&self, sub_bus: &str) -> Result<xdg::NotificationHandle> {
+    pub async fn show_async_at_bus(&self, sub_bus: &str) -> Result<NotificationHandle> {
         let bus = xdg::NotificationBus::custom(sub_bus).ok_or("invalid subpath")?;
         xdg::show_notification_async_at_bus(self, bus).await
     }
@@ -437,39 +1281,204 @@ impl Notification {
     ///
     /// Returns an `Ok` no matter what, since there is currently no way of telling the success of
     /// the notification.
+    ///
+    /// While a [`Capture`](`crate::Capture`) is running the notification is only recorded.
     #[cfg(target_os = "macos")]
-    pub fn show(&self) -> Result<macos::NotificationHandle> {
-        macos::show_notification(self)
+    pub fn show(&self) -> Result<NotificationHandle> {
+        if let Some(id) = crate::capture::record_shown(self) {
+            return Ok(NotificationHandle::captured(id, self.clone()));
+        }
+        let trace = crate::trace::start("show", self.id, self);
+        let shown = macos::show_notification(self);
+        if let Ok(handle) = &shown {
+            trace.id(handle.id());
+        }
+        trace.finish(&shown);
+        shown
     }
 
-    /// Sends Notification to `NSUserNotificationCenter`.
+    /// Sends Notification as a toast.
     ///
-    /// Returns an `Ok` no matter what, since there is currently no way of telling the success of
-    /// the notification.
+    /// Returns a handle to the toast, which keeps track of its tag and group.
+    ///
+    /// While a [`Capture`](`crate::Capture`) is running the notification is only recorded.
     #[cfg(target_os = "windows")]
-    pub fn show(&self) -> Result<()> {
-        windows::show_notification(self)
+    pub fn show(&self) -> Result<NotificationHandle> {
+        if let Some(id) = crate::capture::record_shown(self) {
+            return Ok(NotificationHandle::captured(id, self.clone()));
+        }
+        let trace = crate::trace::start("show", self.id, self);
+        let shown = windows::show_notification(self);
+        if let Ok(handle) = &shown {
+            trace.id(handle.id());
+        }
+        trace.finish(&shown);
+        shown
     }
 
-    /// Wraps [`Notification::show()`] but prints notification to stdout.
-    #[cfg(all(unix, not(target_os = "macos")))]
-    #[deprecated = "this was never meant to be public API"]
-    pub fn show_debug(&mut self) -> Result<xdg::NotificationHandle> {
-        println!(
-            "Notification:\n{appname}: ({icon}) {summary:?} {body:?}\nhints: [{hints:?}]\n",
-            appname = self.appname,
-            summary = self.summary,
-            body = self.body,
-            hints = self.hints,
-            icon = self.icon,
+    /// Shows the notification through the browser's Notification API.
+    ///
+    /// Fails unless the user granted permission, see [`request_permission`](`crate::request_permission`).
+    #[cfg(target_arch = "wasm32")]
+    pub fn show(&self) -> Result<NotificationHandle> {
+        let trace = crate::trace::start("show", self.id, self);
+        let shown = web::show_notification(self);
+        if let Ok(handle) = &shown {
+            trace.id(handle.id());
+        }
+        trace.finish(&shown);
+        shown
+    }
+
+    /// Shows the notification and drops the handle, which on XDG saves waiting for the server.
+    #[cfg(not(all(unix, not(target_os = "macos"))))]
+    pub fn show_no_wait(&self) -> Result<()> {
+        self.show().map(drop)
+    }
+
+    /// Wraps [`Notification::show()`] but logs the notification with its hints and actions,
+    /// and then which backend showed it, at the `debug` level of the [`log`](https://docs.rs/log) crate.
+    pub fn show_debug(&mut self) -> Result<NotificationHandle> {
+        let actions = self
+            .actions
+            .iter()
+            .map(|action| format!("{:?}: {:?}", action.identifier, action.label))
+            .collect::<Vec<_>>();
+        #[cfg(all(unix, not(target_os = "macos")))]
+        log::debug!(
+            "showing {}, icon: {:?}, hints: {:?}, actions: [{}]",
+            self,
+            self.icon,
+            self.hints,
+            actions.join(", ")
+        );
+        #[cfg(not(all(unix, not(target_os = "macos"))))]
+        log::debug!(
+            "showing {}, icon: {:?}, actions: [{}]",
+            self,
+            self.icon,
+            actions.join(", ")
         );
-        self.show()
+        let shown = self.show();
+        match &shown {
+            Ok(handle) => log::debug!(
+                "shown {:?} with id {} via {}",
+                self.summary,
+                handle.id(),
+                handle.backend()
+            ),
+            Err(error) => log::debug!("can't show {:?}, {}", self.summary, error),
+        }
+        shown
+    }
+
+    /// How urgent the notification is, unless it was left at the default.
+    fn urgency_label(&self) -> Option<String> {
+        #[cfg(all(unix, not(target_os = "macos")))]
+        return self.hints.iter().find_map(|hint| match hint {
+            Hint::Urgency(urgency) => Some(urgency.to_string()),
+            _ => None,
+        });
+        #[cfg(not(all(unix, not(target_os = "macos"))))]
+        return Some(self.priority)
+            .filter(|priority| *priority != Priority::Normal)
+            .map(|priority| priority.to_string());
+    }
+}
+
+/// Hashes the text, actions, timeout and id, so equal notifications hash the same.
+/// Hints and platform specific settings are left out, sets of hints have no order to hash them in.
+impl Hash for Notification {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.appname.hash(state);
+        self.summary.hash(state);
+        self.subtitle.hash(state);
+        self.body.hash(state);
+        self.icon.hash(state);
+        self.actions.hash(state);
+        self.timeout.hash(state);
+        self.id.hash(state);
+    }
+}
+
+/// A one line preview for logs, like `[appname] summary — body (critical, never)`.
+///
+/// The body's lines are joined, urgency and timeout are only mentioned if they were set.
+impl fmt::Display for Notification {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if !self.appname.is_empty() {
+            write!(f, "[{}] ", self.appname)?;
+        }
+        f.write_str(&self.summary)?;
+        if !self.body.trim().is_empty() {
+            let body = self.body.split_whitespace().collect::<Vec<_>>().join(" ");
+            write!(f, " — {}", body)?;
+        }
+        let details = self
+            .urgency_label()
+            .into_iter()
+            .chain(
+                Some(self.timeout)
+                    .filter(|timeout| *timeout != Timeout::Default)
+                    .map(|timeout| timeout.to_string()),
+            )
+            .collect::<Vec<_>>();
+        if !details.is_empty() {
+            write!(f, " ({})", details.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Custom hints replace earlier ones with the same name and type, all others are kept in a set.
+#[cfg(all(unix, not(target_os = "macos")))]
+fn unique_key(hint: &Hint) -> Option<(String, CustomHintType)> {
+    match hint {
+        Hint::CustomInt(k, _) => Some((k.clone(), CustomHintType::Int)),
+        Hint::Custom(k, _) => Some((k.clone(), CustomHintType::String)),
+        Hint::CustomValue(k, _) => Some((k.clone(), CustomHintType::Value)),
+        _ => None,
+    }
+}
+
+/// Stores the custom hints as a list, their names and types are taken from the hints again.
+#[cfg(all(feature = "serde", unix, not(target_os = "macos")))]
+mod custom_hints {
+    use super::{unique_key, CustomHintType, Hint};
+    use serde::{de::Error, Deserialize, Deserializer, Serializer};
+    use std::collections::HashMap;
+
+    pub(super) fn serialize<S: Serializer>(
+        hints: &HashMap<(String, CustomHintType), Hint>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(hints.values())
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<HashMap<(String, CustomHintType), Hint>, D::Error> {
+        Vec::<Hint>::deserialize(deserializer)?
+            .into_iter()
+            .map(|hint| match unique_key(&hint) {
+                Some(key) => Ok((key, hint)),
+                None => Err(D::Error::custom(format!("{:?} is not a custom hint", hint))),
+            })
+            .collect()
     }
 }
 
 impl Default for Notification {
-    #[cfg(all(unix, not(target_os = "macos")))]
     fn default() -> Notification {
+        let mut notification = Notification::platform_default();
+        defaults::apply(&mut notification);
+        notification
+    }
+}
+
+impl Notification {
+    #[cfg(all(unix, not(target_os = "macos")))]
+    fn platform_default() -> Notification {
         Notification {
             appname: exe_name(),
             summary: String::new(),
@@ -480,13 +1489,18 @@ impl Default for Notification {
             hints_unique: HashMap::new(),
             actions: Vec::new(),
             timeout: Timeout::Default,
+            reply_placeholder: None,
+            body_markup: None,
             bus: Default::default(),
+            message_bus: Default::default(),
             id: None,
+            tag: None,
+            on_unsupported: Unsupported::Ignore,
         }
     }
 
     #[cfg(target_os = "macos")]
-    fn default() -> Notification {
+    fn platform_default() -> Notification {
         Notification {
             appname: exe_name(),
             summary: String::new(),
@@ -496,12 +1510,22 @@ impl Default for Notification {
             actions: Vec::new(),
             timeout: Timeout::Default,
             sound_name: Default::default(),
+            path_to_image: None,
+            #[cfg(feature = "images")]
+            image_data: None,
+            priority: Default::default(),
+            interruption_level: Default::default(),
+            badge: None,
+            group: None,
+            reply_placeholder: None,
             id: None,
+            tag: None,
+            on_unsupported: Unsupported::Ignore,
         }
     }
 
     #[cfg(target_os = "windows")]
-    fn default() -> Notification {
+    fn platform_default() -> Notification {
         Notification {
             appname: exe_name(),
             summary: String::new(),
@@ -512,8 +1536,41 @@ impl Default for Notification {
             timeout: Timeout::Default,
             sound_name: Default::default(),
             id: None,
+            on_unsupported: Unsupported::Ignore,
             path_to_image: None,
+            #[cfg(feature = "images")]
+            image_data: None,
+            hero_image: None,
+            app_logo: None,
+            attribution: None,
+            scenario: Default::default(),
+            priority: Default::default(),
+            sound_loop: false,
+            silent: false,
             app_id: None,
+            tag: None,
+            group: None,
+            reply_placeholder: None,
+            progress: None,
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn platform_default() -> Notification {
+        Notification {
+            // there is no executable to name the notifications after
+            appname: String::new(),
+            summary: String::new(),
+            subtitle: None,
+            body: String::new(),
+            icon: String::new(),
+            actions: Vec::new(),
+            timeout: Timeout::Default,
+            priority: Default::default(),
+            silent: false,
+            id: None,
+            tag: None,
+            on_unsupported: Unsupported::Ignore,
         }
     }
 }
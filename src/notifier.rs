@@ -0,0 +1,788 @@
+//! Flow control for applications that may show bursts of notifications, see [`Notifier`].
+
+use crate::{action::ActionResult, error::*, notification::Notification, timeout::Timeout};
+
+use std::{
+    collections::{hash_map::Entry, HashMap, VecDeque},
+    fmt,
+    sync::{mpsc, Arc, Condvar, Mutex, MutexGuard, PoisonError},
+    thread,
+    time::{Duration, Instant, SystemTime},
+};
+
+/// How long a notification with [`Timeout::Default`] takes up a slot at most, see [`Notifier::max_concurrent`].
+const DEFAULT_HOLD: Duration = Duration::from_secs(10);
+
+/// How long any notification takes up a slot at most, in case the server never reports it closed.
+const MAX_HOLD: Duration = Duration::from_secs(10 * 60);
+
+/// Shows notifications from a background thread, no faster than configured,
+/// so a burst, e.g. from a tailed log going haywire, can't flood or freeze the notification server.
+///
+/// Notifications are shown in the order they were passed to [`notify`](`Notifier::notify`).
+/// Those that don't fit into the queue any more are summed up by a single "N more events"
+/// notification, shown once the queue has drained.
+/// Repeated notifications can be held back or counted instead, see [`deduplicate`](`Notifier::deduplicate`).
+/// What was sent and how the user reacted can be looked up later, see [`keep_history`](`Notifier::keep_history`).
+///
+/// # Example
+/// ```no_run
+/// # use notify_rust::*;
+/// # use std::time::Duration;
+/// let notifier = Notifier::new()
+///     .max_rate(3, Duration::from_secs(10))
+///     .max_concurrent(2);
+///
+/// for line in ["sda: I/O error", "sda: I/O error", "sda: I/O error", "sda: I/O error"] {
+///     notifier.notify(Notification::new().summary("kernel").body(line))?;
+/// }
+/// # Ok::<(), notify_rust::error::Error>(())
+/// ```
+///
+/// Dropping the notifier doesn't discard what's queued, the background thread still shows it.
+pub struct Notifier {
+    shared: Arc<Shared>,
+}
+
+struct Shared {
+    state: Mutex<State>,
+    wakeup: Condvar,
+}
+
+struct State {
+    queue: Queue,
+    history: History,
+    started: bool,
+    dropped: bool,
+}
+
+/// What to do with a notification identical to one shown shortly before, see [`Notifier::deduplicate`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Duplicates {
+    /// Drops the duplicate.
+    Suppress,
+    /// Replaces the notification shown before with one counting the duplicates, e.g. "disk full ×3".
+    ///
+    /// The count replaces the notification on xdg and windows, on macOS it is shown as another notification.
+    Count,
+}
+
+/// A notification the [`Notifier`] sent, see [`Notifier::history`].
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct SentNotification {
+    /// What was sent, counted duplicates and summaries of overflowing notifications included.
+    pub notification: Notification,
+    /// When it was sent.
+    pub sent_at: SystemTime,
+    /// The id it was shown with, `None` while it is being shown or if that failed.
+    pub id: Option<u32>,
+    /// What became of it so far.
+    pub outcome: Outcome,
+}
+
+/// What became of a [`SentNotification`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    /// Shown, how the user reacted isn't known (yet).
+    Shown,
+    /// The user invoked an action, or the notification was closed, e.g. because it expired.
+    Finished(ActionResult),
+    /// Showing it failed with this error.
+    Failed(String),
+}
+
+/// The latest `capacity` notifications sent, numbered to find them again once they are done.
+#[derive(Debug, Default)]
+struct History {
+    capacity: usize,
+    entries: VecDeque<(u64, SentNotification)>,
+    next: u64,
+}
+
+impl History {
+    fn record(&mut self, notification: &Notification) -> Option<u64> {
+        if self.capacity == 0 {
+            return None;
+        }
+        while self.entries.len() >= self.capacity {
+            self.entries.pop_front();
+        }
+        let entry = self.next;
+        self.next += 1;
+        self.entries.push_back((
+            entry,
+            SentNotification {
+                notification: notification.clone(),
+                sent_at: SystemTime::now(),
+                id: None,
+                outcome: Outcome::Shown,
+            },
+        ));
+        Some(entry)
+    }
+
+    fn get_mut(&mut self, entry: u64) -> Option<&mut SentNotification> {
+        self.entries
+            .iter_mut()
+            .find(|(number, _)| *number == entry)
+            .map(|(_, sent)| sent)
+    }
+}
+
+/// A notification waiting to be shown, under the key identifying its duplicates.
+#[derive(Debug)]
+struct Queued {
+    key: Option<String>,
+    notification: Notification,
+}
+
+/// A notification first shown within the deduplication window.
+#[derive(Debug)]
+struct Recent {
+    since: Instant,
+    count: usize,
+    original: Notification,
+    id: Option<u32>,
+}
+
+impl Recent {
+    fn counted(&self) -> Notification {
+        let mut counted = self.original.clone();
+        counted.summary(format!("{} ×{}", self.original.summary, self.count));
+        if let Some(id) = self.id {
+            counted.id(id);
+        }
+        counted
+    }
+}
+
+/// Decides what to show when, without any threads or clocks of its own.
+#[derive(Debug)]
+struct Queue {
+    max_rate: Option<(usize, Duration)>,
+    max_concurrent: Option<usize>,
+    max_queued: usize,
+    dedup: Option<(Duration, Duplicates)>,
+    recent: HashMap<String, Recent>,
+    pending: VecDeque<Queued>,
+    /// How many notifications didn't fit into `pending` and the latest of them.
+    overflow: Option<(usize, Notification)>,
+    /// When the notifications within the rate's period were shown.
+    shown: VecDeque<Instant>,
+    on_screen: usize,
+}
+
+#[derive(Debug)]
+enum Next {
+    Show(Box<Queued>),
+    /// The rate is exhausted until then.
+    RetryAt(Instant),
+    /// All slots are taken until a notification is closed.
+    Blocked,
+    Idle,
+}
+
+impl Queue {
+    fn new() -> Queue {
+        Queue {
+            max_rate: None,
+            max_concurrent: None,
+            max_queued: 100,
+            dedup: None,
+            recent: HashMap::new(),
+            pending: VecDeque::new(),
+            overflow: None,
+            shown: VecDeque::new(),
+            on_screen: 0,
+        }
+    }
+
+    fn push(&mut self, key: Option<String>, notification: Notification, now: Instant) {
+        let mut queued = Queued {
+            key: None,
+            notification,
+        };
+        if let Some((window, duplicates)) = self.dedup {
+            let key = key.unwrap_or_else(|| default_key(&queued.notification));
+            self.recent
+                .retain(|_, recent| now.duration_since(recent.since) < window);
+            match self.recent.entry(key.clone()) {
+                Entry::Occupied(entry) => {
+                    let recent = entry.into_mut();
+                    recent.count += 1;
+                    if duplicates == Duplicates::Suppress {
+                        return;
+                    }
+                    queued.notification = recent.counted();
+                    // not shown yet, just count on
+                    if let Some(waiting) = self
+                        .pending
+                        .iter_mut()
+                        .find(|waiting| waiting.key.as_ref() == Some(&key))
+                    {
+                        waiting.notification = queued.notification;
+                        return;
+                    }
+                }
+                Entry::Vacant(entry) => {
+                    entry.insert(Recent {
+                        since: now,
+                        count: 1,
+                        original: queued.notification.clone(),
+                        id: None,
+                    });
+                }
+            }
+            queued.key = Some(key);
+        }
+        if self.pending.len() < self.max_queued {
+            self.pending.push_back(queued);
+        } else {
+            let notification = queued.notification;
+            let count = self.overflow.take().map_or(0, |(count, _)| count);
+            self.overflow = Some((count + 1, notification));
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.pending.len() + self.overflow.as_ref().map_or(0, |(count, _)| *count)
+    }
+
+    fn next(&mut self, now: Instant) -> Next {
+        if self.pending.is_empty() && self.overflow.is_none() {
+            return Next::Idle;
+        }
+        if let Some((count, period)) = self.max_rate {
+            while let Some(&shown) = self.shown.front() {
+                if now.duration_since(shown) < period {
+                    break;
+                }
+                self.shown.pop_front();
+            }
+            if self.shown.len() >= count {
+                if let Some(&oldest) = self.shown.front() {
+                    return Next::RetryAt(oldest + period);
+                }
+            }
+        }
+        if let Some(max_concurrent) = self.max_concurrent {
+            if self.on_screen >= max_concurrent {
+                return Next::Blocked;
+            }
+            self.on_screen += 1;
+        }
+        if self.max_rate.is_some() {
+            self.shown.push_back(now);
+        }
+        match self.pending.pop_front() {
+            Some(queued) => Next::Show(Box::new(queued)),
+            None => match self.overflow.take() {
+                Some((1, notification)) => Next::Show(Box::new(Queued {
+                    key: None,
+                    notification,
+                })),
+                Some((count, latest)) => Next::Show(Box::new(Queued {
+                    key: None,
+                    notification: summary(count, &latest),
+                })),
+                None => Next::Idle,
+            },
+        }
+    }
+
+    /// Remembers the id the notification under `key` was shown with, so counting duplicates replaces it.
+    fn shown_as(&mut self, key: &str, id: u32) {
+        if let Some(recent) = self.recent.get_mut(key) {
+            recent.id = Some(id);
+        }
+    }
+
+    fn closed(&mut self) {
+        self.on_screen = self.on_screen.saturating_sub(1);
+    }
+}
+
+/// Duplicates have the same appname, summary and body.
+fn default_key(notification: &Notification) -> String {
+    format!(
+        "{}\u{1f}{}\u{1f}{}",
+        notification.appname, notification.summary, notification.body
+    )
+}
+
+/// Stands in for `count` notifications that didn't fit into the queue.
+fn summary(count: usize, latest: &Notification) -> Notification {
+    let mut notification = Notification::new();
+    notification
+        .appname(&latest.appname)
+        .icon(&latest.icon)
+        .summary(format!("{} more events", count))
+        .body(format!("latest: {}", latest.summary));
+    notification
+}
+
+impl Default for Notifier {
+    fn default() -> Self {
+        Notifier::new()
+    }
+}
+
+impl Notifier {
+    /// Shows notifications right away but keeps at most 100 queued, until told otherwise.
+    pub fn new() -> Notifier {
+        Notifier {
+            shared: Arc::new(Shared {
+                state: Mutex::new(State {
+                    queue: Queue::new(),
+                    history: History::default(),
+                    started: false,
+                    dropped: false,
+                }),
+                wakeup: Condvar::new(),
+            }),
+        }
+    }
+
+    /// Shows at most `count` notifications within any `period`.
+    pub fn max_rate(self, count: usize, period: Duration) -> Notifier {
+        self.shared.lock().queue.max_rate = Some((count.max(1), period));
+        self
+    }
+
+    /// Keeps at most `count` notifications on screen at once.
+    ///
+    /// A notification takes up its slot until it is closed, or its [`timeout`](`Notification::timeout`) has passed,
+    /// which for [`Timeout::Default`] is assumed to be 10 seconds.
+    /// In case the server never reports it closed, e.g. because it never expires, the slot is free again after 10 minutes.
+    pub fn max_concurrent(self, count: usize) -> Notifier {
+        self.shared.lock().queue.max_concurrent = Some(count.max(1));
+        self
+    }
+
+    /// How many notifications may wait to be shown before they are summed up instead.
+    pub fn max_queued(self, count: usize) -> Notifier {
+        self.shared.lock().queue.max_queued = count;
+        self
+    }
+
+    /// Holds back notifications identical to one shown less than `window` before, or counts them.
+    ///
+    /// Notifications are identical if their appname, summary and body are,
+    /// or if they were passed to [`notify_keyed`](`Notifier::notify_keyed`) with the same key.
+    /// The window starts with the first of them, afterwards the next one is shown again.
+    pub fn deduplicate(self, window: Duration, duplicates: Duplicates) -> Notifier {
+        self.shared.lock().queue.dedup = Some((window, duplicates));
+        self
+    }
+
+    /// Remembers the latest `capacity` notifications sent, see [`history`](`Notifier::history`).
+    ///
+    /// To learn how the user reacted the notifier waits for every notification as long as it would take up a slot,
+    /// see [`max_concurrent`](`Notifier::max_concurrent`). If it isn't closed by then, it stays [`Outcome::Shown`].
+    pub fn keep_history(self, capacity: usize) -> Notifier {
+        self.shared.lock().history.capacity = capacity;
+        self
+    }
+
+    /// The notifications sent so far, oldest first, if [`keep_history`](`Notifier::keep_history`) was called.
+    ///
+    /// ```no_run
+    /// # use notify_rust::*;
+    /// let notifier = Notifier::new().keep_history(50);
+    /// notifier.notify(Notification::new().summary("build finished"))?;
+    /// # std::thread::sleep(std::time::Duration::from_secs(1));
+    /// for sent in notifier.history() {
+    ///     if let Outcome::Failed(error) = &sent.outcome {
+    ///         eprintln!("{:?} couldn't be shown: {}", sent.notification.summary, error);
+    ///     }
+    /// }
+    /// # Ok::<(), notify_rust::error::Error>(())
+    /// ```
+    pub fn history(&self) -> Vec<SentNotification> {
+        let state = self.shared.lock();
+        state
+            .history
+            .entries
+            .iter()
+            .map(|(_, sent)| sent.clone())
+            .collect()
+    }
+
+    /// Forgets the notifications sent so far.
+    pub fn clear_history(&self) {
+        self.shared.lock().history.entries.clear();
+    }
+
+    /// Queues `notification` to be shown as soon as the limits allow.
+    ///
+    /// Fails only if the background thread can't be started.
+    pub fn notify(&self, notification: &Notification) -> Result<()> {
+        self.enqueue(None, notification)
+    }
+
+    /// Same as [`notify`](`Notifier::notify`), but notifications with the same `key` count as duplicates,
+    /// e.g. all "disk full" warnings regardless of the free space they mention.
+    pub fn notify_keyed(&self, key: &str, notification: &Notification) -> Result<()> {
+        self.enqueue(Some(key.to_owned()), notification)
+    }
+
+    fn enqueue(&self, key: Option<String>, notification: &Notification) -> Result<()> {
+        let mut state = self.shared.lock();
+        if !state.started {
+            let shared = Arc::clone(&self.shared);
+            thread::Builder::new()
+                .name("notify-rust notifier".into())
+                .spawn(move || run(&shared))
+                .map_err(|error| Error::from(error.to_string().as_str()))?;
+            state.started = true;
+        }
+        state.queue.push(key, notification.clone(), Instant::now());
+        self.shared.wakeup.notify_all();
+        Ok(())
+    }
+
+    /// How many notifications are waiting to be shown, including those that will be summed up.
+    pub fn queued(&self) -> usize {
+        self.shared.lock().queue.len()
+    }
+}
+
+impl fmt::Debug for Notifier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let state = self.shared.lock();
+        f.debug_struct("Notifier")
+            .field("max_rate", &state.queue.max_rate)
+            .field("max_concurrent", &state.queue.max_concurrent)
+            .field("max_queued", &state.queue.max_queued)
+            .field("dedup", &state.queue.dedup)
+            .field("history", &state.history.capacity)
+            .field("queued", &state.queue.len())
+            .finish()
+    }
+}
+
+impl Drop for Notifier {
+    fn drop(&mut self) {
+        self.shared.lock().dropped = true;
+        self.shared.wakeup.notify_all();
+    }
+}
+
+impl Shared {
+    fn lock(&self) -> MutexGuard<'_, State> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn closed(&self) {
+        self.lock().queue.closed();
+        self.wakeup.notify_all();
+    }
+
+    /// Updates the history's `entry`, unless it was dropped from the history by now.
+    fn record(&self, entry: Option<u64>, update: impl FnOnce(&mut SentNotification)) {
+        if let Some(entry) = entry {
+            if let Some(sent) = self.lock().history.get_mut(entry) {
+                update(sent);
+            }
+        }
+    }
+}
+
+/// The background thread, shows what [`Queue::next`] hands out until the notifier is gone and the queue empty.
+fn run(shared: &Arc<Shared>) {
+    let mut state = shared.lock();
+    loop {
+        let now = Instant::now();
+        state = match state.queue.next(now) {
+            Next::Show(queued) => {
+                let watched = state.queue.max_concurrent.is_some() || state.history.capacity > 0;
+                let entry = state.history.record(&queued.notification);
+                drop(state);
+                let id = show(shared, queued.notification, watched, entry);
+                let mut state = shared.lock();
+                if let (Some(key), Some(id)) = (queued.key, id) {
+                    state.queue.shown_as(&key, id);
+                }
+                state
+            }
+            Next::RetryAt(at) => {
+                let timeout = at.saturating_duration_since(now);
+                shared
+                    .wakeup
+                    .wait_timeout(state, timeout)
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .0
+            }
+            Next::Idle if state.dropped => return,
+            Next::Blocked | Next::Idle => shared
+                .wakeup
+                .wait(state)
+                .unwrap_or_else(PoisonError::into_inner),
+        };
+    }
+}
+
+/// Shows `notification` and returns its id.
+///
+/// If `watched` it is waited for, at most [`MAX_HOLD`], to give back its slot and record how the user reacted under `entry`.
+fn show(
+    shared: &Arc<Shared>,
+    notification: Notification,
+    watched: bool,
+    entry: Option<u64>,
+) -> Option<u32> {
+    if !watched {
+        return match notification.show() {
+            Ok(handle) => Some(handle.id()),
+            Err(error) => {
+                log::warn!("failed to show queued notification: {}", error);
+                None
+            }
+        };
+    }
+    // handles can't always be sent to another thread, so the thread waiting for the notification shows it
+    let (shown, on_shown) = mpsc::channel();
+    let watcher = Arc::clone(shared);
+    let spawned = thread::Builder::new()
+        .name("notify-rust notifier slot".into())
+        .spawn(move || {
+            let result = notification.show();
+            let id = result.as_ref().ok().map(|handle| handle.id());
+            watcher.record(entry, |sent| match &result {
+                Ok(_) => sent.id = id,
+                Err(error) => sent.outcome = Outcome::Failed(error.to_string()),
+            });
+            let _ = shown.send(id);
+            match result {
+                Ok(handle) => {
+                    let hold = hold(handle.timeout);
+                    let outcome = handle.wait_for_action_with_timeout(hold);
+                    if outcome != ActionResult::Timeout {
+                        watcher.record(entry, |sent| sent.outcome = Outcome::Finished(outcome));
+                    }
+                }
+                Err(error) => log::warn!("failed to show queued notification: {}", error),
+            }
+            watcher.closed();
+        });
+    if let Err(error) = spawned {
+        shared.record(entry, |sent| {
+            sent.outcome = Outcome::Failed(error.to_string());
+        });
+        shared.closed();
+        return None;
+    }
+    // keeps the order, the next notification is shown only once this one is
+    on_shown.recv().ok().flatten()
+}
+
+/// How long a notification with `timeout` takes up its slot at most.
+fn hold(timeout: Timeout) -> Duration {
+    match timeout {
+        Timeout::Never => MAX_HOLD,
+        Timeout::Default => DEFAULT_HOLD,
+        Timeout::Milliseconds(ms) => Duration::from_millis(ms.into()).min(MAX_HOLD),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn notification(summary: &str) -> Notification {
+        Notification::new().summary(summary).finalize()
+    }
+
+    fn shown(next: Next) -> String {
+        match next {
+            Next::Show(queued) => queued.notification.summary,
+            other => panic!("expected a notification, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn limits_rate() {
+        let mut queue = Queue::new();
+        queue.max_rate = Some((2, Duration::from_secs(10)));
+        for summary in ["a", "b", "c"] {
+            queue.push(None, notification(summary), Instant::now());
+        }
+        let start = Instant::now();
+        assert_eq!(shown(queue.next(start)), "a");
+        assert_eq!(shown(queue.next(start + Duration::from_secs(1))), "b");
+        match queue.next(start + Duration::from_secs(2)) {
+            Next::RetryAt(at) => assert_eq!(at, start + Duration::from_secs(10)),
+            other => panic!("expected to wait, got {:?}", other),
+        }
+        assert_eq!(shown(queue.next(start + Duration::from_secs(10))), "c");
+        assert!(matches!(
+            queue.next(start + Duration::from_secs(30)),
+            Next::Idle
+        ));
+    }
+
+    #[test]
+    fn limits_concurrency() {
+        let mut queue = Queue::new();
+        queue.max_concurrent = Some(1);
+        let now = Instant::now();
+        queue.push(None, notification("a"), now);
+        queue.push(None, notification("b"), now);
+        assert_eq!(shown(queue.next(now)), "a");
+        assert!(matches!(queue.next(now), Next::Blocked));
+        queue.closed();
+        assert_eq!(shown(queue.next(now)), "b");
+    }
+
+    #[test]
+    fn sums_up_overflow() {
+        let mut queue = Queue::new();
+        queue.max_queued = 1;
+        for summary in ["a", "b", "c", "d"] {
+            queue.push(None, notification(summary), Instant::now());
+        }
+        assert_eq!(queue.len(), 4);
+        let now = Instant::now();
+        assert_eq!(shown(queue.next(now)), "a");
+        match queue.next(now) {
+            Next::Show(queued) => {
+                assert_eq!(queued.notification.summary, "3 more events");
+                assert_eq!(queued.notification.body, "latest: d");
+            }
+            other => panic!("expected the summary, got {:?}", other),
+        }
+        assert_eq!(queue.len(), 0);
+    }
+
+    #[test]
+    fn suppresses_duplicates() {
+        let mut queue = Queue::new();
+        queue.dedup = Some((Duration::from_secs(60), Duplicates::Suppress));
+        let start = Instant::now();
+        queue.push(None, notification("disk full"), start);
+        queue.push(
+            None,
+            notification("disk full"),
+            start + Duration::from_secs(1),
+        );
+        queue.push(Some("disk".into()), notification("disk 90%"), start);
+        queue.push(Some("disk".into()), notification("disk 95%"), start);
+        queue.push(
+            None,
+            notification("disk full"),
+            start + Duration::from_secs(61),
+        );
+        let summaries: Vec<_> = queue
+            .pending
+            .iter()
+            .map(|queued| &queued.notification.summary)
+            .collect();
+        assert_eq!(summaries, ["disk full", "disk 90%", "disk full"]);
+    }
+
+    #[test]
+    fn counts_duplicates() {
+        let mut queue = Queue::new();
+        queue.dedup = Some((Duration::from_secs(60), Duplicates::Count));
+        let now = Instant::now();
+        queue.push(None, notification("disk full"), now);
+        queue.push(None, notification("disk full"), now);
+        assert_eq!(queue.len(), 1);
+        let waiting = match queue.next(now) {
+            Next::Show(queued) => queued,
+            other => panic!("expected a notification, got {:?}", other),
+        };
+        assert_eq!(waiting.notification.summary, "disk full ×2");
+        queue.shown_as(waiting.key.as_deref().unwrap(), 7);
+
+        queue.push(None, notification("disk full"), now);
+        match queue.next(now) {
+            Next::Show(queued) => {
+                assert_eq!(queued.notification.summary, "disk full ×3");
+                assert_eq!(queued.notification.id, Some(7));
+            }
+            other => panic!("expected a notification, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn keeps_latest_history() {
+        let mut history = History {
+            capacity: 2,
+            ..History::default()
+        };
+        let first = history.record(&notification("a"));
+        history.record(&notification("b"));
+        let third = history.record(&notification("c"));
+        assert!(history.get_mut(first.unwrap()).is_none());
+        history.get_mut(third.unwrap()).unwrap().id = Some(3);
+        let summaries: Vec<_> = history
+            .entries
+            .iter()
+            .map(|(_, sent)| (sent.notification.summary.as_str(), sent.id))
+            .collect();
+        assert_eq!(summaries, [("b", None), ("c", Some(3))]);
+        assert!(History::default().record(&notification("a")).is_none());
+    }
+
+    #[test]
+    fn holds_slots_for_a_while_only() {
+        assert_eq!(hold(Timeout::Milliseconds(500)), Duration::from_millis(500));
+        assert_eq!(hold(Timeout::Default), DEFAULT_HOLD);
+        assert_eq!(hold(Timeout::Never), MAX_HOLD);
+        assert_eq!(hold(Timeout::Milliseconds(u32::MAX)), MAX_HOLD);
+    }
+
+    #[test]
+    #[cfg(all(unix, not(target_os = "macos")))]
+    fn shows_in_the_background() {
+        let capture = crate::Capture::start();
+        let notifier = Notifier::new().max_concurrent(1);
+        for summary in ["a", "b", "c"] {
+            notifier.notify(&notification(summary)).unwrap();
+        }
+        drop(notifier);
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while capture.shown().len() < 3 && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(10));
+        }
+        let shown: Vec<_> = capture
+            .shown()
+            .into_iter()
+            .map(|notification| notification.summary)
+            .collect();
+        assert_eq!(shown, ["a", "b", "c"]);
+    }
+
+    #[test]
+    #[cfg(all(unix, not(target_os = "macos")))]
+    fn records_outcomes() {
+        let _capture = crate::Capture::start();
+        let notifier = Notifier::new().keep_history(2);
+        for summary in ["a", "b", "c"] {
+            notifier.notify(&notification(summary)).unwrap();
+        }
+
+        let finished = |sent: &SentNotification| sent.outcome != Outcome::Shown;
+        let deadline = Instant::now() + Duration::from_secs(5);
+        let done = || {
+            let history = notifier.history();
+            history.len() == 2 && history.iter().all(finished)
+        };
+        while !done() && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(10));
+        }
+        let history = notifier.history();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].notification.summary, "b");
+        assert!(history[1].id.is_some());
+        assert_eq!(
+            history[1].outcome,
+            Outcome::Finished(ActionResult::Closed(crate::CloseReason::Expired))
+        );
+        notifier.clear_history();
+        assert!(notifier.history().is_empty());
+    }
+}
@@ -0,0 +1,57 @@
+//! Notifies the user when the process panics, see [`install_panic_hook`].
+
+use crate::{notification::Notification, timeout::Timeout, urgency::Priority};
+
+/// Shows a notification with the panic message and where it happened whenever a thread panics,
+/// so background tools without a terminal don't die unnoticed.
+///
+/// The notification is critical and doesn't time out.
+/// The panic hook installed before, by default the one printing to stderr, still runs afterwards.
+///
+/// ```no_run
+/// notify_rust::install_panic_hook();
+///
+/// let config = std::fs::read_to_string("/etc/backup.toml").expect("can't read the config");
+/// ```
+pub fn install_panic_hook() {
+    let previous = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let payload = info.payload();
+        let message = payload
+            .downcast_ref::<&str>()
+            .copied()
+            .or_else(|| payload.downcast_ref::<String>().map(String::as_str));
+        let location = info.location().map(ToString::to_string);
+        let _ = panic_notification(message, location.as_deref()).show();
+        previous(info);
+    }));
+}
+
+fn panic_notification(message: Option<&str>, location: Option<&str>) -> Notification {
+    let mut notification = Notification::new();
+    let summary = format!("{} panicked", notification.appname);
+    let mut body = message.unwrap_or("unknown panic").to_owned();
+    if let Some(location) = location {
+        body.push_str(&format!("\nat {}", location));
+    }
+    notification
+        .summary(summary)
+        .body(body)
+        .priority(Priority::High)
+        .timeout(Timeout::Never);
+    notification
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn describes_panic() {
+        let notification = panic_notification(Some("oh no"), Some("src/main.rs:3:5"));
+        assert!(notification.summary.ends_with(" panicked"));
+        assert_eq!(notification.body, "oh no\nat src/main.rs:3:5");
+        assert_eq!(notification.timeout, Timeout::Never);
+        assert_eq!(panic_notification(None, None).body, "unknown panic");
+    }
+}
@@ -0,0 +1,67 @@
+//! Just enough of a property list reader to find the bundle identifier in an `Info.plist`.
+
+/// Reads the `CFBundleIdentifier` from the contents of an `Info.plist`.
+///
+/// Only handles xml property lists, binary ones are treated as if there was no identifier.
+pub(crate) fn bundle_identifier(info_plist: &str) -> Option<String> {
+    let after_key = info_plist.split("<key>CFBundleIdentifier</key>").nth(1)?;
+    let value = after_key.trim_start().strip_prefix("<string>")?;
+    let end = value.find("</string>")?;
+    Some(value[..end].trim().to_owned()).filter(|ident| !ident.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::bundle_identifier;
+
+    #[test]
+    fn finds_identifier() {
+        let plist = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE plist PUBLIC "-//Apple//DTD PLIST 1.0//EN" "http://www.apple.com/DTDs/PropertyList-1.0.dtd">
+<plist version="1.0">
+<dict>
+    <key>CFBundleName</key>
+    <string>Example</string>
+    <key>CFBundleIdentifier</key>
+    <string>com.example.app</string>
+</dict>
+</plist>"#;
+        assert_eq!(bundle_identifier(plist).as_deref(), Some("com.example.app"));
+    }
+
+    #[test]
+    fn ignores_whitespace() {
+        let plist = "<key>CFBundleIdentifier</key>\n\t  <string>  com.example.app\n</string>";
+        assert_eq!(bundle_identifier(plist).as_deref(), Some("com.example.app"));
+    }
+
+    #[test]
+    fn missing_key() {
+        let plist = "<dict><key>CFBundleName</key><string>Example</string></dict>";
+        assert_eq!(bundle_identifier(plist), None);
+    }
+
+    #[test]
+    fn empty_string() {
+        assert_eq!(
+            bundle_identifier("<key>CFBundleIdentifier</key><string></string>"),
+            None
+        );
+        assert_eq!(
+            bundle_identifier("<key>CFBundleIdentifier</key><string> </string>"),
+            None
+        );
+    }
+
+    #[test]
+    fn key_without_string() {
+        let plist =
+            "<key>CFBundleIdentifier</key><true/><key>CFBundleName</key><string>Example</string>";
+        assert_eq!(bundle_identifier(plist), None);
+        assert_eq!(bundle_identifier("<key>CFBundleIdentifier</key>"), None);
+        assert_eq!(
+            bundle_identifier("<key>CFBundleIdentifier</key><string>com.example.app"),
+            None
+        );
+    }
+}
@@ -0,0 +1,279 @@
+//! Pushes notifications to an [ntfy](https://ntfy.sh) or [Gotify](https://gotify.net) server,
+//! which forward them to phones and browsers.
+
+use crate::{error::*, notification::Notification, urgency::Priority};
+#[cfg(all(unix, not(target_os = "macos")))]
+use crate::{hints::Hint, urgency::Urgency};
+
+use serde_json::{json, Map, Value};
+
+use std::time::Duration;
+
+/// An ntfy or Gotify server to push notifications to.
+///
+/// Actions whose identifier is a `http(s)://` url become buttons opening that url,
+/// a `"default"` action's url is opened when the notification is clicked,
+/// all other actions are left out.
+///
+/// # Example
+/// ```no_run
+/// # use notify_rust::*;
+/// let phone = PushServer::ntfy("https://ntfy.sh", "my-builds");
+/// phone.send(
+///     Notification::new()
+///         .summary("build finished")
+///         .action("https://ci.example.com/builds/42", "Open"),
+/// )?;
+/// # Ok::<(), notify_rust::error::Error>(())
+/// ```
+#[derive(Debug, Clone)]
+pub struct PushServer {
+    service: Service,
+    url: String,
+    token: Option<String>,
+    timeout: Duration,
+}
+
+#[derive(Debug, Clone)]
+enum Service {
+    Ntfy { topic: String },
+    Gotify,
+}
+
+impl PushServer {
+    /// Publishes to `topic` on the ntfy server at `url`.
+    pub fn ntfy(url: &str, topic: &str) -> PushServer {
+        PushServer {
+            service: Service::Ntfy {
+                topic: topic.to_owned(),
+            },
+            url: url.trim_end_matches('/').to_owned(),
+            token: None,
+            timeout: Duration::from_secs(10),
+        }
+    }
+
+    /// Sends to the Gotify server at `url` as the application `app_token` belongs to.
+    pub fn gotify(url: &str, app_token: &str) -> PushServer {
+        PushServer {
+            service: Service::Gotify,
+            url: url.trim_end_matches('/').to_owned(),
+            token: Some(app_token.to_owned()),
+            timeout: Duration::from_secs(10),
+        }
+    }
+
+    /// Authenticates with an ntfy access token, for Gotify this replaces the app token.
+    pub fn token(mut self, token: &str) -> PushServer {
+        self.token = Some(token.to_owned());
+        self
+    }
+
+    /// How long to wait for the server to answer, 10 seconds by default.
+    pub fn timeout(mut self, timeout: Duration) -> PushServer {
+        self.timeout = timeout;
+        self
+    }
+
+    /// Pushes `notification` to the server.
+    pub fn send(&self, notification: &Notification) -> Result<()> {
+        let agent = ureq::AgentBuilder::new().timeout(self.timeout).build();
+        let request = match self.service {
+            Service::Ntfy { .. } => {
+                let request = agent.post(&self.url);
+                match &self.token {
+                    Some(token) => request.set("Authorization", &format!("Bearer {}", token)),
+                    None => request,
+                }
+            }
+            Service::Gotify => {
+                let request = agent.post(&format!("{}/message", self.url));
+                match &self.token {
+                    Some(token) => request.set("X-Gotify-Key", token),
+                    None => request,
+                }
+            }
+        };
+        request
+            .send_json(self.payload(notification))
+            .map(drop)
+            .map_err(|error| Error::from(error.to_string().as_str()))
+    }
+
+    fn payload(&self, notification: &Notification) -> Value {
+        match &self.service {
+            Service::Ntfy { topic } => ntfy_payload(notification, topic),
+            Service::Gotify => gotify_payload(notification),
+        }
+    }
+}
+
+fn ntfy_payload(notification: &Notification, topic: &str) -> Value {
+    let mut payload = Map::new();
+    payload.insert("topic".into(), topic.into());
+    payload.insert("title".into(), notification.summary.as_str().into());
+    payload.insert("message".into(), notification.body.as_str().into());
+    let priority = match priority(notification) {
+        Priority::Low => 2,
+        Priority::Normal => 3,
+        Priority::High => 5,
+    };
+    payload.insert("priority".into(), priority.into());
+    if is_url(&notification.icon) {
+        payload.insert("icon".into(), notification.icon.as_str().into());
+    }
+    if let Some(url) = click_url(notification) {
+        payload.insert("click".into(), url.into());
+    }
+    let actions = url_actions(notification)
+        .map(|(url, label)| json!({ "action": "view", "label": label, "url": url }))
+        .collect::<Vec<_>>();
+    if !actions.is_empty() {
+        payload.insert("actions".into(), actions.into());
+    }
+    payload.into()
+}
+
+fn gotify_payload(notification: &Notification) -> Value {
+    let mut payload = Map::new();
+    payload.insert("title".into(), notification.summary.as_str().into());
+    payload.insert("message".into(), notification.body.as_str().into());
+    let priority = match priority(notification) {
+        Priority::Low => 2,
+        Priority::Normal => 5,
+        Priority::High => 8,
+    };
+    payload.insert("priority".into(), priority.into());
+    // gotify has no buttons, only an url to open on click
+    let click =
+        click_url(notification).or_else(|| url_actions(notification).next().map(|(url, _)| url));
+    if let Some(url) = click {
+        payload.insert(
+            "extras".into(),
+            json!({ "client::notification": { "click": { "url": url } } }),
+        );
+    }
+    payload.into()
+}
+
+/// `(url, label)` of every action that opens an url.
+fn url_actions(notification: &Notification) -> impl Iterator<Item = (&str, &str)> {
+    notification
+        .actions
+        .iter()
+        .filter(|action| is_url(&action.identifier))
+        .map(|action| (action.identifier.as_str(), action.label.as_str()))
+}
+
+/// What a `"default"` action labeled with an url opens.
+fn click_url(notification: &Notification) -> Option<&str> {
+    notification
+        .actions
+        .iter()
+        .find(|action| action.identifier == "default" && is_url(&action.label))
+        .map(|action| action.label.as_str())
+}
+
+fn is_url(candidate: &str) -> bool {
+    candidate.starts_with("https://") || candidate.starts_with("http://")
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+fn priority(notification: &Notification) -> Priority {
+    notification
+        .hints
+        .iter()
+        .find_map(|hint| match hint {
+            Hint::Urgency(Urgency::Low) => Some(Priority::Low),
+            Hint::Urgency(Urgency::Normal) => Some(Priority::Normal),
+            Hint::Urgency(Urgency::Critical) => Some(Priority::High),
+            _ => None,
+        })
+        .unwrap_or_default()
+}
+
+#[cfg(not(all(unix, not(target_os = "macos"))))]
+fn priority(notification: &Notification) -> Priority {
+    notification.priority
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        io::{Read, Write},
+        net::TcpListener,
+        thread,
+    };
+
+    #[test]
+    fn ntfy() {
+        let payload = ntfy_payload(
+            Notification::new()
+                .summary("build finished")
+                .body("all green")
+                .priority(Priority::High)
+                .action("default", "https://ci.example.com")
+                .action("https://ci.example.com/42", "Open")
+                .action("retry", "Retry"),
+            "builds",
+        );
+        assert_eq!(
+            payload,
+            json!({
+                "topic": "builds",
+                "title": "build finished",
+                "message": "all green",
+                "priority": 5,
+                "click": "https://ci.example.com",
+                "actions": [{ "action": "view", "label": "Open", "url": "https://ci.example.com/42" }],
+            })
+        );
+    }
+
+    #[test]
+    fn gotify() {
+        let payload = gotify_payload(
+            Notification::new()
+                .summary("build finished")
+                .priority(Priority::Low)
+                .action("https://ci.example.com/42", "Open"),
+        );
+        assert_eq!(
+            payload,
+            json!({
+                "title": "build finished",
+                "message": "",
+                "priority": 2,
+                "extras": { "client::notification": { "click": { "url": "https://ci.example.com/42" } } },
+            })
+        );
+    }
+
+    #[test]
+    fn posts_to_gotify() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut request = String::new();
+            let mut buf = [0; 4096];
+            while !request.ends_with('}') {
+                let read = stream.read(&mut buf).unwrap();
+                request.push_str(std::str::from_utf8(&buf[..read]).unwrap());
+            }
+            stream
+                .write_all(b"HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n")
+                .unwrap();
+            request
+        });
+
+        PushServer::gotify(&url, "app-token")
+            .send(Notification::new().summary("hi"))
+            .unwrap();
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /message HTTP/1.1"));
+        assert!(request.contains("X-Gotify-Key: app-token"));
+        assert!(request.contains(r#""title":"hi""#));
+    }
+}
@@ -0,0 +1,151 @@
+//! Showing notifications later, see [`Notification::show_at`].
+
+use crate::{error::*, notification::Notification};
+
+use std::{fmt, time::SystemTime};
+#[cfg(all(unix, not(target_os = "macos")))]
+use std::{
+    sync::mpsc::{self, RecvTimeoutError, Sender},
+    thread,
+};
+
+/// A notification waiting for its delivery time, returned by [`Notification::show_at`] and [`Notification::show_after`].
+///
+/// Dropping it doesn't withdraw the notification, [`cancel`](`ScheduledNotification::cancel`) does.
+pub struct ScheduledNotification {
+    delivery_time: SystemTime,
+    inner: Inner,
+}
+
+enum Inner {
+    #[cfg(all(unix, not(target_os = "macos")))]
+    Timer(Sender<()>),
+    #[cfg(target_os = "macos")]
+    Native(crate::NotificationHandle),
+    #[cfg(target_os = "windows")]
+    Native(crate::windows::ScheduledToast),
+}
+
+impl ScheduledNotification {
+    /// When the notification is, or was, due.
+    pub fn delivery_time(&self) -> SystemTime {
+        self.delivery_time
+    }
+
+    /// Withdraws the notification, unless it was shown already.
+    ///
+    /// On macOS a notification that was delivered already is removed as well.
+    pub fn cancel(self) -> Result<()> {
+        match self.inner {
+            #[cfg(all(unix, not(target_os = "macos")))]
+            Inner::Timer(cancel) => {
+                // the timer is gone if it fired already
+                let _ = cancel.send(());
+                Ok(())
+            }
+            #[cfg(target_os = "macos")]
+            Inner::Native(handle) => handle.close(),
+            #[cfg(target_os = "windows")]
+            Inner::Native(toast) => toast.cancel(),
+        }
+    }
+}
+
+impl fmt::Debug for ScheduledNotification {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ScheduledNotification")
+            .field("delivery_time", &self.delivery_time)
+            .finish()
+    }
+}
+
+pub(crate) fn schedule(
+    notification: &Notification,
+    delivery_time: SystemTime,
+) -> Result<ScheduledNotification> {
+    if !notification.actions.is_empty() || notification.reply_placeholder.is_some() {
+        return Err("scheduled notifications can't report responses, they can't have actions or a reply input".into());
+    }
+
+    #[cfg(all(unix, not(target_os = "macos")))]
+    let inner = Inner::Timer(start_timer(notification.clone(), delivery_time)?);
+
+    #[cfg(target_os = "macos")]
+    let inner = Inner::Native(notification.schedule_at(delivery_time)?);
+
+    #[cfg(target_os = "windows")]
+    let inner = Inner::Native(crate::windows::schedule_notification(
+        notification,
+        delivery_time,
+    )?);
+
+    Ok(ScheduledNotification {
+        delivery_time,
+        inner,
+    })
+}
+
+/// Shows `notification` from a background thread once `delivery_time` has come, unless cancelled before.
+#[cfg(all(unix, not(target_os = "macos")))]
+fn start_timer(notification: Notification, delivery_time: SystemTime) -> Result<Sender<()>> {
+    let (cancel, cancelled) = mpsc::channel::<()>();
+    thread::Builder::new()
+        .name("notify-rust schedule".into())
+        .spawn(move || {
+            // the wall clock may jump or the machine sleep, so check it again after every wait
+            while let Ok(remaining) = delivery_time.duration_since(SystemTime::now()) {
+                match cancelled.recv_timeout(remaining) {
+                    Ok(()) => return,
+                    Err(RecvTimeoutError::Disconnected) => thread::sleep(remaining),
+                    Err(RecvTimeoutError::Timeout) => {}
+                }
+            }
+            if let Err(error) = notification.show_no_wait() {
+                log::warn!("failed to show scheduled notification: {}", error);
+            }
+        })
+        .map_err(|error| Error::from(error.to_string().as_str()))?;
+    Ok(cancel)
+}
+
+#[cfg(all(test, unix, not(target_os = "macos")))]
+mod tests {
+    use super::*;
+    use crate::Capture;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn shows_when_due_unless_cancelled() {
+        let capture = Capture::start();
+        Notification::new()
+            .summary("cancelled")
+            .show_after(Duration::from_millis(50))
+            .unwrap()
+            .cancel()
+            .unwrap();
+        let due = Notification::new()
+            .summary("due")
+            .show_after(Duration::from_millis(50))
+            .unwrap();
+        assert!(due.delivery_time() > SystemTime::now());
+        drop(due);
+
+        let deadline = Instant::now() + Duration::from_secs(5);
+        while capture.shown().is_empty() && Instant::now() < deadline {
+            thread::sleep(Duration::from_millis(10));
+        }
+        thread::sleep(Duration::from_millis(100));
+        let shown = capture.shown();
+        assert_eq!(shown.len(), 1);
+        assert_eq!(shown[0].summary, "due");
+    }
+
+    #[test]
+    fn rejects_actions() {
+        assert!(Notification::new()
+            .summary("confirm")
+            .action("ok", "Ok")
+            .show_after(Duration::from_secs(1))
+            .is_err());
+    }
+}
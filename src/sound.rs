@@ -0,0 +1,37 @@
+/// Sound played when a notification is shown, see [`Notification::sound`](`crate::Notification::sound`).
+///
+/// Each platform plays what it can and falls back to the closest thing it supports.
+///
+/// # Example
+/// ```no_run
+/// # use notify_rust::*;
+/// # fn _doc() -> Result<(), Box<dyn std::error::Error>> {
+/// Notification::new()
+///     .summary("you've got mail")
+///     .sound(Sound::Named("message-new-email".into()))
+///     .show()?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash, Default)]
+pub enum Sound {
+    /// Whatever the platform plays by default.
+    #[default]
+    Default,
+
+    /// No sound at all.
+    Silent,
+
+    /// A sound by name.
+    ///
+    /// On xdg this is a name from the sound theme, e.g. `"message-new-instant"`,
+    /// on macOS the name of a system sound like `"Ping"`,
+    /// on windows one of `"IM"`, `"Mail"`, `"Reminder"`, `"SMS"`, `"Alarm"` … `"Alarm10"` or `"Call"` … `"Call10"`.
+    Named(String),
+
+    /// A sound file.
+    ///
+    /// macOS looks up a sound named like the file, which has to live in one of the `Library/Sounds` folders,
+    /// windows does not play arbitrary files and falls back to the default sound.
+    File(String),
+}
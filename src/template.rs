@@ -0,0 +1,124 @@
+//! Shared settings for many notifications, see [`NotificationTemplate`].
+
+use crate::{error::*, notification::Notification, timeout::Timeout, NotificationHandle};
+
+#[cfg(all(unix, not(target_os = "macos")))]
+use crate::{category::Category, urgency::Urgency};
+
+/// Settings many notifications have in common, stamped out into notifications that only differ in their text.
+///
+/// ```no_run
+/// # use notify_rust::*;
+/// let mut builds = NotificationTemplate::new();
+/// builds.appname("ci").icon("system-run").timeout(Timeout::Milliseconds(6000));
+///
+/// builds.show("build passed", "all 42 tests passed")?;
+/// builds.stamp("build failed", "see the log").icon("dialog-error").show()?;
+/// # Ok::<(), notify_rust::error::Error>(())
+/// ```
+///
+/// Anything else can be set on the template's notification with [`with()`](`NotificationTemplate::with`).
+#[derive(Debug, Clone, Default)]
+pub struct NotificationTemplate {
+    notification: Notification,
+}
+
+impl NotificationTemplate {
+    /// A template that stamps out [`Notification::new()`] with a summary and body.
+    pub fn new() -> NotificationTemplate {
+        NotificationTemplate::default()
+    }
+
+    /// See [`Notification::appname()`].
+    pub fn appname(&mut self, appname: impl Into<String>) -> &mut NotificationTemplate {
+        self.notification.appname(appname);
+        self
+    }
+
+    /// See [`Notification::icon()`].
+    pub fn icon(&mut self, icon: impl Into<String>) -> &mut NotificationTemplate {
+        self.notification.icon(icon);
+        self
+    }
+
+    /// See [`Notification::timeout()`].
+    pub fn timeout<T: Into<Timeout>>(&mut self, timeout: T) -> &mut NotificationTemplate {
+        self.notification.timeout(timeout);
+        self
+    }
+
+    /// See [`Notification::urgency()`].
+    #[cfg(all(unix, not(target_os = "macos")))]
+    pub fn urgency(&mut self, urgency: Urgency) -> &mut NotificationTemplate {
+        self.notification.urgency(urgency);
+        self
+    }
+
+    /// See [`Notification::category()`].
+    #[cfg(all(unix, not(target_os = "macos")))]
+    pub fn category(&mut self, category: Category) -> &mut NotificationTemplate {
+        self.notification.category(category);
+        self
+    }
+
+    /// Changes the template's notification with any of its builder methods.
+    pub fn with(&mut self, apply: impl FnOnce(&mut Notification)) -> &mut NotificationTemplate {
+        apply(&mut self.notification);
+        self
+    }
+
+    /// A copy of the template's notification with this `summary` and `body`,
+    /// which can still be changed before it is shown.
+    pub fn stamp(&self, summary: impl Into<String>, body: impl Into<String>) -> Notification {
+        let mut notification = self.notification.clone();
+        notification.summary(summary).body(body);
+        notification
+    }
+
+    /// Shows a [`stamp()`](`NotificationTemplate::stamp`) of the template right away.
+    pub fn show(
+        &self,
+        summary: impl Into<String>,
+        body: impl Into<String>,
+    ) -> Result<NotificationHandle> {
+        self.stamp(summary, body).show()
+    }
+}
+
+impl From<Notification> for NotificationTemplate {
+    fn from(notification: Notification) -> Self {
+        NotificationTemplate { notification }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stamps_copies() {
+        let mut template = NotificationTemplate::new();
+        template
+            .appname("ci")
+            .icon("system-run")
+            .timeout(Timeout::Never)
+            .with(|n| {
+                n.action("log", "Show log");
+            });
+
+        let passed = template.stamp("build passed", "42 tests");
+        let mut failed = template.stamp("build failed", "1 test");
+        failed.icon("dialog-error");
+
+        assert_eq!(passed.appname, "ci");
+        assert_eq!(passed.icon, "system-run");
+        assert_eq!(passed.timeout, Timeout::Never);
+        assert_eq!(passed.actions.len(), 1);
+        assert_eq!(
+            (passed.summary.as_str(), passed.body.as_str()),
+            ("build passed", "42 tests")
+        );
+        assert_eq!(failed.icon, "dialog-error");
+        assert_eq!(template.stamp("", "").icon, "system-run");
+    }
+}
@@ -0,0 +1,186 @@
+//! Prints notifications to the terminal, for when there is nobody to show them on screen.
+
+use crate::{error::*, notification::Notification};
+
+#[cfg(all(unix, not(target_os = "macos")))]
+use crate::action::{ActionResponse, ActionResponseHandler, CloseReason};
+
+use std::{
+    fmt::Write as _,
+    io::{self, Write as _},
+    sync::RwLock,
+};
+
+/// Set by [`set_terminal_fallback`].
+static FALLBACK: RwLock<Option<TerminalStyle>> = RwLock::new(None);
+
+/// How notifications are printed to the terminal,
+/// see [`Notification::show_in_terminal`] and [`set_terminal_fallback`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TerminalStyle {
+    /// Highlight the summary with ANSI escape codes.
+    pub ansi: bool,
+    /// Ring the terminal bell.
+    pub bell: bool,
+}
+
+impl Default for TerminalStyle {
+    /// Uses ANSI escape codes unless `$NO_COLOR` is set or `$TERM` is missing or `"dumb"`,
+    /// doesn't ring the bell.
+    fn default() -> TerminalStyle {
+        let term = std::env::var("TERM").unwrap_or_default();
+        TerminalStyle {
+            ansi: std::env::var_os("NO_COLOR").is_none() && !term.is_empty() && term != "dumb",
+            bell: false,
+        }
+    }
+}
+
+impl TerminalStyle {
+    pub(crate) fn render(self, notification: &Notification) -> String {
+        let mut rendered = String::new();
+        if self.bell {
+            rendered.push('\x07');
+        }
+        let title = if notification.appname.is_empty() {
+            notification.summary.clone()
+        } else {
+            format!("{}: {}", notification.appname, notification.summary)
+        };
+        if self.ansi {
+            let _ = writeln!(rendered, "\x1b[1m{}\x1b[0m", title);
+        } else {
+            let _ = writeln!(rendered, "{}", title);
+        }
+        if let Some(subtitle) = &notification.subtitle {
+            let _ = writeln!(rendered, "{}", subtitle);
+        }
+        for line in notification.body.lines() {
+            let _ = writeln!(rendered, "  {}", line);
+        }
+        let labels = notification
+            .actions
+            .iter()
+            .filter(|action| action.identifier != "default")
+            .map(|action| format!("[{}]", action.label))
+            .collect::<Vec<_>>();
+        if !labels.is_empty() {
+            let _ = writeln!(rendered, "  {}", labels.join(" "));
+        }
+        rendered
+    }
+
+    fn print(self, notification: &Notification) -> Result<()> {
+        let mut stdout = io::stdout().lock();
+        stdout
+            .write_all(self.render(notification).as_bytes())
+            .and_then(|()| stdout.flush())
+            .map_err(|error| Error::from(error.to_string().as_str()))
+    }
+}
+
+/// Makes [`Notification::show`] print notifications to the terminal if they can't be shown,
+/// e.g. in an ssh session or a container without a notification server.
+///
+/// Pass `None` to turn the fallback off again, which is the default.
+/// Only xdg needs this, windows and macOS always have somewhere to show notifications.
+pub fn set_terminal_fallback(style: Option<TerminalStyle>) {
+    if let Ok(mut fallback) = FALLBACK.write() {
+        *fallback = style;
+    }
+}
+
+impl Notification {
+    /// Prints the notification to stdout instead of showing it, available on all platforms.
+    ///
+    /// Actions are listed by their labels, but can't be invoked from the terminal.
+    pub fn show_in_terminal(&self, style: TerminalStyle) -> Result<()> {
+        style.print(self)
+    }
+}
+
+/// Prints `notification` if a [fallback](`set_terminal_fallback`) is configured,
+/// otherwise passes on the `error` it failed to show with.
+#[cfg(all(unix, not(target_os = "macos")))]
+pub(crate) fn fallback(
+    notification: &Notification,
+    error: Error,
+) -> Result<TerminalNotificationHandle> {
+    let style = FALLBACK
+        .read()
+        .ok()
+        .and_then(|fallback| *fallback)
+        .ok_or(error)?;
+    style.print(notification)?;
+    Ok(TerminalNotificationHandle {
+        id: notification.id.unwrap_or_default(),
+        style,
+        notification: notification.clone(),
+    })
+}
+
+/// A notification that was printed to the terminal,
+/// it went away the moment it was printed, nobody can act upon it.
+#[cfg(all(unix, not(target_os = "macos")))]
+#[derive(Debug)]
+pub(crate) struct TerminalNotificationHandle {
+    pub(crate) id: u32,
+    style: TerminalStyle,
+    pub(crate) notification: Notification,
+}
+
+#[cfg(all(unix, not(target_os = "macos")))]
+impl TerminalNotificationHandle {
+    pub fn wait_for_action(self, invocation_closure: impl ActionResponseHandler) {
+        invocation_closure.call(&ActionResponse::Closed(CloseReason::Expired));
+    }
+
+    pub fn update(&mut self) -> Result<()> {
+        self.style.print(&self.notification)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TerminalStyle;
+    use crate::Notification;
+
+    const PLAIN: TerminalStyle = TerminalStyle {
+        ansi: false,
+        bell: false,
+    };
+
+    #[test]
+    fn renders_summary_and_body() {
+        let rendered = PLAIN.render(
+            Notification::new()
+                .appname("mail")
+                .summary("new mail")
+                .body("from: you\nsubject: hi"),
+        );
+        assert_eq!(rendered, "mail: new mail\n  from: you\n  subject: hi\n");
+    }
+
+    #[test]
+    fn lists_action_labels() {
+        let rendered = PLAIN.render(
+            Notification::new()
+                .appname("")
+                .summary("new mail")
+                .action("default", "Open")
+                .action("archive", "Archive")
+                .action("delete", "Delete"),
+        );
+        assert_eq!(rendered, "new mail\n  [Archive] [Delete]\n");
+    }
+
+    #[test]
+    fn styles_with_ansi_and_bell() {
+        let style = TerminalStyle {
+            ansi: true,
+            bell: true,
+        };
+        let rendered = style.render(Notification::new().appname("").summary("ding"));
+        assert_eq!(rendered, "\x07\x1b[1mding\x1b[0m\n");
+    }
+}
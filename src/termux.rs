@@ -0,0 +1,146 @@
+//! Shows notifications on Android through [Termux:API](https://wiki.termux.com/wiki/Termux:API),
+//! since there is no notification server on a session bus there.
+//!
+//! This needs the `termux-api` package and the Termux:API app,
+//! apps that don't run inside Termux would have to go through JNI instead.
+
+use crate::{
+    action::{ActionResponse, ActionResponseHandler, CloseReason},
+    error::*,
+    hints::Hint,
+    notification::Notification,
+    timeout::Timeout,
+    urgency::Urgency,
+};
+
+use std::{
+    io,
+    process::{Command, Stdio},
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+/// Ids for notifications that don't set their own [`Notification::id`].
+static NEXT_ID: AtomicU32 = AtomicU32::new(1);
+
+/// Shows `notification` via `termux-notification` if it is installed,
+/// otherwise passes on the `error` it failed to show with.
+pub(crate) fn bridge(
+    notification: &Notification,
+    error: Error,
+) -> Result<TermuxNotificationHandle> {
+    let id = notification
+        .id
+        .unwrap_or_else(|| NEXT_ID.fetch_add(1, Ordering::Relaxed));
+    let handle = TermuxNotificationHandle {
+        id,
+        notification: notification.clone(),
+    };
+    match run("termux-notification", &notification_args(notification, id)) {
+        Err(missing) if missing.kind() == io::ErrorKind::NotFound => Err(error),
+        Err(failed) => Err(Error::from(failed.to_string().as_str())),
+        Ok(()) => Ok(handle),
+    }
+}
+
+/// A notification shown by `termux-notification`.
+///
+/// Termux runs commands when buttons are pressed instead of reporting them back,
+/// so all we can do is replace or remove the notification.
+#[derive(Debug)]
+pub(crate) struct TermuxNotificationHandle {
+    pub(crate) id: u32,
+    pub(crate) notification: Notification,
+}
+
+impl TermuxNotificationHandle {
+    pub fn wait_for_action(self, invocation_closure: impl ActionResponseHandler) {
+        invocation_closure.call(&ActionResponse::Closed(CloseReason::Expired));
+    }
+
+    /// Shows the notification again under the same id, which replaces the old one.
+    pub fn update(&mut self) -> Result<()> {
+        run(
+            "termux-notification",
+            &notification_args(&self.notification, self.id),
+        )
+        .map_err(|error| Error::from(error.to_string().as_str()))
+    }
+
+    pub fn close(self) -> Result<()> {
+        run("termux-notification-remove", &[self.id.to_string()])
+            .map_err(|error| Error::from(error.to_string().as_str()))
+    }
+}
+
+fn run(program: &str, args: &[String]) -> io::Result<()> {
+    let output = Command::new(program)
+        .args(args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .output()?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(io::Error::new(
+            io::ErrorKind::Other,
+            format!("{} failed: {}", program, stderr.trim()),
+        ))
+    }
+}
+
+fn notification_args(notification: &Notification, id: u32) -> Vec<String> {
+    let mut args = vec![
+        "--id".to_owned(),
+        id.to_string(),
+        "--title".to_owned(),
+        notification.summary.clone(),
+        "--content".to_owned(),
+        notification.body.clone(),
+    ];
+    for hint in &notification.hints {
+        match hint {
+            Hint::Urgency(Urgency::Critical) => args.extend(["--priority".into(), "high".into()]),
+            Hint::Urgency(Urgency::Low) => args.extend(["--priority".into(), "low".into()]),
+            Hint::SoundName(_) | Hint::SoundFile(_) => args.push("--sound".into()),
+            Hint::ImagePath(path) => args.extend(["--image-path".into(), path.clone()]),
+            _ => {}
+        }
+    }
+    if notification.timeout == Timeout::Never {
+        args.push("--ongoing".into());
+    }
+    args
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn notification_args_map_hints() {
+        let args = notification_args(
+            Notification::new()
+                .summary("new mail")
+                .body("from: you")
+                .urgency(Urgency::Critical)
+                .timeout(Timeout::Never),
+            7,
+        );
+        assert_eq!(
+            args,
+            [
+                "--id",
+                "7",
+                "--title",
+                "new mail",
+                "--content",
+                "from: you",
+                "--priority",
+                "high",
+                "--ongoing"
+            ]
+        );
+    }
+}
@@ -1,16 +1,20 @@
-use std::{num::ParseIntError, str::FromStr, time::Duration};
+use std::{fmt, num::ParseIntError, str::FromStr, time::Duration};
 
 /// Describes the timeout of a notification
 ///
 /// # `FromStr`
-/// You can also parse a `Timeout` from a `&str`.
+/// You can also parse a `Timeout` from a `&str`, bare numbers are milliseconds.
 /// ```
 /// # use notify_rust::Timeout;
 /// assert_eq!("default".parse(), Ok(Timeout::Default));
 /// assert_eq!("never".parse(), Ok(Timeout::Never));
 /// assert_eq!("42".parse(), Ok(Timeout::Milliseconds(42)));
+/// assert_eq!("250ms".parse(), Ok(Timeout::Milliseconds(250)));
+/// assert_eq!("5s".parse(), Ok(Timeout::Milliseconds(5_000)));
 /// ```
-#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum Timeout {
     /// Expires according to server default.
     ///
@@ -32,6 +36,15 @@ fn timeout_from_i32() {
     assert_eq!(Timeout::from(0), Timeout::Never);
 }
 
+#[test]
+fn timeout_from_str() {
+    assert_eq!(" 250ms".parse(), Ok(Timeout::Milliseconds(250)));
+    assert_eq!("0s".parse(), Ok(Timeout::Never));
+    assert_eq!("5000000s".parse(), Ok(Timeout::Default));
+    assert!("5m".parse::<Timeout>().is_err());
+    assert!("ms".parse::<Timeout>().is_err());
+}
+
 impl From<i32> for Timeout {
     fn from(int: i32) -> Timeout {
         use std::cmp::Ordering::*;
@@ -65,10 +78,29 @@ impl From<Timeout> for i32 {
     }
 }
 
+/// Written the way [`FromStr`] reads it, e.g. `"never"` or `"5000ms"`.
+impl fmt::Display for Timeout {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Timeout::Default => f.write_str("default"),
+            Timeout::Never => f.write_str("never"),
+            Timeout::Milliseconds(ms) => write!(f, "{}ms", ms),
+        }
+    }
+}
+
 impl FromStr for Timeout {
     type Err = ParseIntError;
 
     fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if let Some(milliseconds) = s.strip_suffix("ms") {
+            return Ok(Timeout::Milliseconds(u32::from_str(milliseconds)?));
+        }
+        if let Some(seconds) = s.strip_suffix('s') {
+            let seconds = u32::from_str(seconds)?;
+            return Ok(Timeout::from(Duration::from_secs(seconds.into())));
+        }
         match s {
             "default" => Ok(Timeout::Default),
             "never" => Ok(Timeout::Never),
@@ -76,28 +108,3 @@ impl FromStr for Timeout {
         }
     }
 }
-
-pub struct TimeoutMessage(Timeout);
-
-impl From<Timeout> for TimeoutMessage {
-    fn from(hint: Timeout) -> Self {
-        TimeoutMessage(hint)
-    }
-}
-
-impl std::ops::Deref for TimeoutMessage {
-    type Target = Timeout;
-
-    fn deref(&self) -> &Self::Target {
-        &self.0
-    }
-}
-
-#[cfg(all(feature = "dbus", unix, not(target_os = "macos")))]
-impl TryFrom<&dbus::arg::messageitem::MessageItem> for TimeoutMessage {
-    type Error = ();
-
-    fn try_from(mi: &dbus::arg::messageitem::MessageItem) -> Result<TimeoutMessage, ()> {
-        mi.inner::<i32>().map(|i| TimeoutMessage(i.into()))
-    }
-}
@@ -0,0 +1,87 @@
+//! Instrumentation with [`tracing`](https://docs.rs/tracing), all of this does nothing without the `tracing` feature.
+
+#![cfg_attr(not(feature = "tracing"), allow(unused_variables))]
+
+use crate::{action::ActionResult, error::*, notification::Notification};
+
+#[cfg(feature = "tracing")]
+use std::time::Instant;
+
+/// A span around showing, updating or closing a notification, ended by [`Trace::finish`].
+pub(crate) struct Trace {
+    #[cfg(feature = "tracing")]
+    span: tracing::span::EnteredSpan,
+    #[cfg(feature = "tracing")]
+    started: Instant,
+}
+
+/// Enters a span for `operation` on `notification`, `id` is `None` until the server assigned one.
+pub(crate) fn start(
+    operation: &'static str,
+    id: Option<u32>,
+    notification: &Notification,
+) -> Trace {
+    Trace {
+        #[cfg(feature = "tracing")]
+        span: tracing::debug_span!(
+            "notification",
+            operation,
+            id,
+            appname = %notification.appname,
+            summary = %notification.summary,
+        )
+        .entered(),
+        #[cfg(feature = "tracing")]
+        started: Instant::now(),
+    }
+}
+
+impl Trace {
+    /// Records the id the notification ended up with.
+    pub(crate) fn id(&self, id: u32) {
+        #[cfg(feature = "tracing")]
+        self.span.record("id", id);
+    }
+
+    /// Logs how long it took and whether it failed, then leaves the span.
+    pub(crate) fn finish<T>(self, result: &Result<T>) {
+        #[cfg(feature = "tracing")]
+        {
+            let elapsed_ms = self.started.elapsed().as_millis() as u64;
+            match result {
+                Ok(_) => tracing::debug!(elapsed_ms, "done"),
+                Err(error) => tracing::warn!(elapsed_ms, %error, "failed"),
+            }
+        }
+    }
+}
+
+/// Logs a signal the notification server sent about notification `id`.
+#[cfg_attr(
+    not(all(
+        unix,
+        not(target_os = "macos"),
+        any(feature = "dbus", feature = "zbus")
+    )),
+    allow(dead_code)
+)]
+pub(crate) fn signal(id: u32, result: &ActionResult) {
+    #[cfg(feature = "tracing")]
+    match result {
+        ActionResult::Action(action) => tracing::debug!(id, %action, "action invoked"),
+        ActionResult::Closed(reason) => tracing::debug!(id, ?reason, "notification closed"),
+        ActionResult::Timeout => {}
+    }
+}
+
+/// Logs a call to the embedded notification server.
+#[cfg(all(
+    feature = "embedded_server",
+    feature = "zbus",
+    unix,
+    not(target_os = "macos")
+))]
+pub(crate) fn request(method: &'static str, id: u32, appname: &str) {
+    #[cfg(feature = "tracing")]
+    tracing::debug!(method, id, appname, "server request");
+}
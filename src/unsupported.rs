@@ -0,0 +1,99 @@
+//! What to do about features the platform can't show, see [`Unsupported`].
+
+use crate::{error::*, notification::Notification};
+
+/// What [`show()`](`Notification::show`) does when a notification asks for something
+/// the platform or notification server can't express, set with [`Notification::on_unsupported`].
+///
+/// These are e.g. an icon on windows and macOS, a timeout on macOS,
+/// or actions, sounds and a body on XDG servers lacking the matching [`Capability`](`crate::Capability`).
+///
+/// ```no_run
+/// # use notify_rust::*;
+/// Notification::new()
+///     .summary("reboot required")
+///     .action("reboot", "Reboot now")
+///     .on_unsupported(Unsupported::Strict)
+///     .show()?;
+/// # Ok::<(), notify_rust::error::Error>(())
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Unsupported {
+    /// Show the notification without them, like always.
+    #[default]
+    Ignore,
+    /// Show the notification without them, but log a warning naming them.
+    Warn,
+    /// Don't show the notification, fail with [`ErrorKind::Unsupported`] instead.
+    Strict,
+}
+
+impl Notification {
+    /// Decides what happens to features [`show()`](`Notification::show`) would otherwise silently drop,
+    /// [`Unsupported::Ignore`] them by default.
+    pub fn on_unsupported(&mut self, policy: Unsupported) -> &mut Notification {
+        self.on_unsupported = policy;
+        self
+    }
+
+    /// Applies the [`Unsupported`] policy to what the platform reports as `unsupported`,
+    /// which is only looked at unless the policy is to ignore it.
+    #[cfg_attr(
+        all(
+            unix,
+            not(target_os = "macos"),
+            not(any(feature = "dbus", feature = "zbus"))
+        ),
+        allow(dead_code)
+    )]
+    pub(crate) fn check_supported(
+        &self,
+        unsupported: impl FnOnce(&Notification) -> Vec<&'static str>,
+    ) -> Result<()> {
+        if self.on_unsupported == Unsupported::Ignore {
+            return Ok(());
+        }
+        let features = unsupported(self);
+        if features.is_empty() {
+            return Ok(());
+        }
+        let features = features.join(", ");
+        if self.on_unsupported == Unsupported::Strict {
+            return Err(ErrorKind::Unsupported(features).into());
+        }
+        log::warn!("showing {:?} without {}", self.summary, features);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn no_icons(notification: &Notification) -> Vec<&'static str> {
+        if notification.icon.is_empty() {
+            vec![]
+        } else {
+            vec!["icon"]
+        }
+    }
+
+    #[test]
+    fn applies_policy() {
+        let mut notification = Notification::new();
+        notification.icon("dialog-information");
+        notification.check_supported(no_icons).unwrap();
+
+        notification.on_unsupported(Unsupported::Warn);
+        notification.check_supported(no_icons).unwrap();
+
+        notification.on_unsupported(Unsupported::Strict);
+        let error = notification.check_supported(no_icons).unwrap_err();
+        assert!(matches!(error.kind(), ErrorKind::Unsupported(features) if features == "icon"));
+
+        notification.icon("");
+        notification.check_supported(no_icons).unwrap();
+    }
+}
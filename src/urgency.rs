@@ -1,5 +1,7 @@
 use crate::error::ErrorKind;
 
+use std::fmt;
+
 /// Levels of Urgency.
 ///
 /// # Specification
@@ -25,6 +27,8 @@ use crate::error::ErrorKind;
 /// ```
 ///
 #[derive(Eq, PartialEq, Hash, Copy, Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum Urgency {
     /// The behavior for `Low` urgency depends on the notification server.
     Low = 0,
@@ -34,6 +38,52 @@ pub enum Urgency {
     Critical = 2,
 }
 
+/// Portable priority of a notification, see [`Notification::priority`](`crate::Notification::priority`).
+#[derive(Eq, PartialEq, Hash, Copy, Clone, Debug, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Priority {
+    /// Maps to [`Urgency::Low`] on xdg, on macOS the notification is passive.
+    Low,
+    /// Maps to [`Urgency::Normal`] on xdg.
+    #[default]
+    Normal,
+    /// Maps to [`Urgency::Critical`] on xdg,
+    /// on windows the toast stays on screen longer and breaks through Focus Assist,
+    /// on macOS the notification is time sensitive and breaks through Focus.
+    High,
+}
+
+impl From<Priority> for Urgency {
+    fn from(priority: Priority) -> Urgency {
+        match priority {
+            Priority::Low => Urgency::Low,
+            Priority::Normal => Urgency::Normal,
+            Priority::High => Urgency::Critical,
+        }
+    }
+}
+
+impl fmt::Display for Urgency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Urgency::Low => "low",
+            Urgency::Normal => "normal",
+            Urgency::Critical => "critical",
+        })
+    }
+}
+
+impl fmt::Display for Priority {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Priority::Low => "low",
+            Priority::Normal => "normal",
+            Priority::High => "high",
+        })
+    }
+}
+
 impl TryFrom<&str> for Urgency {
     type Error = crate::error::Error;
 
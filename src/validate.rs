@@ -0,0 +1,195 @@
+//! Checks a notification before it is sent, see [`Notification::validate`].
+
+use crate::{error::*, notification::Notification};
+
+#[cfg(all(unix, not(target_os = "macos")))]
+use crate::hints::Hint;
+
+use std::{collections::HashSet, path::Path};
+
+/// Longer bodies are cut off or refused by most servers.
+const MAX_BODY_BYTES: usize = 64 * 1024;
+
+/// Raw image data beyond this gets close to the 128 MiB D-Bus allows per message.
+#[cfg(feature = "images")]
+const MAX_IMAGE_BYTES: usize = 32 * 1024 * 1024;
+
+impl Notification {
+    /// Checks for mistakes servers reject or mangle the notification over, without sending it.
+    ///
+    /// Fails with [`ErrorKind::Invalid`] describing the first of these it finds:
+    /// * text containing NUL bytes, which D-Bus strings can't carry
+    /// * a body over 64 KiB or image data over 32 MiB
+    /// * actions with an empty or repeated identifier
+    /// * icon or image paths that don't exist
+    ///
+    /// ```no_run
+    /// # use notify_rust::*;
+    /// let mut notification = Notification::new();
+    /// notification.summary("build done").icon("/no/such/icon.png");
+    /// assert!(notification.validate().is_err());
+    /// ```
+    pub fn validate(&self) -> Result<()> {
+        let texts = [
+            ("appname", self.appname.as_str()),
+            ("summary", &self.summary),
+            ("subtitle", self.subtitle.as_deref().unwrap_or_default()),
+            ("body", &self.body),
+            ("icon", &self.icon),
+        ];
+        for (field, text) in texts {
+            no_nul(field, text)?;
+        }
+        ensure_valid(
+            self.body.len() <= MAX_BODY_BYTES,
+            format!(
+                "body is {} bytes long, at most {} are allowed",
+                self.body.len(),
+                MAX_BODY_BYTES
+            ),
+        )?;
+
+        let mut identifiers = HashSet::new();
+        for action in &self.actions {
+            no_nul("action identifier", &action.identifier)?;
+            no_nul("action label", &action.label)?;
+            ensure_valid(
+                !action.identifier.is_empty(),
+                format!("action {:?} has an empty identifier", action.label),
+            )?;
+            ensure_valid(
+                identifiers.insert(action.identifier.as_str()),
+                format!("action identifier {:?} is used twice", action.identifier),
+            )?;
+        }
+
+        if let Some(path) = icon_path(&self.icon) {
+            existing_file("icon", path)?;
+        }
+        self.validate_platform()
+    }
+
+    #[cfg(all(unix, not(target_os = "macos")))]
+    fn validate_platform(&self) -> Result<()> {
+        for hint in &self.hints {
+            match hint {
+                Hint::ImagePath(path) => {
+                    no_nul("image path", path)?;
+                    if let Some(path) = icon_path(path) {
+                        existing_file("image", path)?;
+                    }
+                }
+                Hint::SoundFile(path) => {
+                    no_nul("sound file", path)?;
+                    existing_file("sound", Path::new(path))?;
+                }
+                #[cfg(feature = "images")]
+                Hint::ImageData(image) => image_size(image.data_len())?,
+                _ => {}
+            }
+        }
+        Ok(())
+    }
+
+    #[cfg(any(target_os = "macos", target_os = "windows"))]
+    fn validate_platform(&self) -> Result<()> {
+        #[cfg(feature = "images")]
+        if let Some(image) = &self.image_data {
+            image_size(image.data_len())?;
+        }
+        match self.path_to_image.as_deref().and_then(icon_path) {
+            Some(path) => existing_file("image", path),
+            None => Ok(()),
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn validate_platform(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+fn ensure_valid(condition: bool, problem: String) -> Result<()> {
+    if condition {
+        Ok(())
+    } else {
+        Err(ErrorKind::Invalid(problem).into())
+    }
+}
+
+fn no_nul(field: &str, text: &str) -> Result<()> {
+    ensure_valid(
+        !text.contains('\0'),
+        format!("{} contains a NUL byte", field),
+    )
+}
+
+fn existing_file(field: &str, path: &Path) -> Result<()> {
+    ensure_valid(
+        path.is_file(),
+        format!("{} {} does not exist", field, path.display()),
+    )
+}
+
+#[cfg(feature = "images")]
+fn image_size(bytes: usize) -> Result<()> {
+    ensure_valid(
+        bytes <= MAX_IMAGE_BYTES,
+        format!(
+            "image data is {} bytes large, at most {} are allowed",
+            bytes, MAX_IMAGE_BYTES
+        ),
+    )
+}
+
+/// The local file an icon or image refers to, `None` for icon names and remote URLs.
+fn icon_path(icon: &str) -> Option<&Path> {
+    let path = icon.strip_prefix("file://").unwrap_or(icon);
+    if path.starts_with('/') {
+        Some(Path::new(path))
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn problem(notification: &Notification) -> String {
+        match notification.validate().unwrap_err().kind() {
+            ErrorKind::Invalid(problem) => problem.clone(),
+            other => panic!("unexpected error {:?}", other),
+        }
+    }
+
+    #[test]
+    fn accepts_plain_notifications() {
+        let mut notification = Notification::new();
+        notification
+            .summary("summary")
+            .body("body")
+            .icon("dialog-information")
+            .action("open", "Open");
+        notification.validate().unwrap();
+    }
+
+    #[test]
+    fn rejects_mistakes() {
+        let mut nul = Notification::new();
+        nul.summary("sum\0mary");
+        assert_eq!(problem(&nul), "summary contains a NUL byte");
+
+        let mut long = Notification::new();
+        long.body("x".repeat(MAX_BODY_BYTES + 1));
+        assert!(problem(&long).starts_with("body is 65537 bytes long"));
+
+        let mut twice = Notification::new();
+        twice.action("open", "Open").action("open", "Open again");
+        assert_eq!(problem(&twice), r#"action identifier "open" is used twice"#);
+
+        let mut missing = Notification::new();
+        missing.icon("file:///no/such/icon.png");
+        assert_eq!(problem(&missing), "icon /no/such/icon.png does not exist");
+    }
+}
@@ -0,0 +1,227 @@
+//! Shows notifications through the browser's [Notification API](https://developer.mozilla.org/en-US/docs/Web/API/Notification).
+//!
+//! Browsers only show notifications after the user granted permission,
+//! ask for it with [`request_permission`] in response to a click or key press.
+//! Nothing may block in a browser, so [`NotificationHandle::wait_for_action`] and
+//! [`NotificationHandle::on_close`] only register their callbacks and return right away.
+
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use web_sys::{NotificationOptions, NotificationPermission};
+
+use crate::{
+    action::{CloseHandler, CloseReason, DEFAULT_ACTION},
+    error::*,
+    notification::Notification,
+    timeout::Timeout,
+    urgency::Priority,
+};
+
+use std::{
+    cell::Cell,
+    ops::{Deref, DerefMut},
+    rc::Rc,
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+/// Ids for notifications that don't set their own [`Notification::id`],
+/// also used as their tag so an update replaces the notification.
+static NEXT_ID: AtomicU32 = AtomicU32::new(1);
+
+/// Checks whether the browser supports notifications and the user allowed them.
+pub fn is_available() -> bool {
+    js_sys::Reflect::has(&js_sys::global(), &JsValue::from_str("Notification")).unwrap_or(false)
+        && web_sys::Notification::permission() == NotificationPermission::Granted
+}
+
+/// Asks the user for permission to show notifications and resolves to whether it was granted.
+///
+/// Browsers only ask in response to a user gesture, so call this from e.g. a click handler.
+/// Once the user decided, this resolves right away without asking again.
+pub async fn request_permission() -> Result<bool> {
+    let promise = web_sys::Notification::request_permission().map_err(js_error)?;
+    let permission = wasm_bindgen_futures::JsFuture::from(promise)
+        .await
+        .map_err(js_error)?;
+    Ok(permission.as_string().as_deref() == Some("granted"))
+}
+
+/// A handle to a notification shown by the browser.
+#[derive(Debug)]
+pub struct NotificationHandle {
+    id: u32,
+    inner: web_sys::Notification,
+    notification: Notification,
+}
+
+impl NotificationHandle {
+    /// Returns the id this notification was shown with.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Which API delivered the notification, for logging.
+    pub(crate) fn backend(&self) -> &'static str {
+        "browser"
+    }
+
+    /// Always `None`, browsers have no user visible tags.
+    pub fn tag(&self) -> Option<&str> {
+        None
+    }
+
+    /// Always `None`, browsers don't group notifications.
+    pub fn group(&self) -> Option<&str> {
+        None
+    }
+
+    /// Always `true`.
+    pub fn supports_update(&self) -> bool {
+        true
+    }
+
+    /// Always `true`.
+    pub fn supports_close(&self) -> bool {
+        true
+    }
+
+    /// Replaces the notification with the current content of the handle.
+    ///
+    /// Callbacks registered on the old notification are not carried over.
+    pub fn update(&mut self) -> Result<()> {
+        let trace = crate::trace::start("update", Some(self.id), &self.notification);
+        let shown = show(&self.notification, self.id);
+        trace.finish(&shown);
+        self.inner = shown?;
+        Ok(())
+    }
+
+    /// Always fails, browser notifications have no progress bar.
+    pub fn update_progress(&mut self, _value: f32, _status: &str) -> Result<()> {
+        Err("browser notifications have no progress bar".into())
+    }
+
+    /// Calls `invocation_closure` once the user clicks the notification or it goes away.
+    ///
+    /// A click is reported as `"default"` and anything else as `"__closed"`, just like on xdg.
+    /// Unlike on the other platforms this returns right away instead of waiting.
+    pub fn wait_for_action<F>(self, invocation_closure: F)
+    where
+        F: FnOnce(&str) + 'static,
+    {
+        let clicked = Rc::new(Cell::new(Some(invocation_closure)));
+        let closed = Rc::clone(&clicked);
+        self.inner.set_onclick(Some(
+            Closure::once_into_js(move || {
+                if let Some(invocation_closure) = clicked.take() {
+                    invocation_closure("default");
+                }
+            })
+            .unchecked_ref(),
+        ));
+        self.inner.set_onclose(Some(
+            Closure::once_into_js(move || {
+                if let Some(invocation_closure) = closed.take() {
+                    invocation_closure("__closed");
+                }
+            })
+            .unchecked_ref(),
+        ));
+    }
+
+    /// Calls `handler` once the notification goes away.
+    ///
+    /// Browsers don't say why, so this always reports [`CloseReason::Dismissed`].
+    /// Unlike on the other platforms this returns right away instead of waiting.
+    pub fn on_close<A: 'static>(self, handler: impl CloseHandler<A> + 'static) {
+        self.inner.set_onclose(Some(
+            Closure::once_into_js(move || handler.call(CloseReason::Dismissed)).unchecked_ref(),
+        ));
+    }
+
+    /// Always `None`, browser notifications have no text input.
+    pub fn wait_for_reply(self) -> Option<String> {
+        None
+    }
+
+    /// Removes the notification.
+    pub fn close(self) -> Result<()> {
+        let trace = crate::trace::start("close", Some(self.id), &self.notification);
+        self.inner.close();
+        let result = Ok(());
+        trace.finish(&result);
+        result
+    }
+}
+
+/// Required for `DerefMut`
+impl Deref for NotificationHandle {
+    type Target = Notification;
+
+    fn deref(&self) -> &Notification {
+        &self.notification
+    }
+}
+
+/// Allow you to easily modify notification properties
+impl DerefMut for NotificationHandle {
+    fn deref_mut(&mut self) -> &mut Notification {
+        &mut self.notification
+    }
+}
+
+/// What `notification` asks for that browsers don't show, only clicking the notification itself is reported.
+fn unsupported(notification: &Notification) -> Vec<&'static str> {
+    let mut unsupported = Vec::new();
+    if notification.subtitle.is_some() {
+        unsupported.push("subtitle");
+    }
+    if notification
+        .actions
+        .iter()
+        .any(|action| action.identifier != DEFAULT_ACTION)
+    {
+        unsupported.push("actions");
+    }
+    if let Timeout::Milliseconds(_) = notification.timeout {
+        unsupported.push("timeout");
+    }
+    unsupported
+}
+
+pub(crate) fn show_notification(notification: &Notification) -> Result<NotificationHandle> {
+    notification.check_supported(unsupported)?;
+    let id = notification
+        .id
+        .unwrap_or_else(|| NEXT_ID.fetch_add(1, Ordering::Relaxed));
+    Ok(NotificationHandle {
+        id,
+        inner: show(notification, id)?,
+        notification: notification.clone(),
+    })
+}
+
+fn show(notification: &Notification, id: u32) -> Result<web_sys::Notification> {
+    let options = NotificationOptions::new();
+    match &notification.tag {
+        Some(tag) => options.set_tag(tag),
+        None => options.set_tag(&format!("notify-rust-{}", id)),
+    }
+    if !notification.body.is_empty() {
+        options.set_body(&notification.body);
+    }
+    if !notification.icon.is_empty() {
+        options.set_icon(&notification.icon);
+    }
+    if notification.silent {
+        options.set_silent(Some(true));
+    }
+    // browsers decide on their own how long to show a notification, unless it requires interaction
+    options.set_require_interaction(
+        notification.timeout == Timeout::Never || notification.priority == Priority::High,
+    );
+    web_sys::Notification::new_with_options(&notification.summary, &options).map_err(js_error)
+}
+
+fn js_error(error: JsValue) -> Error {
+    Error::from(format!("{:?}", error).as_str())
+}
@@ -1,40 +1,925 @@
-use winrt_notification::Toast;
+use windows::{
+    core::{IInspectable, Interface, HSTRING},
+    Data::Xml::Dom::XmlDocument,
+    Foundation::{DateTime, IPropertyValue, IReference, PropertyValue, TypedEventHandler},
+    Win32::System::Registry::{RegSetKeyValueW, HKEY_CURRENT_USER, REG_SZ},
+    UI::Notifications::{
+        NotificationData, ScheduledToastNotification, ToastActivatedEventArgs,
+        ToastDismissalReason, ToastDismissedEventArgs, ToastFailedEventArgs, ToastNotification,
+        ToastNotificationManager, ToastNotifier,
+    },
+};
 
-pub use crate::{error::*, notification::Notification, timeout::Timeout};
+use crate::{
+    action::{
+        Action, ActionCallbacks, ActionResponse, ActionResult, CloseHandler, CloseReason,
+        DEFAULT_ACTION,
+    },
+    error::*,
+    notification::Notification,
+    timeout::Timeout,
+    urgency::Priority,
+    xml::escape,
+};
 
-use std::{path::Path, str::FromStr};
+use std::{
+    fmt::Write,
+    mem::size_of,
+    ops::{Deref, DerefMut},
+    path::Path,
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        mpsc::{self, Receiver, RecvTimeoutError, Sender},
+        RwLock,
+    },
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
-pub(crate) fn show_notification(notification: &Notification) -> Result<()> {
-    let sound = match &notification.sound_name {
-        Some(chosen_sound_name) => winrt_notification::Sound::from_str(chosen_sound_name).ok(),
-        None => None,
-    };
+/// This can be used if you do not have a `AppUserModelID`.
+///
+/// However, the toast will erroneously report its origin as powershell.
+pub(crate) const POWERSHELL_APP_ID: &str =
+    "{1AC14E77-02E7-4E5D-B744-2EB1AE5198B7}\\WindowsPowerShell\\v1.0\\powershell.exe";
+
+/// Group that toasts shown by this crate are filed under in the Action Center.
+const DEFAULT_GROUP: &str = "notify-rust";
+
+/// Id of the text input added by [`Notification::reply_input`].
+const REPLY_INPUT_ID: &str = "reply";
+
+/// Identifies the toasts shown by this process, unless they were given an [`id`](`Notification::id`).
+static NEXT_ID: AtomicU32 = AtomicU32::new(1);
+
+/// Set by [`register_app`], used by all toasts without an explicit [`Notification::app_id`].
+static REGISTERED_APP_ID: RwLock<Option<String>> = RwLock::new(None);
+
+/// Checks whether notifications can be shown, which on windows they always can.
+///
+/// Exists so code shared with linux and BSD doesn't need to `cfg` this away.
+pub fn is_available() -> bool {
+    true
+}
+
+/// Registers `app_id` as an `AppUserModelID` for the current user
+/// and uses it for all following toasts that don't set their own [`app_id`](`Notification::app_id`).
+///
+/// Without this toasts are attributed to Windows PowerShell,
+/// once registered they show `display_name` and `icon` (an absolute path to an image) instead.
+/// The registration lives in `HKEY_CURRENT_USER\Software\Classes\AppUserModelId`,
+/// so it only needs to happen once, doing it again is harmless.
+///
+/// # Example
+/// ```no_run
+/// notify_rust::register_app("de.hoodie.notify-rust", "notify-rust", None)?;
+/// notify_rust::Notification::new().summary("from notify-rust").show()?;
+/// # Ok::<(), notify_rust::error::Error>(())
+/// ```
+pub fn register_app(app_id: &str, display_name: &str, icon: Option<&Path>) -> Result<()> {
+    let key = format!("Software\\Classes\\AppUserModelId\\{}", app_id);
+    set_registry_string(&key, "DisplayName", display_name)?;
+    if let Some(icon) = icon {
+        let icon = icon.to_str().ok_or("icon path is not valid unicode")?;
+        set_registry_string(&key, "IconUri", icon)?;
+    }
+
+    if let Ok(mut registered) = REGISTERED_APP_ID.write() {
+        *registered = Some(app_id.to_owned());
+    }
+    Ok(())
+}
+
+/// Writes a `REG_SZ` value below `HKEY_CURRENT_USER`, creating the key if necessary.
+#[allow(unsafe_code)]
+fn set_registry_string(key: &str, name: &str, value: &str) -> Result<()> {
+    let data = value.encode_utf16().chain(Some(0)).collect::<Vec<u16>>();
+    // SAFETY: `data` is a nul terminated UTF-16 string which outlives the call
+    // and `cbdata` is its size in bytes, including the terminator.
+    unsafe {
+        RegSetKeyValueW(
+            HKEY_CURRENT_USER,
+            &HSTRING::from(key),
+            &HSTRING::from(name),
+            REG_SZ.0,
+            Some(data.as_ptr().cast()),
+            (data.len() * size_of::<u16>()) as u32,
+        )
+    }
+    .ok()?;
+    Ok(())
+}
+
+/// Progress bar shown on a toast, see [`Notification::progress`].
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub(crate) struct ToastProgress {
+    pub(crate) title: String,
+    pub(crate) value: f32,
+    pub(crate) status: String,
+}
+
+/// Values are equal if their bits are, so that notifications can be [`Eq`].
+impl PartialEq for ToastProgress {
+    fn eq(&self, other: &Self) -> bool {
+        self.title == other.title
+            && self.value.to_bits() == other.value.to_bits()
+            && self.status == other.status
+    }
+}
+
+impl Eq for ToastProgress {}
+
+impl ToastProgress {
+    /// Binds the current values to the placeholders of the `<progress>` element.
+    fn data(&self, sequence_number: u32) -> Result<NotificationData> {
+        let data = NotificationData::new()?;
+        let values = data.Values()?;
+        values.Insert(&HSTRING::from("progressTitle"), &HSTRING::from(&self.title))?;
+        values.Insert(
+            &HSTRING::from("progressValue"),
+            &HSTRING::from(self.value.clamp(0.0, 1.0).to_string()),
+        )?;
+        values.Insert(
+            &HSTRING::from("progressStatus"),
+            &HSTRING::from(&self.status),
+        )?;
+        data.SetSequenceNumber(sequence_number)?;
+        Ok(data)
+    }
+}
+
+/// How [`Notification::app_logo`] is cropped.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum IconCrop {
+    /// Shown as is.
+    #[default]
+    Square,
+    /// Cropped to a circle, e.g. for profile pictures.
+    Circular,
+}
+
+/// Tells Windows what a toast is for, see [`Notification::scenario`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum Scenario {
+    /// A regular toast.
+    #[default]
+    Default,
+    /// Stays on screen and loops an alarm sound.
+    Alarm,
+    /// Stays on screen until dismissed.
+    Reminder,
+    /// Stays on screen, expanded, and loops a ringtone.
+    IncomingCall,
+    /// Breaks through Focus Assist, Windows 11 only.
+    Urgent,
+}
+
+impl Scenario {
+    fn as_str(self) -> Option<&'static str> {
+        match self {
+            Scenario::Default => None,
+            Scenario::Alarm => Some("alarm"),
+            Scenario::Reminder => Some("reminder"),
+            Scenario::IncomingCall => Some("incomingCall"),
+            Scenario::Urgent => Some("urgent"),
+        }
+    }
+}
+
+/// Events a toast reports back to us.
+#[derive(Debug)]
+enum ToastEvent {
+    Activated {
+        arguments: String,
+        reply: Option<String>,
+    },
+    Closed(CloseReason),
+}
+
+impl ToastEvent {
+    /// Clicking the toast itself rather than one of its buttons counts as the `"default"` action.
+    fn action(&self) -> ActionResponse<'_> {
+        match self {
+            ToastEvent::Activated { arguments, .. } if arguments.is_empty() => {
+                ActionResponse::Custom(DEFAULT_ACTION)
+            }
+            ToastEvent::Activated { arguments, .. } => ActionResponse::Custom(arguments),
+            ToastEvent::Closed(reason) => ActionResponse::Closed(*reason),
+        }
+    }
+}
+
+/// A handle to a shown notification.
+///
+/// Toasts are identified by their `tag` and `group` in the Action Center,
+/// this handle keeps track of both so the toast can be updated later on.
+#[derive(Debug)]
+pub struct NotificationHandle {
+    id: u32,
+    pub(crate) tag: String,
+    pub(crate) group: String,
+    // keeps the toast alive so its event handlers keep firing, `None` if a `Capture` recorded it instead
+    shown: Option<(ToastNotification, Receiver<ToastEvent>)>,
+    sequence_number: u32,
+    notification: Notification,
+}
+
+impl NotificationHandle {
+    fn new(
+        id: u32,
+        tag: String,
+        group: String,
+        shown: Option<(ToastNotification, Receiver<ToastEvent>)>,
+        notification: Notification,
+    ) -> Self {
+        NotificationHandle {
+            id,
+            tag,
+            group,
+            shown,
+            sequence_number: 1,
+            notification,
+        }
+    }
+
+    pub(crate) fn captured(id: u32, notification: Notification) -> Self {
+        let (tag, group) = tag_and_group(&notification, id);
+        NotificationHandle::new(id, tag, group, None, notification)
+    }
+
+    /// Waits for what the toast reports next, or takes what a [`Capture`](`crate::Capture`) scripted.
+    fn next_event(
+        &self,
+        timeout: Option<Duration>,
+    ) -> std::result::Result<ToastEvent, RecvTimeoutError> {
+        match (&self.shown, timeout) {
+            (None, _) => match crate::capture::next_response() {
+                ActionResult::Action(arguments) => Ok(ToastEvent::Activated {
+                    arguments,
+                    reply: None,
+                }),
+                ActionResult::Closed(reason) => Ok(ToastEvent::Closed(reason)),
+                ActionResult::Timeout => Err(RecvTimeoutError::Timeout),
+            },
+            (Some((_, events)), Some(timeout)) => events.recv_timeout(timeout),
+            (Some((_, events)), None) => events.recv().map_err(|_| RecvTimeoutError::Disconnected),
+        }
+    }
+
+    /// Returns the id passed to [`Notification::id`], or one unique to this process.
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    /// Which API delivered the notification, for logging.
+    pub(crate) fn backend(&self) -> &'static str {
+        if self.shown.is_some() {
+            "toast"
+        } else {
+            "capture"
+        }
+    }
+
+    /// The toast's tag, unique within its group.
+    pub fn tag(&self) -> Option<&str> {
+        Some(&self.tag)
+    }
+
+    /// The toast's group.
+    pub fn group(&self) -> Option<&str> {
+        Some(&self.group)
+    }
+
+    /// Whether [`update`](`NotificationHandle::update`) can replace the shown notification on this platform.
+    pub fn supports_update(&self) -> bool {
+        true
+    }
+
+    /// Whether [`close`](`NotificationHandle::close`) can remove the shown notification on this platform.
+    pub fn supports_close(&self) -> bool {
+        true
+    }
+
+    /// Replace the original toast with an updated version.
+    pub fn update(&mut self) -> Result<()> {
+        if self.shown.is_none() {
+            crate::capture::record_update(&self.notification);
+            return Ok(());
+        }
+        let trace = crate::trace::start("update", Some(self.id), &self.notification);
+        let shown = show_toast(&self.notification, &self.tag, &self.group);
+        trace.finish(&shown);
+        self.shown = Some(shown?);
+        Ok(())
+    }
+
+    /// Updates the progress bar added by [`progress`](`Notification::progress`) in place.
+    ///
+    /// Unlike [`update`](`NotificationHandle::update`) this does not re-post the toast,
+    /// so it neither pops up again nor plays a sound.
+    pub fn update_progress(&mut self, value: f32, status: &str) -> Result<()> {
+        let progress = self
+            .notification
+            .progress
+            .as_mut()
+            .ok_or("this notification has no progress bar")?;
+        progress.value = value;
+        status.clone_into(&mut progress.status);
+        if self.shown.is_none() {
+            crate::capture::record_update(&self.notification);
+            return Ok(());
+        }
+
+        self.sequence_number += 1;
+        let data = progress.data(self.sequence_number)?;
+        notifier(&self.notification)?.UpdateWithTagAndGroup(
+            &data,
+            &HSTRING::from(&self.tag),
+            &HSTRING::from(&self.group),
+        )?;
+        Ok(())
+    }
+
+    /// Waits for the user to act upon the toast and calls `invocation_closure` with the action's identifier.
+    ///
+    /// Clicking the toast's body is reported as `"default"`,
+    /// `"__closed"` is passed if the toast was dismissed, timed out or failed to show,
+    /// just like on xdg.
+    pub fn wait_for_action<F>(self, invocation_closure: F)
+    where
+        F: FnOnce(&str),
+    {
+        if let Ok(event) = self.next_event(None) {
+            match event.action() {
+                ActionResponse::Custom(action) => invocation_closure(action),
+                ActionResponse::Closed(_reason) => invocation_closure("__closed"), // FIXME: remove backward compatibility with 5.0
+            }
+        }
+    }
+
+    /// Waits for the user to act upon the toast and returns what happened.
+    pub fn wait_for_action_result(self) -> ActionResult {
+        match self.next_event(None) {
+            Ok(event) => ActionResult::from(&event.action()),
+            Err(RecvTimeoutError::Timeout) => ActionResult::Timeout,
+            // the toast's handlers are gone, it won't report back anymore
+            Err(RecvTimeoutError::Disconnected) => ActionResult::Closed(CloseReason::CloseAction),
+        }
+    }
+
+    /// Registers a `callback` for the action with this `identifier`,
+    /// chain more of them and [`wait`](`crate::ActionCallbacks::wait`) for the user to pick one.
+    pub fn on_action<'a>(
+        self,
+        identifier: &str,
+        callback: impl FnOnce() + 'a,
+    ) -> ActionCallbacks<'a> {
+        ActionCallbacks::new(move || self.wait_for_action_result()).on_action(identifier, callback)
+    }
+
+    /// Registers a `callback` for clicking the notification itself,
+    /// see [`default_action`](`crate::Notification::default_action`).
+    pub fn on_click<'a>(self, callback: impl FnOnce() + 'a) -> ActionCallbacks<'a> {
+        self.on_action(DEFAULT_ACTION, callback)
+    }
+
+    /// Like [`wait_for_action_result`](`NotificationHandle::wait_for_action_result`),
+    /// but gives up after `timeout` and returns [`ActionResult::Timeout`].
+    pub fn wait_for_action_with_timeout(self, timeout: Duration) -> ActionResult {
+        match self.next_event(Some(timeout)) {
+            Ok(event) => ActionResult::from(&event.action()),
+            Err(RecvTimeoutError::Timeout) => ActionResult::Timeout,
+            Err(RecvTimeoutError::Disconnected) => ActionResult::Closed(CloseReason::CloseAction),
+        }
+    }
+
+    /// Waits for the toast to go away and calls `handler` if it was dismissed, timed out or failed.
+    ///
+    /// Activating the toast does not count as closing it, `handler` is not called then,
+    /// just like on xdg.
+    pub fn on_close<A>(self, handler: impl CloseHandler<A>) {
+        if let Ok(ToastEvent::Closed(reason)) = self.next_event(None) {
+            handler.call(reason);
+        }
+    }
+
+    /// Removes the toast from the screen and from the Action Center.
+    pub fn close(self) -> Result<()> {
+        if self.shown.is_none() {
+            crate::capture::record_close(&self.notification);
+            return Ok(());
+        }
+        let trace = crate::trace::start("close", Some(self.id), &self.notification);
+        let result = self.remove();
+        trace.finish(&result);
+        result
+    }
+
+    fn remove(&self) -> Result<()> {
+        if let Some((toast, _)) = &self.shown {
+            notifier(&self.notification)?.Hide(toast)?;
+        }
+        ToastNotificationManager::History()?.RemoveGroupedTagWithId(
+            &HSTRING::from(&self.tag),
+            &HSTRING::from(&self.group),
+            &HSTRING::from(app_id(&self.notification)),
+        )?;
+        Ok(())
+    }
+
+    /// Waits for the user to submit the text input added by
+    /// [`reply_input`](`Notification::reply_input`) and returns the typed text.
+    ///
+    /// Returns `None` if the toast went away without a reply,
+    /// including when it was activated through one of its buttons or its body.
+    pub fn wait_for_reply(self) -> Option<String> {
+        match self.next_event(None) {
+            Ok(ToastEvent::Activated { reply, .. }) => reply,
+            Ok(ToastEvent::Closed(_)) | Err(_) => None,
+        }
+    }
+}
+
+impl Deref for NotificationHandle {
+    type Target = Notification;
+
+    fn deref(&self) -> &Notification {
+        &self.notification
+    }
+}
+
+/// Allow to easily modify notification properties
+impl DerefMut for NotificationHandle {
+    fn deref_mut(&mut self) -> &mut Notification {
+        &mut self.notification
+    }
+}
+
+/// What `notification` asks for that toasts can't show, their icon is the [`app_logo`](`Notification::app_logo`).
+fn unsupported(notification: &Notification) -> Vec<&'static str> {
+    if notification.icon.is_empty() {
+        Vec::new()
+    } else {
+        vec!["icon"]
+    }
+}
+
+pub(crate) fn show_notification(notification: &Notification) -> Result<NotificationHandle> {
+    notification.check_supported(unsupported)?;
+    let id = notification
+        .id
+        .unwrap_or_else(|| NEXT_ID.fetch_add(1, Ordering::Relaxed));
+    let (tag, group) = tag_and_group(notification, id);
+
+    let shown = show_toast(notification, &tag, &group)?;
+    Ok(NotificationHandle::new(
+        id,
+        tag,
+        group,
+        Some(shown),
+        notification.clone(),
+    ))
+}
+
+/// The notification's [`tag`](`Notification::tag`) and [`group`](`Notification::group`), or ones unique to it.
+fn tag_and_group(notification: &Notification, id: u32) -> (String, String) {
+    let tag = notification
+        .tag
+        .clone()
+        .unwrap_or_else(|| format!("{}-{}", std::process::id(), id));
+    let group = notification
+        .group
+        .clone()
+        .unwrap_or_else(|| DEFAULT_GROUP.to_owned());
+    (tag, group)
+}
+
+fn show_toast(
+    notification: &Notification,
+    tag: &str,
+    group: &str,
+) -> Result<(ToastNotification, Receiver<ToastEvent>)> {
+    let toast_xml = XmlDocument::new()?;
+    let with_image_file = notification.with_image_file()?;
+    toast_xml.LoadXml(&HSTRING::from(toast_xml_string(&with_image_file)))?;
+
+    let toast = ToastNotification::CreateToastNotification(&toast_xml)?;
+    toast.SetTag(&HSTRING::from(tag))?;
+    toast.SetGroup(&HSTRING::from(group))?;
+
+    if let Timeout::Milliseconds(ms) = notification.timeout {
+        let expiration = SystemTime::now() + Duration::from_millis(ms.into());
+        toast.SetExpirationTime(&date_time(expiration)?)?;
+    }
+
+    if let Some(progress) = &notification.progress {
+        toast.SetData(&progress.data(1)?)?;
+    }
+
+    let (sender, events) = mpsc::channel();
+    register_handlers(&toast, sender)?;
+
+    notifier(notification)?.Show(&toast)?;
+    Ok((toast, events))
+}
+
+/// A toast windows shows at its delivery time, even if the process has exited by then.
+#[derive(Debug)]
+pub(crate) struct ScheduledToast {
+    notifier: ToastNotifier,
+    toast: ScheduledToastNotification,
+}
+
+impl ScheduledToast {
+    pub(crate) fn cancel(&self) -> Result<()> {
+        Ok(self.notifier.RemoveFromSchedule(&self.toast)?)
+    }
+}
+
+pub(crate) fn schedule_notification(
+    notification: &Notification,
+    delivery_time: SystemTime,
+) -> Result<ScheduledToast> {
+    notification.check_supported(unsupported)?;
+    let id = notification
+        .id
+        .unwrap_or_else(|| NEXT_ID.fetch_add(1, Ordering::Relaxed));
+    let toast_xml = XmlDocument::new()?;
+    let with_image_file = notification.with_image_file()?;
+    toast_xml.LoadXml(&HSTRING::from(toast_xml_string(&with_image_file)))?;
+
+    let toast = ScheduledToastNotification::CreateScheduledToastNotification(
+        &toast_xml,
+        ticks(delivery_time),
+    )?;
+    toast.SetTag(&HSTRING::from(
+        notification
+            .tag
+            .clone()
+            .unwrap_or_else(|| format!("{}-{}", std::process::id(), id)),
+    ))?;
+    toast.SetGroup(&HSTRING::from(
+        notification.group.as_deref().unwrap_or(DEFAULT_GROUP),
+    ))?;
+
+    let notifier = notifier(notification)?;
+    notifier.AddToSchedule(&toast)?;
+    Ok(ScheduledToast { notifier, toast })
+}
+
+fn notifier(notification: &Notification) -> Result<ToastNotifier> {
+    Ok(ToastNotificationManager::CreateToastNotifierWithId(
+        &HSTRING::from(app_id(notification)),
+    )?)
+}
+
+/// The explicit [`Notification::app_id`], the one passed to [`register_app`] or powershell's.
+fn app_id(notification: &Notification) -> String {
+    notification
+        .app_id
+        .clone()
+        .or_else(|| REGISTERED_APP_ID.read().ok().and_then(|id| id.clone()))
+        .unwrap_or_else(|| POWERSHELL_APP_ID.to_owned())
+}
+
+/// Converts `time` into a `DateTime`, which counts 100ns ticks since 1601.
+fn ticks(time: SystemTime) -> DateTime {
+    const SECONDS_FROM_1601_TO_1970: u64 = 11_644_473_600;
+    let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let since_1601 = since_epoch + Duration::from_secs(SECONDS_FROM_1601_TO_1970);
+    DateTime {
+        UniversalTime: (since_1601.as_nanos() / 100) as i64,
+    }
+}
+
+/// Same as [`ticks`], boxed for properties that are optional.
+fn date_time(time: SystemTime) -> Result<IReference<DateTime>> {
+    Ok(PropertyValue::CreateDateTime(ticks(time))?.cast()?)
+}
+
+/// Forwards the toast's `Activated`, `Dismissed` and `Failed` events to `sender`.
+fn register_handlers(toast: &ToastNotification, sender: Sender<ToastEvent>) -> Result<()> {
+    let activated = sender.clone();
+    toast.Activated(&TypedEventHandler::<ToastNotification, IInspectable>::new(
+        move |_, args| {
+            if let Some(args) = args
+                .as_ref()
+                .and_then(|args| args.cast::<ToastActivatedEventArgs>().ok())
+            {
+                let arguments = args
+                    .Arguments()
+                    .map(|arguments| arguments.to_string_lossy())
+                    .unwrap_or_default();
+                let reply = args
+                    .UserInput()
+                    .and_then(|input| input.Lookup(&HSTRING::from(REPLY_INPUT_ID)))
+                    .and_then(|value| value.cast::<IPropertyValue>()?.GetString())
+                    .ok()
+                    .map(|reply| reply.to_string_lossy());
+                let _ = activated.send(ToastEvent::Activated { arguments, reply });
+            }
+            Ok(())
+        },
+    ))?;
 
+    let dismissed = sender.clone();
+    toast.Dismissed(&TypedEventHandler::<
+        ToastNotification,
+        ToastDismissedEventArgs,
+    >::new(move |_, args| {
+        if let Some(args) = args.as_ref() {
+            let reason = match args.Reason()? {
+                ToastDismissalReason::UserCanceled => CloseReason::Dismissed,
+                ToastDismissalReason::ApplicationHidden => CloseReason::CloseAction,
+                ToastDismissalReason::TimedOut => CloseReason::Expired,
+                other => CloseReason::Other(other.0 as u32),
+            };
+            let _ = dismissed.send(ToastEvent::Closed(reason));
+        }
+        Ok(())
+    }))?;
+
+    toast.Failed(
+        &TypedEventHandler::<ToastNotification, ToastFailedEventArgs>::new(move |_, args| {
+            if let Some(args) = args.as_ref() {
+                let code = args.ErrorCode()?;
+                let _ = sender.send(ToastEvent::Closed(CloseReason::Other(code.0 as u32)));
+            }
+            Ok(())
+        }),
+    )?;
+    Ok(())
+}
+
+fn toast_xml_string(notification: &Notification) -> String {
     let duration = match notification.timeout {
-        Timeout::Default => winrt_notification::Duration::Short,
-        Timeout::Never => winrt_notification::Duration::Long,
+        // a looping sound plays as long as the toast is shown
+        _ if notification.sound_loop || notification.priority == Priority::High => "long",
+        Timeout::Default => "short",
+        Timeout::Never => "long",
         Timeout::Milliseconds(t) => {
             if t >= 25000 {
-                winrt_notification::Duration::Long
+                "long"
             } else {
-                winrt_notification::Duration::Short
+                "short"
             }
         }
     };
 
-    let powershell_app_id = &Toast::POWERSHELL_APP_ID.to_string();
-    let app_id = &notification.app_id.as_ref().unwrap_or(powershell_app_id);
-    let mut toast = Toast::new(app_id)
-        .title(&notification.summary)
-        .text1(notification.subtitle.as_ref().map_or("", AsRef::as_ref)) // subtitle
-        .text2(&notification.body)
-        .sound(sound)
-        .duration(duration);
+    let mut xml = String::new();
+    let _ = write!(xml, r#"<toast duration="{}""#, duration);
+    let scenario = match notification.scenario {
+        Scenario::Default if notification.priority == Priority::High => Scenario::Urgent,
+        scenario => scenario,
+    };
+    if let Some(scenario) = scenario.as_str() {
+        let _ = write!(xml, r#" scenario="{}""#, scenario);
+    }
+    xml.push('>');
+    xml.push_str(r#"<visual><binding template="ToastGeneric">"#);
+    let _ = write!(xml, "<text>{}</text>", escape(&notification.summary));
+    if let Some(subtitle) = &notification.subtitle {
+        let _ = write!(xml, "<text>{}</text>", escape(subtitle));
+    }
+    if !notification.body.is_empty() {
+        let _ = write!(xml, "<text>{}</text>", escape(&notification.body));
+    }
     if let Some(image_path) = &notification.path_to_image {
-        toast = toast.image(Path::new(&image_path), "");
+        let _ = write!(
+            xml,
+            r#"<image src="file:///{}" alt="" />"#,
+            escape(image_path)
+        );
+    }
+    if let Some(image_path) = &notification.hero_image {
+        let _ = write!(
+            xml,
+            r#"<image placement="hero" src="file:///{}" alt="" />"#,
+            escape(image_path)
+        );
+    }
+    if let Some((image_path, crop)) = &notification.app_logo {
+        let crop = match crop {
+            IconCrop::Square => "none",
+            IconCrop::Circular => "circle",
+        };
+        let _ = write!(
+            xml,
+            r#"<image placement="appLogoOverride" hint-crop="{}" src="file:///{}" alt="" />"#,
+            crop,
+            escape(image_path)
+        );
+    }
+    if let Some(attribution) = &notification.attribution {
+        let _ = write!(
+            xml,
+            r#"<text placement="attribution">{}</text>"#,
+            escape(attribution)
+        );
+    }
+    if notification.progress.is_some() {
+        xml.push_str(
+            r#"<progress title="{progressTitle}" value="{progressValue}" status="{progressStatus}" />"#,
+        );
+    }
+    xml.push_str("</binding></visual>");
+    xml.push_str(&actions_xml(
+        &notification.actions,
+        notification.reply_placeholder.as_deref(),
+    ));
+    xml.push_str(&audio_xml(notification));
+    xml.push_str("</toast>");
+    xml
+}
+
+/// Maps the actions onto `<action>` buttons.
+///
+/// The identifier is passed back as the activation argument once the button is clicked.
+/// Like on xdg, the `"default"` action is invoked by clicking the toast itself and gets no button.
+/// A reply input comes with its own send button.
+fn actions_xml(actions: &[Action], reply_placeholder: Option<&str>) -> String {
+    let buttons = actions
+        .iter()
+        .filter(|action| action.identifier != DEFAULT_ACTION)
+        .collect::<Vec<_>>();
+    if buttons.is_empty() && reply_placeholder.is_none() {
+        return String::new();
+    }
+    let mut xml = String::from("<actions>");
+    if let Some(placeholder) = reply_placeholder {
+        let _ = write!(
+            xml,
+            r#"<input id="{id}" type="text" placeHolderContent="{}" />"#,
+            escape(placeholder),
+            id = REPLY_INPUT_ID
+        );
+        let _ = write!(
+            xml,
+            r#"<action content="Send" arguments="{id}" hint-inputId="{id}" activationType="foreground" />"#,
+            id = REPLY_INPUT_ID
+        );
+    }
+    for action in buttons {
+        let _ = write!(
+            xml,
+            r#"<action content="{}" arguments="{}" activationType="foreground" />"#,
+            escape(&action.label),
+            escape(&action.identifier)
+        );
+    }
+    xml.push_str("</actions>");
+    xml
+}
+
+/// Maps the `sound_name` onto the toast's `<audio>` element.
+///
+/// Toasts without a (valid) sound name stay silent, `"Default"` plays the system default sound.
+/// Alarms and incoming calls without a sound name keep the looping sound of their scenario.
+fn audio_xml(notification: &Notification) -> String {
+    const SILENT: &str = r#"<audio silent="true" />"#;
+    let scenario = notification.scenario;
+    let src = match notification.sound_name.as_deref() {
+        _ if notification.silent => return SILENT.to_owned(),
+        Some("Default") => None,
+        None if matches!(scenario, Scenario::Alarm | Scenario::IncomingCall) => None,
+        Some(name @ ("IM" | "Mail" | "Reminder" | "SMS")) => {
+            Some(format!("ms-winsoundevent:Notification.{}", name))
+        }
+        Some(name) if is_loopable_sound(name) => {
+            Some(format!("ms-winsoundevent:Notification.Looping.{}", name))
+        }
+        _ => return SILENT.to_owned(),
+    };
+    let looping = if notification.sound_loop {
+        r#" loop="true""#
+    } else {
+        ""
+    };
+    match src {
+        Some(src) => format!(r#"<audio src="{}"{} />"#, src, looping),
+        None if notification.sound_loop => format!("<audio{} />", looping),
+        None => String::new(),
+    }
+}
+
+/// `Alarm`, `Alarm2` … `Alarm10` and `Call`, `Call2` … `Call10`
+fn is_loopable_sound(name: &str) -> bool {
+    let number = name
+        .strip_prefix("Alarm")
+        .or_else(|| name.strip_prefix("Call"));
+    match number {
+        Some("") => true,
+        Some(n) => matches!(n.parse::<u8>(), Ok(2..=10)) && !n.starts_with('0'),
+        None => false,
+    }
+}
+
+#[cfg(all(test, target_os = "windows"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escapes_text_and_attributes() {
+        assert_eq!(escape("plain"), "plain");
+        assert_eq!(
+            escape(r#"<b>"Tom" & 'Jerry'</b>"#),
+            "&lt;b&gt;&quot;Tom&quot; &amp; &apos;Jerry&apos;&lt;/b&gt;"
+        );
+
+        let xml = toast_xml_string(
+            Notification::new()
+                .summary("1 < 2")
+                .body("fish & chips")
+                .action("a&b", "\"quoted\""),
+        );
+        assert!(xml.contains("<text>1 &lt; 2</text>"));
+        assert!(xml.contains("<text>fish &amp; chips</text>"));
+        assert!(xml.contains(r#"content="&quot;quoted&quot;" arguments="a&amp;b""#));
     }
 
-    toast
-        .show()
-        .map_err(|e| Error::from(ErrorKind::Msg(format!("{:?}", e))))
+    #[test]
+    fn default_action_gets_no_button() {
+        let xml = actions_xml(
+            &[
+                Action::new("default", "Open"),
+                Action::new("snooze", "Snooze"),
+            ],
+            None,
+        );
+        assert!(!xml.contains(r#"arguments="default""#));
+        assert!(xml.contains(r#"content="Snooze" arguments="snooze""#));
+
+        let only_default = [Action::new("default", "Open")];
+        assert_eq!(actions_xml(&only_default, None), "");
+    }
+
+    #[test]
+    fn reply_input_comes_with_send_button() {
+        let xml = actions_xml(&[], Some("Type a reply"));
+        assert!(
+            xml.contains(r#"<input id="reply" type="text" placeHolderContent="Type a reply" />"#)
+        );
+        assert!(xml.contains(r#"arguments="reply" hint-inputId="reply""#));
+    }
+
+    #[test]
+    fn scenario_attribute() {
+        let xml = toast_xml_string(Notification::new().scenario(Scenario::Alarm));
+        assert!(xml.starts_with(r#"<toast duration="short" scenario="alarm">"#));
+
+        let xml = toast_xml_string(Notification::new().priority(Priority::High));
+        assert!(xml.starts_with(r#"<toast duration="long" scenario="urgent">"#));
+
+        let xml = toast_xml_string(&Notification::new());
+        assert!(xml.starts_with(r#"<toast duration="short">"#));
+    }
+
+    #[test]
+    fn audio() {
+        assert_eq!(
+            audio_xml(&Notification::new()),
+            r#"<audio silent="true" />"#
+        );
+        assert_eq!(audio_xml(Notification::new().sound_name("Default")), "");
+        assert_eq!(
+            audio_xml(Notification::new().sound_name("Mail")),
+            r#"<audio src="ms-winsoundevent:Notification.Mail" />"#
+        );
+        assert_eq!(
+            audio_xml(Notification::new().sound_name("Mail").silent(true)),
+            r#"<audio silent="true" />"#
+        );
+        assert_eq!(
+            audio_xml(Notification::new().sound_name("Alarm3").sound_loop(true)),
+            r#"<audio src="ms-winsoundevent:Notification.Looping.Alarm3" loop="true" />"#
+        );
+        assert_eq!(audio_xml(Notification::new().scenario(Scenario::Alarm)), "");
+        assert_eq!(
+            audio_xml(
+                Notification::new()
+                    .scenario(Scenario::IncomingCall)
+                    .sound_loop(true)
+            ),
+            r#"<audio loop="true" />"#
+        );
+    }
+
+    #[test]
+    fn loopable_sounds() {
+        assert!(is_loopable_sound("Alarm"));
+        assert!(is_loopable_sound("Alarm2"));
+        assert!(is_loopable_sound("Alarm10"));
+        assert!(is_loopable_sound("Call"));
+        assert!(is_loopable_sound("Call7"));
+
+        assert!(!is_loopable_sound("Alarm1"));
+        assert!(!is_loopable_sound("Alarm11"));
+        assert!(!is_loopable_sound("Alarm02"));
+        assert!(!is_loopable_sound("Call0"));
+        assert!(!is_loopable_sound("Alarms"));
+        assert!(!is_loopable_sound("Mail"));
+    }
 }
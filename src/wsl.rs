@@ -0,0 +1,200 @@
+//! Shows notifications on the windows host when running inside WSL,
+//! which usually has no notification server of its own.
+//!
+//! Toasts are handed to `powershell.exe` through the WSL interop,
+//! so there is nothing to install on either side.
+
+use crate::{
+    action::{ActionResponse, ActionResponseHandler, CloseReason},
+    error::*,
+    notification::Notification,
+    timeout::Timeout,
+    xml::escape,
+};
+
+use std::{
+    fmt::Write as _,
+    io::Write as _,
+    process::{Command, Stdio},
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+/// Toasts shown by powershell are attributed to it, there is no way to register our own app from inside WSL.
+const POWERSHELL_APP_ID: &str =
+    "{1AC14E77-02E7-4E5D-B744-2EB1AE5198B7}\\WindowsPowerShell\\v1.0\\powershell.exe";
+
+const GROUP: &str = "notify-rust";
+
+/// Loads the `WinRT` types, which powershell doesn't do by itself.
+const PRELUDE: &str = "$ErrorActionPreference = 'Stop'
+$null = [Windows.UI.Notifications.ToastNotificationManager, Windows.UI.Notifications, ContentType = WindowsRuntime]
+$null = [Windows.Data.Xml.Dom.XmlDocument, Windows.Data.Xml.Dom.XmlDocument, ContentType = WindowsRuntime]
+";
+
+/// Ids for bridged notifications that don't set their own [`Notification::id`].
+static NEXT_ID: AtomicU32 = AtomicU32::new(1);
+
+/// Whether we are running inside the Windows Subsystem for Linux.
+pub(crate) fn detected() -> bool {
+    std::env::var_os("WSL_DISTRO_NAME").is_some()
+        || std::fs::read_to_string("/proc/sys/kernel/osrelease").map_or(false, |release| {
+            release.to_lowercase().contains("microsoft")
+        })
+}
+
+/// Shows `notification` as a toast on the windows host if we are inside WSL,
+/// otherwise passes on the `error` it failed to show with.
+pub(crate) fn bridge(notification: &Notification, error: Error) -> Result<WslNotificationHandle> {
+    if !detected() {
+        return Err(error);
+    }
+    let id = notification
+        .id
+        .unwrap_or_else(|| NEXT_ID.fetch_add(1, Ordering::Relaxed));
+    let handle = WslNotificationHandle {
+        id,
+        notification: notification.clone(),
+    };
+    handle.show()?;
+    Ok(handle)
+}
+
+/// A notification that was handed to the windows host.
+///
+/// Windows keeps actions and dismissals to itself, so all we can do is replace or remove the toast.
+#[derive(Debug)]
+pub(crate) struct WslNotificationHandle {
+    pub(crate) id: u32,
+    pub(crate) notification: Notification,
+}
+
+impl WslNotificationHandle {
+    pub fn wait_for_action(self, invocation_closure: impl ActionResponseHandler) {
+        invocation_closure.call(&ActionResponse::Closed(CloseReason::Expired));
+    }
+
+    /// Shows the toast again under the same tag, which replaces the old one.
+    pub fn update(&mut self) -> Result<()> {
+        self.show()
+    }
+
+    pub fn close(self) -> Result<()> {
+        run_powershell(&format!(
+            "[Windows.UI.Notifications.ToastNotificationManager]::History.Remove('{}', '{}', '{}')",
+            self.tag(),
+            GROUP,
+            POWERSHELL_APP_ID
+        ))
+    }
+
+    /// The notification's [`tag`](`Notification::tag`) or one unique to it, with `'` escaped for powershell.
+    fn tag(&self) -> String {
+        match &self.notification.tag {
+            Some(tag) => tag.replace('\'', "''"),
+            None => format!("{}-{}", std::process::id(), self.id),
+        }
+    }
+
+    fn show(&self) -> Result<()> {
+        run_powershell(&show_script(&self.notification, &self.tag()))
+    }
+}
+
+fn run_powershell(script: &str) -> Result<()> {
+    let mut child = Command::new("powershell.exe")
+        .args(["-NoProfile", "-NonInteractive", "-Command", "-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|error| Error::from(format!("can't run powershell.exe: {}", error).as_str()))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(PRELUDE.as_bytes())
+            .and_then(|()| stdin.write_all(script.as_bytes()))
+            .map_err(|error| Error::from(error.to_string().as_str()))?;
+    }
+    let output = child
+        .wait_with_output()
+        .map_err(|error| Error::from(error.to_string().as_str()))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        Err(Error::from(
+            format!("powershell.exe failed: {}", stderr.trim()).as_str(),
+        ))
+    }
+}
+
+/// The toast XML is escaped, so it contains no `'` that could end the powershell string early.
+fn show_script(notification: &Notification, tag: &str) -> String {
+    format!(
+        "$xml = New-Object Windows.Data.Xml.Dom.XmlDocument
+$xml.LoadXml('{}')
+$toast = New-Object Windows.UI.Notifications.ToastNotification $xml
+$toast.Tag = '{}'
+$toast.Group = '{}'
+[Windows.UI.Notifications.ToastNotificationManager]::CreateToastNotifier('{}').Show($toast)
+",
+        toast_xml_string(notification),
+        tag,
+        GROUP,
+        POWERSHELL_APP_ID
+    )
+}
+
+fn toast_xml_string(notification: &Notification) -> String {
+    let duration = match notification.timeout {
+        Timeout::Never => "long",
+        Timeout::Milliseconds(t) if t >= 25000 => "long",
+        _ => "short",
+    };
+
+    let mut xml = String::new();
+    let _ = write!(xml, r#"<toast duration="{}">"#, duration);
+    xml.push_str(r#"<visual><binding template="ToastGeneric">"#);
+    let _ = write!(xml, "<text>{}</text>", escape(&notification.summary));
+    if !notification.body.is_empty() {
+        let _ = write!(xml, "<text>{}</text>", escape(&notification.body));
+    }
+    if !notification.appname.is_empty() {
+        let _ = write!(
+            xml,
+            r#"<text placement="attribution">{}</text>"#,
+            escape(&notification.appname)
+        );
+    }
+    xml.push_str("</binding></visual></toast>");
+    xml
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toast_xml() {
+        let xml = toast_xml_string(
+            Notification::new()
+                .appname("mail")
+                .summary("it's 1 < 2")
+                .body("fish & chips"),
+        );
+        assert_eq!(
+            xml,
+            r#"<toast duration="short"><visual><binding template="ToastGeneric"><text>it&apos;s 1 &lt; 2</text><text>fish &amp; chips</text><text placement="attribution">mail</text></binding></visual></toast>"#
+        );
+
+        let xml = toast_xml_string(Notification::new().appname("").timeout(Timeout::Never));
+        assert!(xml.starts_with(r#"<toast duration="long">"#));
+        assert!(!xml.contains("attribution"));
+    }
+
+    #[test]
+    fn script_quotes_only_its_own_strings() {
+        let script = show_script(Notification::new().summary("don't"), "1-2");
+        assert_eq!(script.matches('\'').count(), 8);
+        assert!(script.contains("$toast.Tag = '1-2'"));
+    }
+}
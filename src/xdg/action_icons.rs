@@ -0,0 +1,94 @@
+//! Servers with `"action-icons"` take action identifiers as icon names, see
+//! [`Notification::action_with_icon`](`crate::Notification::action_with_icon`).
+//! Those icon names are sent in place of the identifiers and translated back here.
+#![cfg_attr(not(any(feature = "dbus", feature = "zbus")), allow(dead_code))]
+
+use crate::{action::ActionResult, notification::Notification};
+
+use std::{collections::HashMap, sync::RwLock};
+
+/// Identifiers by icon name, for every shown notification that has icon actions.
+static SHOWN: RwLock<Option<HashMap<u32, HashMap<String, String>>>> = RwLock::new(None);
+
+/// Remembers the icon actions of the notification just shown or updated with `id`.
+pub(crate) fn remember(id: u32, notification: &Notification) {
+    if let Ok(mut shown) = SHOWN.write() {
+        let shown = shown.get_or_insert_with(HashMap::new);
+        let icons = notification
+            .actions
+            .iter()
+            .filter(|_| notification.uses_action_icons())
+            .filter_map(|action| Some((action.icon.clone()?, action.identifier.clone())))
+            .collect::<HashMap<_, _>>();
+        if icons.is_empty() {
+            shown.remove(&id);
+        } else {
+            shown.insert(id, icons);
+        }
+    }
+}
+
+/// Turns the icon name an action was invoked by back into its identifier,
+/// and forgets about notifications once they're closed.
+pub(crate) fn translate(id: u32, result: ActionResult) -> ActionResult {
+    let mut shown = match SHOWN.write() {
+        Ok(shown) => shown,
+        Err(_) => return result,
+    };
+    let shown = match shown.as_mut() {
+        Some(shown) => shown,
+        None => return result,
+    };
+    match result {
+        ActionResult::Action(action) => ActionResult::Action(
+            shown
+                .get(&id)
+                .and_then(|icons| icons.get(&action))
+                .cloned()
+                .unwrap_or(action),
+        ),
+        ActionResult::Closed(reason) => {
+            shown.remove(&id);
+            ActionResult::Closed(reason)
+        }
+        ActionResult::Timeout => ActionResult::Timeout,
+    }
+}
+
+#[cfg(all(test, any(feature = "dbus", feature = "zbus")))]
+mod tests {
+    use super::*;
+    use crate::{
+        action::{Action, CloseReason},
+        Hint,
+    };
+
+    #[test]
+    fn translates_until_closed() {
+        let id = u32::MAX - 78;
+        let mut notification = Notification::new();
+        notification
+            .hint(Hint::ActionIcons(true))
+            .add_action(Action::with_icon("pause", "Pause", "media-playback-pause"));
+        assert_eq!(
+            notification.actions_to_pairs(),
+            ["media-playback-pause", "Pause"]
+        );
+        remember(id, &notification);
+
+        let invoked = |action: &str| translate(id, ActionResult::Action(action.into()));
+        assert_eq!(
+            invoked("media-playback-pause"),
+            ActionResult::Action("pause".into())
+        );
+        assert_eq!(invoked("default"), ActionResult::Action("default".into()));
+        assert_eq!(
+            translate(id, ActionResult::Closed(CloseReason::Dismissed)),
+            ActionResult::Closed(CloseReason::Dismissed)
+        );
+        assert_eq!(
+            invoked("media-playback-pause"),
+            ActionResult::Action("media-playback-pause".into())
+        );
+    }
+}
@@ -0,0 +1,269 @@
+//! Degrades the body for servers that can't show markup or links, see [`set_adapt_body`].
+#![cfg_attr(not(any(feature = "dbus", feature = "zbus")), allow(dead_code))]
+
+use super::{Capability, ServerCapabilities};
+use crate::notification::Notification;
+
+use std::{
+    borrow::Cow,
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Makes [`Notification::show`](`crate::Notification::show`) fit the body to what the notification server can show,
+/// instead of raw `<b>` tags showing up on servers like dunst that don't render markup.
+///
+/// The server's capabilities are queried once, before the first notification is shown, then
+/// * without `"body-markup"` all tags are stripped and entities like `&amp;` are unescaped,
+/// * without `"body-hyperlinks"` links are replaced by their text,
+/// * without `"body"` the body is truncated to its first line, which is appended to the summary.
+///
+/// Off by default. (XDG only)
+pub fn set_adapt_body(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Asks the server for its capabilities, unless that happened before or nothing depends on them:
+/// adapting is off and `notification` has neither a body to build markup from,
+/// nor a reply input or actions with icons.
+#[cfg(any(feature = "dbus", feature = "zbus"))]
+pub(crate) fn query_capabilities_if_needed(notification: &Notification) {
+    if ENABLED.load(Ordering::Relaxed)
+        || notification.markup().is_some()
+        || notification.reply_placeholder.is_some()
+        || notification
+            .actions
+            .iter()
+            .any(|action| action.icon.is_some())
+    {
+        super::capabilities::queried();
+    }
+}
+
+/// `summary` and `body` of `notification` as the server should get them.
+#[cfg(any(feature = "dbus", feature = "zbus"))]
+pub(crate) fn adapt(notification: &Notification) -> (Cow<'_, str>, Cow<'_, str>) {
+    let capabilities = super::capabilities::known();
+    let (body, is_markup) = body_for(notification, capabilities.as_ref());
+    match capabilities {
+        Some(capabilities) if ENABLED.load(Ordering::Relaxed) => {
+            adapt_to(&capabilities, &notification.summary, body, is_markup)
+        }
+        _ => (Cow::Borrowed(notification.summary.as_str()), body),
+    }
+}
+
+/// The body as markup if it was built from pieces and the server renders markup, as plain text if it wasn't.
+/// Whether it is markup comes with it, a body set as a string may be either.
+fn body_for<'a>(
+    notification: &'a Notification,
+    capabilities: Option<&ServerCapabilities>,
+) -> (Cow<'a, str>, bool) {
+    match (notification.markup(), capabilities) {
+        (Some(markup), Some(capabilities)) if capabilities.supports(Capability::BodyMarkup) => {
+            let hyperlinks = capabilities.supports(Capability::BodyHyperlinks);
+            (Cow::Owned(markup.to_markup(hyperlinks)), true)
+        }
+        (Some(markup), _) => (
+            Cow::Borrowed(notification.body.as_str()),
+            markup.has_markup(),
+        ),
+        (None, _) => (Cow::Borrowed(notification.body.as_str()), true),
+    }
+}
+
+fn adapt_to<'a>(
+    capabilities: &ServerCapabilities,
+    summary: &'a str,
+    mut body: Cow<'a, str>,
+    is_markup: bool,
+) -> (Cow<'a, str>, Cow<'a, str>) {
+    if is_markup && !capabilities.supports(Capability::BodyMarkup) {
+        if body.contains(['<', '&']) {
+            body = Cow::Owned(unescape(&strip_tags(&body, |_| true)));
+        }
+    } else if is_markup && !capabilities.supports(Capability::BodyHyperlinks) && body.contains('<')
+    {
+        body = Cow::Owned(strip_tags(&body, is_link));
+    }
+
+    if capabilities.supports(Capability::Body) {
+        return (Cow::Borrowed(summary), body);
+    }
+    // the summary shows no markup
+    let first_line = body
+        .lines()
+        .map(|line| {
+            if is_markup {
+                unescape(&strip_tags(line, |_| true)).trim().to_owned()
+            } else {
+                line.trim().to_owned()
+            }
+        })
+        .find(|line| !line.is_empty());
+    let summary = match first_line {
+        Some(first_line) if summary.is_empty() => Cow::Owned(first_line),
+        Some(first_line) => Cow::Owned(format!("{}: {}", summary, first_line)),
+        None => Cow::Borrowed(summary),
+    };
+    (summary, Cow::Borrowed(""))
+}
+
+/// Removes every tag `strip` picks, an unclosed `<` is kept as it is.
+fn strip_tags(body: &str, strip: impl Fn(&str) -> bool) -> String {
+    let mut stripped = String::with_capacity(body.len());
+    let mut rest = body;
+    while let Some(start) = rest.find('<') {
+        let end = match rest[start..].find('>') {
+            Some(end) => start + end + 1,
+            None => break,
+        };
+        stripped.push_str(&rest[..start]);
+        let tag = &rest[start..end];
+        if !strip(tag) {
+            stripped.push_str(tag);
+        }
+        rest = &rest[end..];
+    }
+    stripped.push_str(rest);
+    stripped
+}
+
+/// `<a href="…">` and `</a>`
+fn is_link(tag: &str) -> bool {
+    tag[1..]
+        .trim_start_matches('/')
+        .split(|c: char| c.is_whitespace() || c == '>' || c == '/')
+        .next()
+        .map_or(false, |name| name.eq_ignore_ascii_case("a"))
+}
+
+fn unescape(body: &str) -> String {
+    body.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&#39;", "'")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn capabilities(names: &[&str]) -> ServerCapabilities {
+        names
+            .iter()
+            .map(|name| name.to_string())
+            .collect::<Vec<_>>()
+            .into()
+    }
+
+    #[test]
+    fn strips_markup() {
+        let (summary, body) = adapt_to(
+            &capabilities(&["body"]),
+            "mail",
+            "<b>Tom &amp; Jerry</b> wrote <a href=\"https://example.com\">this</a> <3".into(),
+            true,
+        );
+        assert_eq!(summary, "mail");
+        assert_eq!(body, "Tom & Jerry wrote this <3");
+    }
+
+    #[test]
+    fn drops_hyperlinks() {
+        let (_, body) = adapt_to(
+            &capabilities(&["body", "body-markup"]),
+            "mail",
+            "<b>new</b> <A HREF=\"https://example.com\">mail</A>".into(),
+            true,
+        );
+        assert_eq!(body, "<b>new</b> mail");
+    }
+
+    #[test]
+    fn leaves_capable_servers_alone() {
+        let (_, body) = adapt_to(
+            &capabilities(&["body", "body-markup", "body-hyperlinks"]),
+            "mail",
+            "<a href=\"https://example.com\">mail</a>".into(),
+            true,
+        );
+        assert!(matches!(body, Cow::Borrowed(_)));
+    }
+
+    #[test]
+    fn truncates_into_summary() {
+        let (summary, body) = adapt_to(
+            &capabilities(&["body-markup"]),
+            "backup",
+            "\n<i>finished</i>\n42 files".into(),
+            true,
+        );
+        assert_eq!(summary, "backup: finished");
+        assert_eq!(body, "");
+    }
+
+    #[test]
+    fn escapes_text_for_markup_servers_only() {
+        let mut notification = Notification::new();
+        notification.body_text("Tom & Jerry <3");
+        let markup = capabilities(&["body", "body-markup"]);
+        assert_eq!(
+            body_for(&notification, Some(&markup)),
+            (Cow::Borrowed("Tom &amp; Jerry &lt;3"), true)
+        );
+        assert_eq!(
+            body_for(&notification, Some(&capabilities(&["body"]))),
+            (Cow::Borrowed("Tom & Jerry <3"), false)
+        );
+        assert_eq!(
+            adapt_to(
+                &capabilities(&["body"]),
+                "mail",
+                "Tom &amp; Jerry".into(),
+                false
+            )
+            .1,
+            "Tom &amp; Jerry"
+        );
+
+        notification.body("<b>bold</b>");
+        assert_eq!(
+            body_for(&notification, Some(&markup)),
+            (Cow::Borrowed("<b>bold</b>"), true)
+        );
+    }
+
+    #[test]
+    fn builds_links_when_showing() {
+        let mut notification = Notification::new();
+        notification
+            .body("<b>build</b> failed")
+            .hyperlink("https://ci.example.com/?build=42&log", "the log");
+        assert_eq!(
+            notification.body,
+            "<b>build</b> failed https://ci.example.com/?build=42&log"
+        );
+        assert_eq!(
+            body_for(
+                &notification,
+                Some(&capabilities(&["body", "body-markup", "body-hyperlinks"]))
+            ),
+            (
+                Cow::Borrowed(
+                    "<b>build</b> failed <a href=\"https://ci.example.com/?build=42&amp;log\">the log</a>"
+                ),
+                true
+            )
+        );
+        assert_eq!(
+            body_for(&notification, Some(&capabilities(&["body", "body-markup"]))).0,
+            "<b>build</b> failed https://ci.example.com/?build=42&amp;log"
+        );
+        // the hand written markup still needs stripping
+        assert!(body_for(&notification, Some(&capabilities(&["body"]))).1);
+    }
+}
@@ -1,4 +1,5 @@
-use crate::xdg::NOTIFICATION_DEFAULT_BUS;
+#[cfg(any(feature = "dbus", feature = "zbus"))]
+use crate::xdg::{namespace, NOTIFICATION_DEFAULT_BUS};
 
 fn skip_first_slash(s: &str) -> &str {
     if let Some('/') = s.chars().next() {
@@ -12,28 +13,26 @@ use std::path::PathBuf;
 
 type BusNameType = std::borrow::Cow<'static, str>;
 
-#[derive(Clone, Debug)]
-pub struct NotificationBus(BusNameType);
+/// `None` stands for the bus name of the [namespace](`crate::set_dbus_namespace`) at the time of sending.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct NotificationBus(Option<BusNameType>);
 
-impl Default for NotificationBus {
-    #[cfg(feature = "zbus")]
-    fn default() -> Self {
-        Self(
-            zbus::names::WellKnownName::from_static_str(NOTIFICATION_DEFAULT_BUS)
-                .unwrap()
-                .to_string()
-                .into(),
-        )
+/// The bus name of the current namespace, the default one if that isn't a valid bus name.
+#[cfg(feature = "zbus")]
+fn namespace_name() -> BusNameType {
+    let name = namespace::bus_name();
+    match zbus::names::WellKnownName::try_from(name.as_str()) {
+        Ok(_) => name.into(),
+        Err(_) => NOTIFICATION_DEFAULT_BUS.into(),
     }
+}
 
-    #[cfg(all(feature = "dbus", not(feature = "zbus")))]
-    fn default() -> Self {
-        Self(
-            dbus::strings::BusName::from_slice(NOTIFICATION_DEFAULT_BUS)
-                .unwrap()
-                .to_string()
-                .into(),
-        )
+#[cfg(all(feature = "dbus", not(feature = "zbus")))]
+fn namespace_name() -> BusNameType {
+    let name = namespace::bus_name();
+    match dbus::strings::BusName::new(name.as_str()) {
+        Ok(_) => name.into(),
+        Err(_) => NOTIFICATION_DEFAULT_BUS.into(),
     }
 }
 
@@ -53,16 +52,22 @@ impl NotificationBus {
     pub fn custom(custom_path: &str) -> Option<Self> {
         let name =
             zbus::names::WellKnownName::try_from(Self::namespaced_custom(custom_path)?).ok()?;
-        Some(Self(name.to_string().into()))
+        Some(Self(Some(name.to_string().into())))
     }
 
     #[cfg(all(feature = "dbus", not(feature = "zbus")))]
     pub fn custom(custom_path: &str) -> Option<Self> {
         let name = dbus::strings::BusName::new(Self::namespaced_custom(custom_path)?).ok()?;
-        Some(Self(name.to_string().into()))
+        Some(Self(Some(name.to_string().into())))
+    }
+
+    #[cfg(not(any(feature = "dbus", feature = "zbus")))]
+    pub fn custom(custom_path: &str) -> Option<Self> {
+        Some(Self(Some(Self::namespaced_custom(custom_path)?.into())))
     }
 
+    #[cfg(any(feature = "dbus", feature = "zbus"))]
     pub fn into_name(self) -> BusNameType {
-        self.0
+        self.0.unwrap_or_else(namespace_name)
     }
 }
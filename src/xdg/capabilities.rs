@@ -0,0 +1,184 @@
+//! What a notification server can do, see [`get_capabilities`](`crate::get_capabilities`).
+
+use std::{fmt, ops::Deref, sync::RwLock};
+
+/// What the running server said it can do, see [`queried`].
+static QUERIED: RwLock<Option<ServerCapabilities>> = RwLock::new(None);
+
+/// An optional feature of the notification server.
+///
+/// ## Specification
+/// As listed under [Table 4. Server Capabilities](https://specifications.freedesktop.org/notification-spec/latest/protocol.html#command-get-capabilities)
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum Capability {
+    /// `"action-icons"`, action identifiers are shown as icons instead of labels.
+    ActionIcons,
+    /// `"actions"`, actions are shown and can be invoked.
+    Actions,
+    /// `"body"`, the body is shown.
+    Body,
+    /// `"body-hyperlinks"`, links in the body can be clicked.
+    BodyHyperlinks,
+    /// `"body-images"`, images in the body are shown.
+    BodyImages,
+    /// `"body-markup"`, markup like `<b>` in the body is rendered.
+    BodyMarkup,
+    /// `"icon-multi"`, several frames of an icon are shown as an animation.
+    IconMulti,
+    /// `"icon-static"`, only a single frame of an icon is shown.
+    IconStatic,
+    /// `"persistence"`, notifications are kept until the user removes them.
+    Persistence,
+    /// `"sound"`, sounds are played.
+    Sound,
+    /// `"inline-reply"`, a KDE extension, notifications can have a text input,
+    /// see [`reply_input`](`crate::Notification::reply_input`).
+    InlineReply,
+    /// Anything not in the specification, usually prefixed with `x-vendor-`.
+    Other(String),
+}
+
+impl Capability {
+    /// The name the server uses for this capability.
+    pub fn as_str(&self) -> &str {
+        match self {
+            Capability::ActionIcons => "action-icons",
+            Capability::Actions => "actions",
+            Capability::Body => "body",
+            Capability::BodyHyperlinks => "body-hyperlinks",
+            Capability::BodyImages => "body-images",
+            Capability::BodyMarkup => "body-markup",
+            Capability::IconMulti => "icon-multi",
+            Capability::IconStatic => "icon-static",
+            Capability::Persistence => "persistence",
+            Capability::Sound => "sound",
+            Capability::InlineReply => "inline-reply",
+            Capability::Other(other) => other,
+        }
+    }
+}
+
+impl From<&str> for Capability {
+    fn from(name: &str) -> Self {
+        match name {
+            "action-icons" => Capability::ActionIcons,
+            "actions" => Capability::Actions,
+            "body" => Capability::Body,
+            "body-hyperlinks" => Capability::BodyHyperlinks,
+            "body-images" => Capability::BodyImages,
+            "body-markup" => Capability::BodyMarkup,
+            "icon-multi" => Capability::IconMulti,
+            "icon-static" => Capability::IconStatic,
+            "persistence" => Capability::Persistence,
+            "sound" => Capability::Sound,
+            "inline-reply" => Capability::InlineReply,
+            other => Capability::Other(other.to_owned()),
+        }
+    }
+}
+
+impl fmt::Display for Capability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Return value of [`get_capabilities`](`crate::get_capabilities`),
+/// derefs to a slice of [`Capability`].
+///
+/// ```no_run
+/// # use notify_rust::*;
+/// if get_capabilities()?.supports(Capability::BodyMarkup) {
+///     Notification::new().body("<b>bold</b> move").show()?;
+/// }
+/// # Ok::<(), notify_rust::error::Error>(())
+/// ```
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ServerCapabilities(Vec<Capability>);
+
+impl ServerCapabilities {
+    /// Whether the server advertises `capability`.
+    pub fn supports(&self, capability: Capability) -> bool {
+        self.0.contains(&capability)
+    }
+}
+
+impl Deref for ServerCapabilities {
+    type Target = [Capability];
+
+    fn deref(&self) -> &[Capability] {
+        &self.0
+    }
+}
+
+impl From<Vec<String>> for ServerCapabilities {
+    fn from(names: Vec<String>) -> Self {
+        ServerCapabilities(
+            names
+                .iter()
+                .map(|name| Capability::from(name.as_str()))
+                .collect(),
+        )
+    }
+}
+
+impl From<ServerCapabilities> for Vec<Capability> {
+    fn from(capabilities: ServerCapabilities) -> Self {
+        capabilities.0
+    }
+}
+
+impl IntoIterator for ServerCapabilities {
+    type Item = Capability;
+    type IntoIter = std::vec::IntoIter<Capability>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
+}
+
+/// The running server's capabilities, asked for only the first time they're needed.
+#[cfg(any(feature = "dbus", feature = "zbus"))]
+pub(crate) fn queried() -> Option<ServerCapabilities> {
+    if let Some(capabilities) = known() {
+        return Some(capabilities);
+    }
+    match super::get_capabilities() {
+        Ok(capabilities) => {
+            if let Ok(mut queried) = QUERIED.write() {
+                *queried = Some(capabilities.clone());
+            }
+            Some(capabilities)
+        }
+        Err(error) => {
+            log::warn!("can't query the server's capabilities, {}", error);
+            None
+        }
+    }
+}
+
+/// The capabilities [`queried`] before, without asking the server.
+pub(crate) fn known() -> Option<ServerCapabilities> {
+    QUERIED.read().ok().and_then(|queried| queried.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_names() {
+        let capabilities = ServerCapabilities::from(vec![
+            "body".to_owned(),
+            "body-markup".to_owned(),
+            "x-dunst-fancy".to_owned(),
+        ]);
+        assert!(capabilities.supports(Capability::BodyMarkup));
+        assert!(!capabilities.supports(Capability::Actions));
+        assert_eq!(
+            capabilities[2],
+            Capability::Other("x-dunst-fancy".to_owned())
+        );
+        assert_eq!(capabilities[1].to_string(), "body-markup");
+    }
+}
@@ -4,21 +4,28 @@ use dbus::{
     Message,
 };
 
-use super::{
-    bus::NotificationBus, ActionResponse, ActionResponseHandler, CloseReason,
-    NOTIFICATION_INTERFACE,
-};
+use super::{bus::NotificationBus, NOTIFICATION_INTERFACE};
 
 use crate::{
+    action::{ActionResponse, ActionResponseHandler, ActionResult, CloseReason},
     error::*,
     hints::message::HintMessage,
     notification::Notification,
-    xdg::{ServerInformation, NOTIFICATION_OBJECTPATH},
+    xdg::{
+        action_icons, adapt::adapt, call_timeout, namespace, retry_policy, tags, MessageBus,
+        ServerInformation, SERVER_GONE_ERRORS,
+    },
+};
+
+use std::{
+    sync::mpsc::{self, Receiver, Sender},
+    thread,
+    time::{Duration, Instant},
 };
 
 pub mod bus {
 
-    use crate::xdg::NOTIFICATION_DEFAULT_BUS;
+    use crate::xdg::{namespace, NOTIFICATION_DEFAULT_BUS};
 
     fn skip_first_slash(s: &str) -> &str {
         if let Some('/') = s.chars().next() {
@@ -32,14 +39,9 @@ pub mod bus {
 
     type BusNameType = dbus::strings::BusName<'static>;
 
-    #[derive(Clone, Debug)]
-    pub struct NotificationBus(BusNameType);
-
-    impl Default for NotificationBus {
-        fn default() -> Self {
-            Self(dbus::strings::BusName::from_slice(NOTIFICATION_DEFAULT_BUS).unwrap())
-        }
-    }
+    /// `None` stands for the bus name of the [namespace](`crate::set_dbus_namespace`) at the time of sending.
+    #[derive(Clone, Debug, Default, PartialEq, Eq)]
+    pub struct NotificationBus(Option<BusNameType>);
 
     impl NotificationBus {
         fn namespaced_custom(custom_path: &str) -> Option<String> {
@@ -55,11 +57,15 @@ pub mod bus {
 
         pub fn custom(custom_path: &str) -> Option<Self> {
             let name = dbus::strings::BusName::new(Self::namespaced_custom(custom_path)?).ok()?;
-            Some(Self(name))
+            Some(Self(Some(name)))
         }
 
         pub fn into_name(self) -> BusNameType {
-            self.0
+            self.0.unwrap_or_else(|| {
+                dbus::strings::BusName::new(namespace::bus_name()).unwrap_or_else(|_| {
+                    dbus::strings::BusName::from_slice(NOTIFICATION_DEFAULT_BUS).unwrap()
+                })
+            })
         }
     }
 }
@@ -80,6 +86,8 @@ impl DbusNotificationHandle {
         connection: Connection,
         notification: Notification,
     ) -> DbusNotificationHandle {
+        action_icons::remember(id, &notification);
+        tags::remember(id, &notification);
         DbusNotificationHandle {
             id,
             connection,
@@ -91,10 +99,47 @@ impl DbusNotificationHandle {
         wait_for_action_signal(&self.connection, self.id, invocation_closure);
     }
 
-    pub fn close(self) {
+    pub fn wait_for_action_with_timeout(
+        self,
+        timeout: Duration,
+        invocation_closure: impl ActionResponseHandler,
+    ) {
+        let deadline = Instant::now() + timeout;
+        wait_for_action_signal_until(
+            &self.connection,
+            self.id,
+            Some(deadline),
+            invocation_closure,
+        );
+    }
+
+    pub fn wait_for_reply(self) -> Option<String> {
+        if let Err(error) = add_signal_rules(&self.connection) {
+            log::warn!("can't listen for replies, {}", error);
+            return None;
+        }
+        for item in self.connection.iter(1000) {
+            if let ConnectionItem::Signal(message) = item {
+                match parse_reply(&message) {
+                    Some((id, reply)) if id == self.id => return Some(reply),
+                    _ => {}
+                }
+                match parse_signal(&message) {
+                    Some((id, _)) if id == self.id => return None,
+                    _ => {}
+                }
+            }
+        }
+        None
+    }
+
+    pub fn close(self) -> Result<()> {
         let mut message = build_message("CloseNotification", Default::default());
         message.append_items(&[self.id.into()]);
-        let _ = self.connection.send(message); // If closing fails there's nothing we could do anyway
+        self.connection
+            .send(message)
+            .map_err(|()| Error::from("failed to send CloseNotification"))?;
+        Ok(())
     }
 
     pub fn on_close<F>(self, closure: F)
@@ -108,9 +153,37 @@ impl DbusNotificationHandle {
         });
     }
 
-    pub fn update(&mut self) {
-        self.id = send_notification_via_connection(&self.notification, self.id, &self.connection)
-            .unwrap();
+    pub fn update(&mut self) -> Result<()> {
+        self.id = with_retries(
+            &mut self.connection,
+            &self.notification.message_bus,
+            |connection| send_notification_via_connection(&self.notification, self.id, connection),
+        )?;
+        Ok(())
+    }
+
+    pub fn listen(&self) -> Receiver<ActionResult> {
+        let (sender, receiver) = mpsc::channel();
+        let (ready, subscribed) = mpsc::channel();
+        let (id, bus) = (self.id, self.notification.message_bus.clone());
+        // dbus-rs connections can't move between threads, the listener opens its own
+        let spawned = thread::Builder::new()
+            .name("notify-rust listener".into())
+            .spawn(move || {
+                let connection = connect(&bus)
+                    .and_then(|connection| add_signal_rules(&connection).map(|()| connection));
+                let _ = ready.send(());
+                match connection {
+                    Ok(connection) => forward_signals(&connection, id, &sender),
+                    Err(error) => log::warn!("can't listen for actions, {}", error),
+                }
+            });
+        match spawned {
+            // subscribe before returning, so nothing that happens right after is missed
+            Ok(_) => drop(subscribed.recv()),
+            Err(error) => log::warn!("can't listen for actions, {}", error),
+        }
+        receiver
     }
 }
 
@@ -128,25 +201,42 @@ pub fn send_notification_via_connection_at_bus(
     connection: &Connection,
     bus: NotificationBus,
 ) -> Result<u32> {
+    let message = notify_message(notification, id, bus)?;
+    let reply = connection.send_with_reply_and_block(message, call_timeout_ms())?;
+
+    match reply.get_items().first() {
+        Some(MessageItem::UInt32(ref id)) => Ok(*id),
+        _ => Ok(0),
+    }
+}
+
+fn notify_message(notification: &Notification, id: u32, bus: NotificationBus) -> Result<Message> {
     let mut message = build_message("Notify", bus);
     let timeout: i32 = notification.timeout.into();
+    let (summary, body) = adapt(notification);
     message.append_items(&[
         notification.appname.to_owned().into(), // appname
         id.into(),                              // notification to update
         notification.icon.to_owned().into(),    // icon
-        notification.summary.to_owned().into(), // summary (title)
-        notification.body.to_owned().into(),    // body
+        summary.into_owned().into(),            // summary (title)
+        body.into_owned().into(),               // body
         pack_actions(notification),             // actions
         pack_hints(notification)?,              // hints
         timeout.into(),                         // timeout
     ]);
+    Ok(message)
+}
 
-    let reply = connection.send_with_reply_and_block(message, 2000)?;
-
-    match reply.get_items().first() {
-        Some(MessageItem::UInt32(ref id)) => Ok(*id),
-        _ => Ok(0),
-    }
+/// Sends `Notify` without asking the server for a reply.
+pub fn connect_and_send_notification_no_wait(notification: &Notification) -> Result<()> {
+    let connection = connect(&notification.message_bus)?;
+    let inner_id = tags::replaces_id(notification);
+    let mut message = notify_message(notification, inner_id, notification.bus.clone())?;
+    message.set_no_reply(true);
+    connection
+        .send(message)
+        .map_err(|()| Error::from("failed to send Notify"))?;
+    Ok(())
 }
 
 pub fn connect_and_send_notification(
@@ -160,9 +250,11 @@ pub fn connect_and_send_notification_at_bus(
     notification: &Notification,
     bus: NotificationBus,
 ) -> Result<DbusNotificationHandle> {
-    let connection = Connection::get_private(BusType::Session)?;
-    let inner_id = notification.id.unwrap_or(0);
-    let id = send_notification_via_connection_at_bus(notification, inner_id, &connection, bus)?;
+    let mut connection = connect(&notification.message_bus)?;
+    let inner_id = tags::replaces_id(notification);
+    let id = with_retries(&mut connection, &notification.message_bus, |connection| {
+        send_notification_via_connection_at_bus(notification, inner_id, connection, bus.clone())
+    })?;
 
     Ok(DbusNotificationHandle::new(
         id,
@@ -171,10 +263,67 @@ pub fn connect_and_send_notification_at_bus(
     ))
 }
 
+/// Runs `call`, retrying according to the [`RetryPolicy`](`crate::xdg::RetryPolicy`)
+/// if the server or the bus went away, a broken `connection` is replaced first.
+fn with_retries<T>(
+    connection: &mut Connection,
+    bus: &MessageBus,
+    call: impl Fn(&Connection) -> Result<T>,
+) -> Result<T> {
+    let policy = retry_policy();
+    let mut attempt = 0;
+    loop {
+        match call(connection) {
+            Err(error) if attempt < policy.attempts && is_transient(&error) => {
+                attempt += 1;
+                thread::sleep(policy.delay);
+                if error_name(&error) == Some(DISCONNECTED) {
+                    *connection = connect(bus)?;
+                }
+            }
+            result => return result,
+        }
+    }
+}
+
+const DISCONNECTED: &str = "org.freedesktop.DBus.Error.Disconnected";
+
+fn error_name(error: &Error) -> Option<&str> {
+    match error.kind() {
+        ErrorKind::Dbus(error) => error.name(),
+        _ => None,
+    }
+}
+
+fn is_transient(error: &Error) -> bool {
+    error_name(error).map_or(false, |name| {
+        name == DISCONNECTED || SERVER_GONE_ERRORS.contains(&name)
+    })
+}
+
+pub(super) fn connect(bus: &MessageBus) -> Result<Connection> {
+    Ok(match bus {
+        MessageBus::Session => Connection::get_private(BusType::Session)?,
+        MessageBus::System => Connection::get_private(BusType::System)?,
+        MessageBus::Address(address) => {
+            let connection = Connection::open_private(address)?;
+            connection.register()?;
+            connection
+        }
+    })
+}
+
+/// How long to wait for replies, 2 seconds unless set otherwise.
+fn call_timeout_ms() -> i32 {
+    call_timeout().map_or(2000, |timeout| {
+        i32::try_from(timeout.as_millis()).unwrap_or(i32::MAX)
+    })
+}
+
 fn build_message(method_name: &str, bus: NotificationBus) -> Message {
     Message::new_method_call(
         bus.into_name(),
-        NOTIFICATION_OBJECTPATH,
+        namespace::object_path(),
         NOTIFICATION_INTERFACE,
         method_name,
     )
@@ -182,13 +331,13 @@ fn build_message(method_name: &str, bus: NotificationBus) -> Message {
 }
 
 pub fn pack_hints(notification: &Notification) -> Result<MessageItem> {
-    if !notification.hints.is_empty() || !notification.hints_unique.is_empty() {
-        let hints = notification
-            .get_hints()
-            .cloned()
-            .map(HintMessage::wrap_hint)
-            .collect::<Vec<(MessageItem, MessageItem)>>();
-
+    let hints = notification
+        .get_hints()
+        .cloned()
+        .chain(crate::hints::adapted_hints(notification))
+        .map(HintMessage::wrap_hint)
+        .collect::<Vec<(MessageItem, MessageItem)>>();
+    if !hints.is_empty() {
         if let Ok(array) = MessageItem::new_dict(hints) {
             return Ok(array);
         }
@@ -200,10 +349,11 @@ pub fn pack_hints(notification: &Notification) -> Result<MessageItem> {
 }
 
 pub fn pack_actions(notification: &Notification) -> MessageItem {
-    if !notification.actions.is_empty() {
+    let pairs = notification.actions_to_pairs();
+    if !pairs.is_empty() {
         let mut actions = vec![];
-        for action in &notification.actions {
-            actions.push(action.to_owned().into());
+        for action in pairs {
+            actions.push(action.into());
         }
         if let Ok(array) = MessageItem::new_array(actions) {
             return array;
@@ -216,9 +366,11 @@ pub fn pack_actions(notification: &Notification) -> MessageItem {
 pub fn get_capabilities() -> Result<Vec<String>> {
     let mut capabilities = vec![];
 
-    let message = build_message("GetCapabilities", Default::default());
-    let connection = Connection::get_private(BusType::Session)?;
-    let reply = connection.send_with_reply_and_block(message, 2000)?;
+    let mut connection = connect(&MessageBus::Session)?;
+    let reply = with_retries(&mut connection, &MessageBus::Session, |connection| {
+        let message = build_message("GetCapabilities", Default::default());
+        Ok(connection.send_with_reply_and_block(message, call_timeout_ms())?)
+    })?;
 
     if let Some(MessageItem::Array(items)) = reply.get_items().first() {
         for item in items.iter() {
@@ -240,9 +392,11 @@ fn unwrap_message_string(item: Option<&MessageItem>) -> String {
 
 #[allow(clippy::get_first)]
 pub fn get_server_information() -> Result<ServerInformation> {
-    let message = build_message("GetServerInformation", Default::default());
-    let connection = Connection::get_private(BusType::Session)?;
-    let reply = connection.send_with_reply_and_block(message, 2000)?;
+    let mut connection = connect(&MessageBus::Session)?;
+    let reply = with_retries(&mut connection, &MessageBus::Session, |connection| {
+        let message = build_message("GetServerInformation", Default::default());
+        Ok(connection.send_with_reply_and_block(message, call_timeout_ms())?)
+    })?;
 
     let items = reply.get_items();
 
@@ -254,75 +408,149 @@ pub fn get_server_information() -> Result<ServerInformation> {
     })
 }
 
+pub fn is_server_running() -> Result<bool> {
+    let mut connection = connect(&MessageBus::Session)?;
+    let reply = with_retries(&mut connection, &MessageBus::Session, |connection| {
+        let message = Message::new_method_call(
+            "org.freedesktop.DBus",
+            "/org/freedesktop/DBus",
+            "org.freedesktop.DBus",
+            "NameHasOwner",
+        )
+        .map_err(|error| Error::from(error.as_str()))?
+        .append1(NotificationBus::default().into_name().to_string());
+        Ok(connection.send_with_reply_and_block(message, call_timeout_ms())?)
+    })?;
+    Ok(matches!(
+        reply.get_items().first(),
+        Some(MessageItem::Bool(true))
+    ))
+}
+
 /// Listens for the `ActionInvoked(UInt32, String)` Signal.
 ///
 /// No need to use this, check out `Notification::show_and_wait_for_action(FnOnce(action:&str))`
 pub fn handle_action(id: u32, func: impl ActionResponseHandler) {
-    let connection = Connection::get_private(BusType::Session).unwrap();
-    wait_for_action_signal(&connection, id, func);
+    match Connection::get_private(BusType::Session) {
+        Ok(connection) => wait_for_action_signal(&connection, id, func),
+        Err(error) => log::warn!("can't listen for actions, {}", error),
+    }
 }
 
 // Listens for the `ActionInvoked(UInt32, String)` signal.
 fn wait_for_action_signal(connection: &Connection, id: u32, handler: impl ActionResponseHandler) {
-    connection
-        .add_match(&format!(
-            "interface='{}',member='ActionInvoked'",
-            NOTIFICATION_INTERFACE
-        ))
-        .unwrap();
-    connection
-        .add_match(&format!(
-            "interface='{}',member='NotificationClosed'",
-            NOTIFICATION_INTERFACE
-        ))
-        .unwrap();
+    wait_for_action_signal_until(connection, id, None, handler);
+}
 
+/// Sends everything that happens to notification `id` to `sender`, until it is closed or nobody listens anymore.
+fn forward_signals(connection: &Connection, id: u32, sender: &Sender<ActionResult>) {
     for item in connection.iter(1000) {
         if let ConnectionItem::Signal(message) = item {
-            let items = message.get_items();
-
-            let (path, interface, member) = (
-                message.path().map_or_else(String::new, |p| {
-                    p.into_cstring().to_string_lossy().into_owned()
-                }),
-                message.interface().map_or_else(String::new, |p| {
-                    p.into_cstring().to_string_lossy().into_owned()
-                }),
-                message.member().map_or_else(String::new, |p| {
-                    p.into_cstring().to_string_lossy().into_owned()
-                }),
-            );
-            match (path.as_str(), interface.as_str(), member.as_str()) {
-                // match (protocol.unwrap(), iface.unwrap(), member.unwrap()) {
-                // Action Invoked
-                (path, interface, "ActionInvoked")
-                    if path == NOTIFICATION_OBJECTPATH && interface == NOTIFICATION_INTERFACE =>
-                {
-                    if let (&MessageItem::UInt32(nid), MessageItem::Str(ref action)) =
-                        (&items[0], &items[1])
-                    {
-                        if nid == id {
-                            handler.call(&ActionResponse::Custom(action));
-                            break;
-                        }
+            match parse_signal(&message) {
+                Some((nid, event)) if nid == id => {
+                    let closed = matches!(event, ActionResult::Closed(_));
+                    if sender.send(event).is_err() || closed {
+                        break;
                     }
                 }
+                _ => {}
+            }
+        }
+    }
+}
 
-                // Notification Closed
-                (path, interface, "NotificationClosed")
-                    if path == NOTIFICATION_OBJECTPATH && interface == NOTIFICATION_INTERFACE =>
-                {
-                    if let (&MessageItem::UInt32(nid), &MessageItem::UInt32(reason)) =
-                        (&items[0], &items[1])
-                    {
-                        if nid == id {
-                            handler.call(&ActionResponse::Closed(reason.into()));
-                            break;
-                        }
-                    }
+// Listens for the `ActionInvoked(UInt32, String)` signal, gives up once `deadline` has passed.
+fn wait_for_action_signal_until(
+    connection: &Connection,
+    id: u32,
+    deadline: Option<Instant>,
+    handler: impl ActionResponseHandler,
+) {
+    if let Err(error) = add_signal_rules(connection) {
+        log::warn!("can't listen for actions, {}", error);
+        return;
+    }
+
+    loop {
+        // wake up at least once a second, but never sleep past the deadline
+        let timeout_ms = deadline.map_or(1000, |deadline| {
+            let remaining = deadline.saturating_duration_since(Instant::now());
+            remaining.as_millis().min(1000) as i32
+        });
+        let item = match connection.iter(timeout_ms).next() {
+            Some(item) => item,
+            None => break,
+        };
+        if let ConnectionItem::Signal(message) = item {
+            match parse_signal(&message) {
+                Some((nid, ActionResult::Action(action))) if nid == id => {
+                    handler.call(&ActionResponse::Custom(&action));
+                    break;
                 }
-                (..) => (),
+                Some((nid, ActionResult::Closed(reason))) if nid == id => {
+                    handler.call(&ActionResponse::Closed(reason));
+                    break;
+                }
+                _ => {}
             }
         }
+        if deadline.map_or(false, |deadline| Instant::now() >= deadline) {
+            break;
+        }
+    }
+}
+
+/// Subscribes to `ActionInvoked`, `NotificationClosed` and `NotificationReplied`, the server only sends us signals we asked for.
+fn add_signal_rules(connection: &Connection) -> Result<()> {
+    for member in ["ActionInvoked", "NotificationClosed", "NotificationReplied"] {
+        let rule = format!("interface='{}',member='{}'", NOTIFICATION_INTERFACE, member);
+        connection.add_match(&rule)?;
+    }
+    Ok(())
+}
+
+/// Reads the notification id and what happened from an `ActionInvoked`, `NotificationClosed` or `NotificationReplied` signal,
+/// a reply counts as the `"reply"` action.
+fn parse_signal(message: &Message) -> Option<(u32, ActionResult)> {
+    let path = message.path()?;
+    let interface = message.interface()?;
+    if *path != namespace::object_path() || &*interface != NOTIFICATION_INTERFACE {
+        return None;
+    }
+    let items = message.get_items();
+    let parsed = match (&*message.member()?, items.first(), items.get(1)) {
+        ("ActionInvoked", Some(&MessageItem::UInt32(id)), Some(MessageItem::Str(action))) => {
+            Some((
+                id,
+                action_icons::translate(id, ActionResult::Action(action.clone())),
+            ))
+        }
+        (
+            "NotificationClosed",
+            Some(&MessageItem::UInt32(id)),
+            Some(&MessageItem::UInt32(reason)),
+        ) => Some((
+            id,
+            action_icons::translate(id, ActionResult::Closed(reason.into())),
+        )),
+        ("NotificationReplied", Some(&MessageItem::UInt32(id)), Some(MessageItem::Str(_))) => {
+            Some((id, ActionResult::Action("reply".into())))
+        }
+        _ => None,
+    };
+    if let Some((id, result)) = &parsed {
+        crate::trace::signal(*id, result);
+    }
+    parsed
+}
+
+/// Reads the notification id and the typed text from a `NotificationReplied` signal.
+fn parse_reply(message: &Message) -> Option<(u32, String)> {
+    if &*message.member()? != "NotificationReplied" {
+        return None;
+    }
+    match message.get_items().as_slice() {
+        [MessageItem::UInt32(id), MessageItem::Str(reply)] => Some((*id, reply.clone())),
+        _ => None,
     }
 }
@@ -0,0 +1,121 @@
+//! Routes actions and closings of many notifications from one connection, see [`ActionDispatcher`].
+
+use crate::{
+    action::ActionResult,
+    error::*,
+    notification::Notification,
+    xdg::{zbus_rs, MessageBus, NotificationHandle},
+};
+
+use futures_lite::StreamExt;
+
+use std::{
+    collections::HashMap,
+    sync::{
+        mpsc::{self, Receiver, Sender},
+        Arc, Mutex, Weak,
+    },
+    thread,
+};
+
+type Subscribers = Mutex<HashMap<u32, Sender<ActionResult>>>;
+
+/// Listens for actions and closings of any number of notifications on a single connection
+/// and a single background thread, instead of blocking one thread per notification
+/// in [`wait_for_action`](`NotificationHandle::wait_for_action`).
+///
+/// Every notification gets its own channel, which receives the actions invoked on it
+/// and finally an [`ActionResult::Closed`].
+///
+/// # Example
+/// ```no_run
+/// # use notify_rust::*;
+/// let dispatcher = ActionDispatcher::new()?;
+/// let (_handle, events) = dispatcher.show(
+///     Notification::new()
+///         .summary("download finished")
+///         .action("open", "Open"),
+/// )?;
+/// for event in events {
+///     match event {
+///         ActionResult::Action(action) => println!("invoked {:?}", action),
+///         ActionResult::Closed(reason) => println!("closed {:?}", reason),
+///         ActionResult::Timeout => {}
+///     }
+/// }
+/// # Ok::<(), notify_rust::error::Error>(())
+/// ```
+///
+/// The dispatcher only hears about notifications sent to the session bus.
+/// Its thread ends with the next signal after the dispatcher was dropped. (zbus only)
+#[derive(Debug)]
+pub struct ActionDispatcher {
+    subscribers: Arc<Subscribers>,
+}
+
+impl ActionDispatcher {
+    /// Connects to the session bus and starts listening in the background.
+    pub fn new() -> Result<ActionDispatcher> {
+        let stream = zbus::block_on(async {
+            let connection = zbus_rs::connect(&MessageBus::Session).await?;
+            Ok::<_, Error>(zbus_rs::signal_stream(&connection).await?)
+        })?;
+        let subscribers = Arc::<Subscribers>::default();
+        let weak = Arc::downgrade(&subscribers);
+        thread::Builder::new()
+            .name("notify-rust dispatcher".into())
+            .spawn(move || zbus::block_on(dispatch(stream, weak)))
+            .map_err(|error| Error::from(error.to_string().as_str()))?;
+        Ok(ActionDispatcher { subscribers })
+    }
+
+    /// Shows `notification` and subscribes to it, without missing events that arrive right away.
+    pub fn show(
+        &self,
+        notification: &Notification,
+    ) -> Result<(NotificationHandle, Receiver<ActionResult>)> {
+        let mut subscribers = self
+            .subscribers
+            .lock()
+            .map_err(|_| Error::from("dispatcher poisoned"))?;
+        let handle = notification.show()?;
+        let (sender, receiver) = mpsc::channel();
+        subscribers.insert(handle.id(), sender);
+        Ok((handle, receiver))
+    }
+
+    /// Subscribes to the notification with `id`, which has been shown before.
+    ///
+    /// Subscribing again to the same id takes the events away from the previous receiver.
+    pub fn subscribe(&self, id: u32) -> Receiver<ActionResult> {
+        let (sender, receiver) = mpsc::channel();
+        if let Ok(mut subscribers) = self.subscribers.lock() {
+            subscribers.insert(id, sender);
+        }
+        receiver
+    }
+}
+
+async fn dispatch(mut stream: zbus::MessageStream, subscribers: Weak<Subscribers>) {
+    while let Some(Ok(msg)) = stream.next().await {
+        let subscribers = match subscribers.upgrade() {
+            Some(subscribers) => subscribers,
+            None => break,
+        };
+        let (id, event) = match zbus_rs::parse_signal(&msg) {
+            Some(signal) => signal,
+            None => continue,
+        };
+        let mut subscribers = match subscribers.lock() {
+            Ok(subscribers) => subscribers,
+            Err(_) => break,
+        };
+        let closed = matches!(event, ActionResult::Closed(_));
+        if let Some(sender) = subscribers.get(&id) {
+            // nobody listens anymore or nothing will follow
+            if sender.send(event).is_err() || closed {
+                subscribers.remove(&id);
+            }
+        }
+    }
+}
@@ -7,28 +7,72 @@ use dbus::ffidisp::Connection as DbusConnection;
 #[cfg(feature = "zbus")]
 use zbus::{block_on, zvariant};
 
-use crate::{error::*, notification::Notification};
-
-use std::ops::{Deref, DerefMut};
+use crate::{
+    action::{
+        ActionCallbacks, ActionResponse, ActionResult, CloseHandler, CloseReason, DEFAULT_ACTION,
+    },
+    error::*,
+    notification::Notification,
+    terminal,
+};
+
+use std::{
+    ops::{Deref, DerefMut},
+    sync::{
+        mpsc::{self, Receiver},
+        RwLock,
+    },
+    time::Duration,
+};
 
 #[cfg(feature = "dbus")]
 mod dbus_rs;
 #[cfg(all(feature = "dbus", not(feature = "zbus")))]
 use dbus_rs::bus;
 
+mod action_icons;
+mod adapt;
+pub use adapt::set_adapt_body;
+
+mod quirks;
+pub use quirks::{set_server_quirks, ServerKind};
+
+mod progress;
+pub use progress::ProgressNotification;
+
+pub(crate) mod namespace;
+pub use namespace::{set_dbus_namespace, DbusNamespace};
+
+mod token;
+
+pub(crate) mod tags;
+
+mod capabilities;
+pub(crate) use capabilities::known as known_capabilities;
+pub use capabilities::{Capability, ServerCapabilities};
+
+#[cfg(feature = "zbus")]
+mod dispatcher;
 #[cfg(feature = "zbus")]
 mod zbus_rs;
+#[cfg(feature = "zbus")]
+pub use dispatcher::ActionDispatcher;
 #[cfg(all(feature = "zbus", not(feature = "dbus")))]
 use zbus_rs::bus;
 
-#[cfg(all(feature = "dbus", feature = "zbus"))]
+#[cfg(any(
+    all(feature = "dbus", feature = "zbus"),
+    not(any(feature = "dbus", feature = "zbus"))
+))]
 mod bus;
 
 // #[cfg(all(feature = "server", feature = "dbus", unix, not(target_os = "macos")))]
 // pub mod server_dbus;
 
-// #[cfg(all(feature = "server", feature = "zbus", unix, not(target_os = "macos")))]
-// pub mod server_zbus;
+#[cfg(all(feature = "embedded_server", feature = "zbus"))]
+mod server_zbus;
+#[cfg(all(feature = "embedded_server", feature = "zbus"))]
+pub use server_zbus::set_embedded_server;
 
 // #[cfg(all(feature = "server", unix, not(target_os = "macos")))]
 // pub mod server;
@@ -42,12 +86,27 @@ pub static NOTIFICATION_DEFAULT_BUS: &str = "org.freedesktop.Notifications";
 // #[deprecated]
 pub static NOTIFICATION_DEFAULT_BUS: &str = "de.hoodie.Notifications";
 
+#[cfg(any(feature = "dbus", feature = "zbus"))]
 #[doc(hidden)]
 pub static NOTIFICATION_INTERFACE: &str = "org.freedesktop.Notifications";
 
 #[doc(hidden)]
 pub static NOTIFICATION_OBJECTPATH: &str = "/org/freedesktop/Notifications";
 
+/// Which message bus to look for the notification server on, see [`Notification::message_bus`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum MessageBus {
+    /// The user's session bus, where desktops run their notification server.
+    #[default]
+    Session,
+    /// The system wide bus, e.g. for daemons running as root.
+    System,
+    /// Any bus by its address, e.g. `"tcp:host=192.168.0.2,port=55556"` for notifications over the network.
+    Address(String),
+}
+
 pub(crate) use bus::NotificationBus;
 
 #[derive(Debug)]
@@ -57,6 +116,19 @@ enum NotificationHandleInner {
 
     #[cfg(feature = "zbus")]
     Zbus(zbus_rs::ZbusNotificationHandle),
+
+    Terminal(terminal::TerminalNotificationHandle),
+
+    Capture(crate::capture::CaptureNotificationHandle),
+
+    #[cfg(target_os = "linux")]
+    Wsl(crate::wsl::WslNotificationHandle),
+
+    #[cfg(target_os = "android")]
+    Termux(crate::termux::TermuxNotificationHandle),
+
+    #[cfg(feature = "exec")]
+    Exec(crate::exec::ExecNotificationHandle),
 }
 
 /// A handle to a shown notification.
@@ -115,9 +187,353 @@ impl NotificationHandle {
                     }),
                 );
             }
+
+            NotificationHandleInner::Terminal(_) => invocation_closure("__closed"), // FIXME: remove backward compatibility with 5.0
+            NotificationHandleInner::Capture(inner) => {
+                inner.wait_for_action(|action: &ActionResponse| match action {
+                    ActionResponse::Custom(action) => invocation_closure(action),
+                    ActionResponse::Closed(_reason) => invocation_closure("__closed"), // FIXME: remove backward compatibility with 5.0
+                });
+            }
+            #[cfg(target_os = "linux")]
+            NotificationHandleInner::Wsl(_) => invocation_closure("__closed"), // FIXME: remove backward compatibility with 5.0
+            #[cfg(target_os = "android")]
+            NotificationHandleInner::Termux(_) => invocation_closure("__closed"), // FIXME: remove backward compatibility with 5.0
+            #[cfg(feature = "exec")]
+            NotificationHandleInner::Exec(_) => invocation_closure("__closed"), // FIXME: remove backward compatibility with 5.0
+        };
+    }
+
+    /// Waits for the user to act on a notification and returns what happened.
+    ///
+    /// Unlike [`wait_for_action`](`NotificationHandle::wait_for_action`) this does not use
+    /// sentinel strings like `"__closed"`, you can match on a proper [`ActionResult`] instead.
+    ///
+    /// ```no_run
+    /// # use notify_rust::*;
+    /// # fn _doc() -> Result<(), Box<dyn std::error::Error>> {
+    /// let result = Notification::new()
+    ///     .summary("click me")
+    ///     .action("clicked", "click here")
+    ///     .show()?
+    ///     .wait_for_action_result();
+    ///
+    /// match result {
+    ///     ActionResult::Action(action) => println!("invoked {:?}", action),
+    ///     ActionResult::Closed(reason) => println!("closed {:?}", reason),
+    ///     ActionResult::Timeout => println!("nothing happened"),
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn wait_for_action_result(self) -> ActionResult {
+        let mut result = ActionResult::Timeout;
+        match self.inner {
+            #[cfg(feature = "dbus")]
+            NotificationHandleInner::Dbus(inner) => {
+                inner.wait_for_action(|action: &ActionResponse| result = action.into());
+            }
+
+            #[cfg(feature = "zbus")]
+            NotificationHandleInner::Zbus(inner) => {
+                block_on(inner.wait_for_action(|action: &ActionResponse| result = action.into()));
+            }
+
+            NotificationHandleInner::Terminal(inner) => {
+                inner.wait_for_action(|action: &ActionResponse| result = action.into());
+            }
+            NotificationHandleInner::Capture(inner) => {
+                inner.wait_for_action(|action: &ActionResponse| result = action.into());
+            }
+            #[cfg(target_os = "linux")]
+            NotificationHandleInner::Wsl(inner) => {
+                inner.wait_for_action(|action: &ActionResponse| result = action.into());
+            }
+            #[cfg(target_os = "android")]
+            NotificationHandleInner::Termux(inner) => {
+                inner.wait_for_action(|action: &ActionResponse| result = action.into());
+            }
+            #[cfg(feature = "exec")]
+            NotificationHandleInner::Exec(inner) => {
+                inner.wait_for_action(|action: &ActionResponse| result = action.into());
+            }
+        };
+        result
+    }
+
+    /// Registers a `callback` for the action with this `identifier`,
+    /// chain more of them and [`wait`](`crate::ActionCallbacks::wait`) for the user to pick one.
+    pub fn on_action<'a>(
+        self,
+        identifier: &str,
+        callback: impl FnOnce() + 'a,
+    ) -> ActionCallbacks<'a> {
+        ActionCallbacks::new(move || self.wait_for_action_result()).on_action(identifier, callback)
+    }
+
+    /// Registers a `callback` for clicking the notification itself,
+    /// see [`default_action`](`crate::Notification::default_action`).
+    pub fn on_click<'a>(self, callback: impl FnOnce() + 'a) -> ActionCallbacks<'a> {
+        self.on_action(DEFAULT_ACTION, callback)
+    }
+
+    /// Like [`wait_for_action_result`](`NotificationHandle::wait_for_action_result`) but gives up
+    /// after `timeout`.
+    ///
+    /// Returns [`ActionResult::Timeout`] if neither an action was invoked nor the notification
+    /// was closed in time. Useful in command-line tools that must exit eventually.
+    ///
+    /// ```no_run
+    /// # use notify_rust::*;
+    /// # use std::time::Duration;
+    /// # fn _doc() -> Result<(), Box<dyn std::error::Error>> {
+    /// let result = Notification::new()
+    ///     .summary("click me, quickly")
+    ///     .action("clicked", "click here")
+    ///     .show()?
+    ///     .wait_for_action_with_timeout(Duration::from_secs(5));
+    ///
+    /// if result == ActionResult::Timeout {
+    ///     println!("too slow");
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    #[cfg_attr(not(any(feature = "dbus", feature = "zbus")), allow(unused_variables))]
+    pub fn wait_for_action_with_timeout(self, timeout: Duration) -> ActionResult {
+        let mut result = ActionResult::Timeout;
+        match self.inner {
+            #[cfg(feature = "dbus")]
+            NotificationHandleInner::Dbus(inner) => {
+                inner.wait_for_action_with_timeout(timeout, |action: &ActionResponse| {
+                    result = action.into();
+                });
+            }
+
+            #[cfg(feature = "zbus")]
+            NotificationHandleInner::Zbus(inner) => {
+                block_on(
+                    inner.wait_for_action_with_timeout(timeout, |action: &ActionResponse| {
+                        result = action.into();
+                    }),
+                );
+            }
+
+            NotificationHandleInner::Terminal(inner) => {
+                inner.wait_for_action(|action: &ActionResponse| result = action.into());
+            }
+            NotificationHandleInner::Capture(inner) => {
+                inner.wait_for_action(|action: &ActionResponse| result = action.into());
+            }
+            #[cfg(target_os = "linux")]
+            NotificationHandleInner::Wsl(inner) => {
+                inner.wait_for_action(|action: &ActionResponse| result = action.into());
+            }
+            #[cfg(target_os = "android")]
+            NotificationHandleInner::Termux(inner) => {
+                inner.wait_for_action(|action: &ActionResponse| result = action.into());
+            }
+            #[cfg(feature = "exec")]
+            NotificationHandleInner::Exec(inner) => {
+                inner.wait_for_action(|action: &ActionResponse| result = action.into());
+            }
+        };
+        result
+    }
+
+    /// Async version of [`wait_for_action_with_timeout`](`NotificationHandle::wait_for_action_with_timeout`).
+    ///
+    /// (zbus only, not available if dbus-rs is enabled as well)
+    #[cfg(all(feature = "async", feature = "zbus", not(feature = "dbus")))]
+    pub async fn wait_for_action_with_timeout_async(self, timeout: Duration) -> ActionResult {
+        let mut result = ActionResult::Timeout;
+        match self.inner {
+            NotificationHandleInner::Zbus(inner) => {
+                inner
+                    .wait_for_action_with_timeout(timeout, |action: &ActionResponse| {
+                        result = action.into();
+                    })
+                    .await;
+            }
+            NotificationHandleInner::Terminal(inner) => {
+                inner.wait_for_action(|action: &ActionResponse| result = action.into());
+            }
+            NotificationHandleInner::Capture(inner) => {
+                inner.wait_for_action(|action: &ActionResponse| result = action.into());
+            }
+            #[cfg(target_os = "linux")]
+            NotificationHandleInner::Wsl(inner) => {
+                inner.wait_for_action(|action: &ActionResponse| result = action.into());
+            }
+            #[cfg(target_os = "android")]
+            NotificationHandleInner::Termux(inner) => {
+                inner.wait_for_action(|action: &ActionResponse| result = action.into());
+            }
+            #[cfg(feature = "exec")]
+            NotificationHandleInner::Exec(inner) => {
+                inner.wait_for_action(|action: &ActionResponse| result = action.into());
+            }
+        };
+        result
+    }
+
+    /// Async version of [`wait_for_action_result`](`NotificationHandle::wait_for_action_result`).
+    ///
+    /// (zbus only, not available if dbus-rs is enabled as well)
+    #[cfg(all(feature = "async", feature = "zbus", not(feature = "dbus")))]
+    pub async fn wait_for_action_result_async(self) -> ActionResult {
+        let mut result = ActionResult::Timeout;
+        match self.inner {
+            NotificationHandleInner::Zbus(inner) => {
+                inner
+                    .wait_for_action(|action: &ActionResponse| result = action.into())
+                    .await;
+            }
+            NotificationHandleInner::Terminal(inner) => {
+                inner.wait_for_action(|action: &ActionResponse| result = action.into());
+            }
+            NotificationHandleInner::Capture(inner) => {
+                inner.wait_for_action(|action: &ActionResponse| result = action.into());
+            }
+            #[cfg(target_os = "linux")]
+            NotificationHandleInner::Wsl(inner) => {
+                inner.wait_for_action(|action: &ActionResponse| result = action.into());
+            }
+            #[cfg(target_os = "android")]
+            NotificationHandleInner::Termux(inner) => {
+                inner.wait_for_action(|action: &ActionResponse| result = action.into());
+            }
+            #[cfg(feature = "exec")]
+            NotificationHandleInner::Exec(inner) => {
+                inner.wait_for_action(|action: &ActionResponse| result = action.into());
+            }
+        };
+        result
+    }
+
+    /// Async version of [`wait_for_action`](`NotificationHandle::wait_for_action`).
+    ///
+    /// Waits for the user to act on a notification without blocking the executor.
+    ///
+    /// ```no_run
+    /// # use notify_rust::*;
+    /// # async fn _doc() -> Result<(), Box<dyn std::error::Error>> {
+    /// Notification::new()
+    ///     .summary("click me")
+    ///     .action("clicked", "click here")
+    ///     .show_async()
+    ///     .await?
+    ///     .wait_for_action_async(|action| println!("invoked {:?}", action))
+    ///     .await;
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// (zbus only, not available if dbus-rs is enabled as well)
+    #[cfg(all(feature = "async", feature = "zbus", not(feature = "dbus")))]
+    pub async fn wait_for_action_async<F>(self, invocation_closure: F)
+    where
+        F: FnOnce(&str),
+    {
+        match self.inner {
+            NotificationHandleInner::Zbus(inner) => {
+                inner
+                    .wait_for_action(|action: &ActionResponse| match action {
+                        ActionResponse::Custom(action) => invocation_closure(action),
+                        ActionResponse::Closed(_reason) => invocation_closure("__closed"), // FIXME: remove backward compatibility with 5.0
+                    })
+                    .await;
+            }
+            NotificationHandleInner::Terminal(_) => invocation_closure("__closed"), // FIXME: remove backward compatibility with 5.0
+            NotificationHandleInner::Capture(inner) => {
+                inner.wait_for_action(|action: &ActionResponse| match action {
+                    ActionResponse::Custom(action) => invocation_closure(action),
+                    ActionResponse::Closed(_reason) => invocation_closure("__closed"), // FIXME: remove backward compatibility with 5.0
+                });
+            }
+            #[cfg(target_os = "linux")]
+            NotificationHandleInner::Wsl(_) => invocation_closure("__closed"), // FIXME: remove backward compatibility with 5.0
+            #[cfg(target_os = "android")]
+            NotificationHandleInner::Termux(_) => invocation_closure("__closed"), // FIXME: remove backward compatibility with 5.0
+            #[cfg(feature = "exec")]
+            NotificationHandleInner::Exec(_) => invocation_closure("__closed"), // FIXME: remove backward compatibility with 5.0
         };
     }
 
+    /// Async version of [`on_close`](`NotificationHandle::on_close`).
+    ///
+    /// (zbus only, not available if dbus-rs is enabled as well)
+    #[cfg(all(feature = "async", feature = "zbus", not(feature = "dbus")))]
+    pub async fn on_close_async<A>(self, handler: impl CloseHandler<A>) {
+        match self.inner {
+            NotificationHandleInner::Zbus(inner) => {
+                inner
+                    .wait_for_action(|action: &ActionResponse| {
+                        if let ActionResponse::Closed(reason) = action {
+                            handler.call(*reason);
+                        }
+                    })
+                    .await;
+            }
+            NotificationHandleInner::Terminal(inner) => {
+                inner.wait_for_action(|action: &ActionResponse| {
+                    if let ActionResponse::Closed(reason) = action {
+                        handler.call(*reason);
+                    }
+                });
+            }
+            NotificationHandleInner::Capture(inner) => {
+                inner.wait_for_action(|action: &ActionResponse| {
+                    if let ActionResponse::Closed(reason) = action {
+                        handler.call(*reason);
+                    }
+                });
+            }
+            #[cfg(target_os = "linux")]
+            NotificationHandleInner::Wsl(inner) => {
+                inner.wait_for_action(|action: &ActionResponse| {
+                    if let ActionResponse::Closed(reason) = action {
+                        handler.call(*reason);
+                    }
+                });
+            }
+            #[cfg(target_os = "android")]
+            NotificationHandleInner::Termux(inner) => {
+                inner.wait_for_action(|action: &ActionResponse| {
+                    if let ActionResponse::Closed(reason) = action {
+                        handler.call(*reason);
+                    }
+                });
+            }
+            #[cfg(feature = "exec")]
+            NotificationHandleInner::Exec(inner) => {
+                inner.wait_for_action(|action: &ActionResponse| {
+                    if let ActionResponse::Closed(reason) = action {
+                        handler.call(*reason);
+                    }
+                });
+            }
+        };
+    }
+
+    /// Async version of [`close`](`NotificationHandle::close`).
+    ///
+    /// (zbus only, not available if dbus-rs is enabled as well)
+    #[cfg(all(feature = "async", feature = "zbus", not(feature = "dbus")))]
+    pub async fn close_async(self) -> Result<()> {
+        match self.inner {
+            NotificationHandleInner::Zbus(inner) => inner.close().await,
+            NotificationHandleInner::Terminal(_) => Ok(()),
+            NotificationHandleInner::Capture(inner) => inner.close(),
+            #[cfg(target_os = "linux")]
+            NotificationHandleInner::Wsl(inner) => inner.close(),
+            #[cfg(target_os = "android")]
+            NotificationHandleInner::Termux(inner) => inner.close(),
+            #[cfg(feature = "exec")]
+            NotificationHandleInner::Exec(_) => Ok(()),
+        }
+    }
+
     /// Manually close the notification
     ///
     /// # Example
@@ -133,15 +549,27 @@ impl NotificationHandle {
     ///     .show()
     ///     .unwrap();
     /// // ... and then later
-    /// handle.close();
+    /// handle.close().unwrap();
     /// ```
-    pub fn close(self) {
-        match self.inner {
+    pub fn close(self) -> Result<()> {
+        let trace = crate::trace::start("close", Some(self.id()), &self);
+        let result = match self.inner {
             #[cfg(feature = "dbus")]
             NotificationHandleInner::Dbus(inner) => inner.close(),
             #[cfg(feature = "zbus")]
             NotificationHandleInner::Zbus(inner) => block_on(inner.close()),
-        }
+            // printed notifications can't be taken back
+            NotificationHandleInner::Terminal(_) => Ok(()),
+            NotificationHandleInner::Capture(inner) => inner.close(),
+            #[cfg(target_os = "linux")]
+            NotificationHandleInner::Wsl(inner) => inner.close(),
+            #[cfg(target_os = "android")]
+            NotificationHandleInner::Termux(inner) => inner.close(),
+            #[cfg(feature = "exec")]
+            NotificationHandleInner::Exec(_) => Ok(()),
+        };
+        trace.finish(&result);
+        result
     }
 
     /// Executes a closure after the notification has closed.
@@ -169,6 +597,13 @@ impl NotificationHandle {
     ///                    .unwrap()
     ///                    .on_close(|reason| println!("closed: {:?}", reason));
     /// ```
+    ///
+    /// The reason comes from the server's `NotificationClosed` signal, e.g. [`CloseReason::Dismissed`]
+    /// when the user dismissed it or [`CloseReason::Expired`] when it timed out.
+    /// Notifications shown by a fallback like the terminal always report [`CloseReason::Expired`].
+    ///
+    /// [`CloseReason::Dismissed`]: crate::CloseReason::Dismissed
+    /// [`CloseReason::Expired`]: crate::CloseReason::Expired
     pub fn on_close<A>(self, handler: impl CloseHandler<A>) {
         match self.inner {
             #[cfg(feature = "dbus")]
@@ -187,6 +622,44 @@ impl NotificationHandle {
                     }
                 }));
             }
+            NotificationHandleInner::Terminal(inner) => {
+                inner.wait_for_action(|action: &ActionResponse| {
+                    if let ActionResponse::Closed(reason) = action {
+                        handler.call(*reason);
+                    }
+                });
+            }
+            NotificationHandleInner::Capture(inner) => {
+                inner.wait_for_action(|action: &ActionResponse| {
+                    if let ActionResponse::Closed(reason) = action {
+                        handler.call(*reason);
+                    }
+                });
+            }
+            #[cfg(target_os = "linux")]
+            NotificationHandleInner::Wsl(inner) => {
+                inner.wait_for_action(|action: &ActionResponse| {
+                    if let ActionResponse::Closed(reason) = action {
+                        handler.call(*reason);
+                    }
+                });
+            }
+            #[cfg(target_os = "android")]
+            NotificationHandleInner::Termux(inner) => {
+                inner.wait_for_action(|action: &ActionResponse| {
+                    if let ActionResponse::Closed(reason) = action {
+                        handler.call(*reason);
+                    }
+                });
+            }
+            #[cfg(feature = "exec")]
+            NotificationHandleInner::Exec(inner) => {
+                inner.wait_for_action(|action: &ActionResponse| {
+                    if let ActionResponse::Closed(reason) = action {
+                        handler.call(*reason);
+                    }
+                });
+            }
         };
     }
 
@@ -204,21 +677,91 @@ impl NotificationHandle {
     /// notification.summary("Latest News (Correction)")
     ///             .body("Bayern Dortmund 3:3");
     ///
-    /// notification.update();
+    /// notification.update().unwrap();
     /// ```
     /// Watch out for different implementations of the
     /// notification server! On plasma5 for instance, you should also change the appname, so the old
-    /// message is really replaced and not just amended. Xfce behaves well, all others have not
-    /// been tested by the developer.
-    pub fn update(&mut self) {
-        match self.inner {
+    /// message is really replaced and not just amended, or let [`set_server_quirks`] take care of it.
+    /// Xfce behaves well, all others have not been tested by the developer.
+    pub fn update(&mut self) -> Result<()> {
+        let trace = crate::trace::start("update", Some(self.id()), self);
+        let result = match self.inner {
+            #[cfg(feature = "dbus")]
+            NotificationHandleInner::Dbus(_) if quirks::update_by_reshowing() => self.reshow(),
+            #[cfg(feature = "zbus")]
+            NotificationHandleInner::Zbus(_) if quirks::update_by_reshowing() => self.reshow(),
             #[cfg(feature = "dbus")]
             NotificationHandleInner::Dbus(ref mut inner) => inner.update(),
             #[cfg(feature = "zbus")]
             NotificationHandleInner::Zbus(ref mut inner) => inner.update(),
+            NotificationHandleInner::Terminal(ref mut inner) => inner.update(),
+            NotificationHandleInner::Capture(ref mut inner) => inner.update(),
+            #[cfg(target_os = "linux")]
+            NotificationHandleInner::Wsl(ref mut inner) => inner.update(),
+            #[cfg(target_os = "android")]
+            NotificationHandleInner::Termux(ref mut inner) => inner.update(),
+            #[cfg(feature = "exec")]
+            NotificationHandleInner::Exec(ref mut inner) => inner.update(),
+        };
+        if result.is_ok() {
+            action_icons::remember(self.id(), self);
+        }
+        trace.finish(&result);
+        result
+    }
+
+    /// Listens for the user acting on the notification in the background, without giving up the handle.
+    ///
+    /// The receiver gets every action invoked on the notification and finally an [`ActionResult::Closed`],
+    /// meanwhile the handle can still [`update`](`NotificationHandle::update`) or
+    /// [`close`](`NotificationHandle::close`) the notification.
+    ///
+    /// ```no_run
+    /// # use notify_rust::*;
+    /// # fn _doc() -> Result<(), Box<dyn std::error::Error>> {
+    /// let mut handle = Notification::new()
+    ///     .summary("downloading")
+    ///     .action("cancel", "Cancel")
+    ///     .show()?;
+    /// let events = handle.listen();
+    /// for percent in (0..=100).step_by(10) {
+    ///     if let Ok(ActionResult::Action(_)) = events.try_recv() {
+    ///         break;
+    ///     }
+    ///     handle.body(&format!("{}%", percent));
+    ///     handle.update()?;
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    ///
+    /// Every call spawns a thread, which ends once the notification was closed.
+    /// To keep track of many notifications at once, an [`ActionDispatcher`](`crate::ActionDispatcher`) is cheaper with zbus.
+    pub fn listen(&self) -> Receiver<ActionResult> {
+        match self.inner {
+            #[cfg(feature = "dbus")]
+            NotificationHandleInner::Dbus(ref inner) => inner.listen(),
+            #[cfg(feature = "zbus")]
+            NotificationHandleInner::Zbus(ref inner) => inner.listen(),
+            NotificationHandleInner::Capture(ref inner) => inner.listen(),
+            // fallbacks never hear back, like in `wait_for_action` the notification is gone right away
+            _ => {
+                let (sender, receiver) = mpsc::channel();
+                let _ = sender.send(ActionResult::Closed(CloseReason::Expired));
+                receiver
+            }
         }
     }
 
+    /// Shows the notification anew and closes the old one, for servers that don't replace it properly.
+    #[cfg(any(feature = "dbus", feature = "zbus"))]
+    fn reshow(&mut self) -> Result<()> {
+        let mut notification = (**self).clone();
+        notification.id = None;
+        let shown = notification.show()?;
+        std::mem::replace(self, shown).close()
+    }
+
     /// Returns the Handle's id.
     pub fn id(&self) -> u32 {
         match self.inner {
@@ -226,8 +769,127 @@ impl NotificationHandle {
             NotificationHandleInner::Dbus(ref inner) => inner.id,
             #[cfg(feature = "zbus")]
             NotificationHandleInner::Zbus(ref inner) => inner.id,
+            NotificationHandleInner::Terminal(ref inner) => inner.id,
+            NotificationHandleInner::Capture(ref inner) => inner.id,
+            #[cfg(target_os = "linux")]
+            NotificationHandleInner::Wsl(ref inner) => inner.id,
+            #[cfg(target_os = "android")]
+            NotificationHandleInner::Termux(ref inner) => inner.id,
+            #[cfg(feature = "exec")]
+            NotificationHandleInner::Exec(ref inner) => inner.id,
         }
     }
+
+    /// Describes how to reach the notification again, so another process can [`update`](`NotificationHandle::update`)
+    /// or [`close`](`NotificationHandle::close`) it, e.g. an update agent after rebooting halfway through.
+    ///
+    /// The token holds the id, the appname and the [`MessageBus`], turn it back into a handle with
+    /// [`from_token`](`NotificationHandle::from_token`).
+    /// Returns `None` if the notification wasn't sent via D-Bus, e.g. because it was printed to the terminal.
+    ///
+    /// ```no_run
+    /// # use notify_rust::*;
+    /// # fn _doc() -> Result<(), Box<dyn std::error::Error>> {
+    /// let handle = Notification::new().summary("installing updates").show()?;
+    /// if let Some(token) = handle.to_token() {
+    ///     std::fs::write("/var/lib/updater/notification", token)?;
+    /// }
+    /// // after restarting
+    /// let token = std::fs::read_to_string("/var/lib/updater/notification")?;
+    /// let mut handle = NotificationHandle::from_token(&token)?;
+    /// handle.summary("updates installed");
+    /// handle.update()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn to_token(&self) -> Option<String> {
+        match self.inner {
+            #[cfg(feature = "dbus")]
+            NotificationHandleInner::Dbus(_) => {}
+            #[cfg(feature = "zbus")]
+            NotificationHandleInner::Zbus(_) => {}
+            NotificationHandleInner::Capture(_) => {}
+            _ => return None,
+        }
+        Some(token::encode(self.id(), &self.appname, &self.message_bus))
+    }
+
+    /// Reconnects to the notification a [`to_token`](`NotificationHandle::to_token`) describes.
+    ///
+    /// The handle only knows the notification's id and appname,
+    /// everything else has to be set again before [`update`](`NotificationHandle::update`)ing it.
+    /// Whether the notification is still shown isn't checked, servers show updates of notifications
+    /// that are gone as new ones.
+    pub fn from_token(token: &str) -> Result<NotificationHandle> {
+        let (id, appname, message_bus) = token::decode(token)?;
+        let mut notification = Notification::new();
+        notification
+            .appname(appname)
+            .id(id)
+            .message_bus(message_bus);
+        if let Some(captured) = crate::capture::resume(&notification) {
+            return Ok(captured.into());
+        }
+        resume(id, notification)
+    }
+
+    /// Which backend delivered the notification, for logging.
+    pub(crate) fn backend(&self) -> &'static str {
+        match self.inner {
+            #[cfg(feature = "dbus")]
+            NotificationHandleInner::Dbus(_) => "dbus-rs",
+            #[cfg(feature = "zbus")]
+            NotificationHandleInner::Zbus(_) => "zbus",
+            NotificationHandleInner::Terminal(_) => "terminal",
+            NotificationHandleInner::Capture(_) => "capture",
+            #[cfg(target_os = "linux")]
+            NotificationHandleInner::Wsl(_) => "wsl",
+            #[cfg(target_os = "android")]
+            NotificationHandleInner::Termux(_) => "termux",
+            #[cfg(feature = "exec")]
+            NotificationHandleInner::Exec(_) => "notify-send",
+        }
+    }
+
+    /// The notification's [`tag`](`Notification::tag`), the server identifies it by its [`id`](`NotificationHandle::id`) though.
+    pub fn tag(&self) -> Option<&str> {
+        self.tag.as_deref()
+    }
+
+    /// The notification's group, xdg notifications have none.
+    pub fn group(&self) -> Option<&str> {
+        None
+    }
+
+    /// Waits for the user to submit the text input added by
+    /// [`reply_input`](`Notification::reply_input`) and returns the typed text.
+    ///
+    /// Returns `None` if the notification went away without a reply or was shown without an input,
+    /// because the server doesn't offer one.
+    pub fn wait_for_reply(self) -> Option<String> {
+        match self.inner {
+            #[cfg(feature = "dbus")]
+            NotificationHandleInner::Dbus(inner) => inner.wait_for_reply(),
+            #[cfg(feature = "zbus")]
+            NotificationHandleInner::Zbus(inner) => block_on(inner.wait_for_reply()),
+            _ => None,
+        }
+    }
+
+    /// Fails, xdg notifications have no progress bar.
+    pub fn update_progress(&mut self, _value: f32, _status: &str) -> Result<()> {
+        Err("xdg notifications have no progress bar".into())
+    }
+
+    /// Whether [`update`](`NotificationHandle::update`) can replace the shown notification on this platform.
+    pub fn supports_update(&self) -> bool {
+        true
+    }
+
+    /// Whether [`close`](`NotificationHandle::close`) can remove the shown notification on this platform.
+    pub fn supports_close(&self) -> bool {
+        true
+    }
 }
 
 /// Required for `DerefMut`
@@ -240,6 +902,14 @@ impl Deref for NotificationHandle {
             NotificationHandleInner::Dbus(ref inner) => &inner.notification,
             #[cfg(feature = "zbus")]
             NotificationHandleInner::Zbus(ref inner) => &inner.notification,
+            NotificationHandleInner::Terminal(ref inner) => &inner.notification,
+            NotificationHandleInner::Capture(ref inner) => &inner.notification,
+            #[cfg(target_os = "linux")]
+            NotificationHandleInner::Wsl(ref inner) => &inner.notification,
+            #[cfg(target_os = "android")]
+            NotificationHandleInner::Termux(ref inner) => &inner.notification,
+            #[cfg(feature = "exec")]
+            NotificationHandleInner::Exec(ref inner) => &inner.notification,
         }
     }
 }
@@ -252,6 +922,14 @@ impl DerefMut for NotificationHandle {
             NotificationHandleInner::Dbus(ref mut inner) => &mut inner.notification,
             #[cfg(feature = "zbus")]
             NotificationHandleInner::Zbus(ref mut inner) => &mut inner.notification,
+            NotificationHandleInner::Terminal(ref mut inner) => &mut inner.notification,
+            NotificationHandleInner::Capture(ref mut inner) => &mut inner.notification,
+            #[cfg(target_os = "linux")]
+            NotificationHandleInner::Wsl(ref mut inner) => &mut inner.notification,
+            #[cfg(target_os = "android")]
+            NotificationHandleInner::Termux(ref mut inner) => &mut inner.notification,
+            #[cfg(feature = "exec")]
+            NotificationHandleInner::Exec(ref mut inner) => &mut inner.notification,
         }
     }
 }
@@ -279,6 +957,49 @@ impl From<dbus_rs::DbusNotificationHandle> for NotificationHandle {
     }
 }
 
+impl From<terminal::TerminalNotificationHandle> for NotificationHandle {
+    fn from(handle: terminal::TerminalNotificationHandle) -> NotificationHandle {
+        NotificationHandle {
+            inner: NotificationHandleInner::Terminal(handle),
+        }
+    }
+}
+
+impl From<crate::capture::CaptureNotificationHandle> for NotificationHandle {
+    fn from(handle: crate::capture::CaptureNotificationHandle) -> NotificationHandle {
+        NotificationHandle {
+            inner: NotificationHandleInner::Capture(handle),
+        }
+    }
+}
+
+#[cfg(target_os = "linux")]
+impl From<crate::wsl::WslNotificationHandle> for NotificationHandle {
+    fn from(handle: crate::wsl::WslNotificationHandle) -> NotificationHandle {
+        NotificationHandle {
+            inner: NotificationHandleInner::Wsl(handle),
+        }
+    }
+}
+
+#[cfg(target_os = "android")]
+impl From<crate::termux::TermuxNotificationHandle> for NotificationHandle {
+    fn from(handle: crate::termux::TermuxNotificationHandle) -> NotificationHandle {
+        NotificationHandle {
+            inner: NotificationHandleInner::Termux(handle),
+        }
+    }
+}
+
+#[cfg(feature = "exec")]
+impl From<crate::exec::ExecNotificationHandle> for NotificationHandle {
+    fn from(handle: crate::exec::ExecNotificationHandle) -> NotificationHandle {
+        NotificationHandle {
+            inner: NotificationHandleInner::Exec(handle),
+        }
+    }
+}
+
 #[cfg(feature = "zbus")]
 impl From<zbus_rs::ZbusNotificationHandle> for NotificationHandle {
     fn from(handle: zbus_rs::ZbusNotificationHandle) -> NotificationHandle {
@@ -295,11 +1016,11 @@ impl From<zbus_rs::ZbusNotificationHandle> for NotificationHandle {
 //compile_error!("the z and d features are mutually exclusive");
 
 #[cfg(all(
-    not(any(feature = "dbus", feature = "zbus")),
+    not(any(feature = "dbus", feature = "zbus", feature = "exec")),
     unix,
     not(target_os = "macos")
 ))]
-compile_error!("you have to build with either zbus or dbus turned on");
+compile_error!("you have to build with either zbus, dbus or exec turned on");
 
 /// Which Dbus implementation are we using?
 #[derive(Copy, Clone, Debug)]
@@ -315,6 +1036,7 @@ const DBUS_SWITCH_VAR: &str = "DBUSRS";
 
 #[cfg(all(feature = "zbus", not(feature = "dbus")))]
 pub(crate) fn show_notification(notification: &Notification) -> Result<NotificationHandle> {
+    ensure_available(notification)?;
     block_on(zbus_rs::connect_and_send_notification(notification)).map(Into::into)
 }
 
@@ -322,6 +1044,7 @@ pub(crate) fn show_notification(notification: &Notification) -> Result<Notificat
 pub(crate) async fn show_notification_async(
     notification: &Notification,
 ) -> Result<NotificationHandle> {
+    ensure_available(notification)?;
     zbus_rs::connect_and_send_notification(notification)
         .await
         .map(Into::into)
@@ -332,6 +1055,7 @@ pub(crate) async fn show_notification_async_at_bus(
     notification: &Notification,
     bus: NotificationBus,
 ) -> Result<NotificationHandle> {
+    ensure_available(notification)?;
     zbus_rs::connect_and_send_notification_at_bus(notification, bus)
         .await
         .map(Into::into)
@@ -339,11 +1063,18 @@ pub(crate) async fn show_notification_async_at_bus(
 
 #[cfg(all(feature = "dbus", not(feature = "zbus")))]
 pub(crate) fn show_notification(notification: &Notification) -> Result<NotificationHandle> {
+    ensure_available(notification)?;
     dbus_rs::connect_and_send_notification(notification).map(Into::into)
 }
 
+#[cfg(not(any(feature = "dbus", feature = "zbus")))]
+pub(crate) fn show_notification(_notification: &Notification) -> Result<NotificationHandle> {
+    Err(ErrorKind::ImplementationMissing.into())
+}
+
 #[cfg(all(feature = "dbus", feature = "zbus"))]
 pub(crate) fn show_notification(notification: &Notification) -> Result<NotificationHandle> {
+    ensure_available(notification)?;
     if std::env::var(DBUS_SWITCH_VAR).is_ok() {
         dbus_rs::connect_and_send_notification(notification).map(Into::into)
     } else {
@@ -351,6 +1082,293 @@ pub(crate) fn show_notification(notification: &Notification) -> Result<Notificat
     }
 }
 
+/// A handle for the notification with `id` shown before, without showing anything.
+#[cfg(all(feature = "zbus", not(feature = "dbus")))]
+fn resume(id: u32, notification: Notification) -> Result<NotificationHandle> {
+    let connection = block_on(zbus_rs::connect(&notification.message_bus))?;
+    Ok(NotificationHandle::for_zbus(id, connection, notification))
+}
+
+#[cfg(all(feature = "dbus", not(feature = "zbus")))]
+fn resume(id: u32, notification: Notification) -> Result<NotificationHandle> {
+    let connection = dbus_rs::connect(&notification.message_bus)?;
+    Ok(NotificationHandle::for_dbus(id, connection, notification))
+}
+
+#[cfg(all(feature = "dbus", feature = "zbus"))]
+fn resume(id: u32, notification: Notification) -> Result<NotificationHandle> {
+    if std::env::var(DBUS_SWITCH_VAR).is_ok() {
+        let connection = dbus_rs::connect(&notification.message_bus)?;
+        Ok(NotificationHandle::for_dbus(id, connection, notification))
+    } else {
+        let connection = block_on(zbus_rs::connect(&notification.message_bus))?;
+        Ok(NotificationHandle::for_zbus(id, connection, notification))
+    }
+}
+
+#[cfg(not(any(feature = "dbus", feature = "zbus")))]
+fn resume(_id: u32, _notification: Notification) -> Result<NotificationHandle> {
+    Err(ErrorKind::ImplementationMissing.into())
+}
+
+/// Pipelines the `Notify` calls with zbus, if all notifications go to the same bus and it is there,
+/// otherwise shows one after another.
+#[cfg(feature = "zbus")]
+pub(crate) fn show_notifications(
+    notifications: &[Notification],
+) -> Result<Vec<NotificationHandle>> {
+    #[cfg(feature = "dbus")]
+    if std::env::var(DBUS_SWITCH_VAR).is_ok() {
+        return notifications.iter().map(Notification::show).collect();
+    }
+    let pipelined = notifications.first().map_or(false, |first| {
+        ensure_available(first).is_ok()
+            && notifications
+                .iter()
+                .all(|notification| notification.message_bus == first.message_bus)
+    });
+    if !pipelined {
+        return notifications.iter().map(Notification::show).collect();
+    }
+    block_on(zbus_rs::send_notifications(notifications))
+        .map(|handles| handles.into_iter().map(Into::into).collect())
+}
+
+#[cfg(not(feature = "zbus"))]
+pub(crate) fn show_notifications(
+    notifications: &[Notification],
+) -> Result<Vec<NotificationHandle>> {
+    notifications.iter().map(Notification::show).collect()
+}
+
+#[cfg(feature = "zbus")]
+pub(crate) fn show_notification_with_connection(
+    notification: &Notification,
+    connection: &zbus::Connection,
+) -> Result<NotificationHandle> {
+    block_on(zbus_rs::send_notification_with_connection(
+        notification,
+        connection.clone(),
+    ))
+    .map(Into::into)
+}
+
+#[cfg(all(feature = "async", feature = "zbus"))]
+pub(crate) async fn show_notification_with_connection_async(
+    notification: &Notification,
+    connection: &zbus::Connection,
+) -> Result<NotificationHandle> {
+    zbus_rs::send_notification_with_connection(notification, connection.clone())
+        .await
+        .map(Into::into)
+}
+
+#[cfg(all(feature = "zbus", not(feature = "dbus")))]
+pub(crate) fn send_notification(notification: &Notification) -> Result<()> {
+    ensure_available(notification)?;
+    block_on(zbus_rs::connect_and_send_notification_no_wait(notification))
+}
+
+#[cfg(all(feature = "dbus", not(feature = "zbus")))]
+pub(crate) fn send_notification(notification: &Notification) -> Result<()> {
+    ensure_available(notification)?;
+    dbus_rs::connect_and_send_notification_no_wait(notification)
+}
+
+#[cfg(all(feature = "dbus", feature = "zbus"))]
+pub(crate) fn send_notification(notification: &Notification) -> Result<()> {
+    ensure_available(notification)?;
+    if std::env::var(DBUS_SWITCH_VAR).is_ok() {
+        dbus_rs::connect_and_send_notification_no_wait(notification)
+    } else {
+        block_on(zbus_rs::connect_and_send_notification_no_wait(notification))
+    }
+}
+
+#[cfg(not(any(feature = "dbus", feature = "zbus")))]
+pub(crate) fn send_notification(_notification: &Notification) -> Result<()> {
+    Err(ErrorKind::ImplementationMissing.into())
+}
+
+/// Cheaply checks whether there is a session bus to send notifications to, without connecting to it.
+///
+/// This only looks for `$DBUS_SESSION_BUS_ADDRESS` or the default socket at `$XDG_RUNTIME_DIR/bus`,
+/// which are both missing in most containers, cron jobs and ssh sessions.
+/// If this returns `false`, [`Notification::show`] fails with [`ErrorKind::NoNotificationService`]
+/// instead of a dbus error, if it returns `true` there may still be no notification server on that bus.
+///
+/// Inside WSL this is always `true`, notifications are shown on the windows host instead,
+/// same inside Termux on Android, where they are shown via Termux:API.
+/// With the `exec` feature this also checks whether `notify-send` is installed,
+/// if there is no session bus.
+pub fn is_available() -> bool {
+    #[cfg(target_os = "linux")]
+    if crate::wsl::detected() {
+        return true;
+    }
+    #[cfg(target_os = "android")]
+    if std::env::var_os("TERMUX_VERSION").is_some() {
+        return true;
+    }
+    #[cfg(feature = "exec")]
+    if !has_session_bus() {
+        return crate::exec::installed();
+    }
+    has_session_bus()
+}
+
+fn has_session_bus() -> bool {
+    std::env::var_os("DBUS_SESSION_BUS_ADDRESS").map_or(false, |address| !address.is_empty())
+        || std::env::var_os("XDG_RUNTIME_DIR")
+            .map_or(false, |dir| std::path::Path::new(&dir).join("bus").exists())
+}
+
+/// Checks whether a notification server is running on the session bus,
+/// without starting one via D-Bus activation like [`Notification::show`] would.
+///
+/// This asks the bus whether anybody owns `org.freedesktop.Notifications`,
+/// or the bus name [`set_dbus_namespace`] chose instead, which is much cheaper than a failing `show()`, e.g. to decide on a fallback up front.
+/// Also `false` if the session bus can't be reached.
+#[cfg(any(feature = "dbus", feature = "zbus"))]
+pub fn is_server_running() -> bool {
+    if !has_session_bus() {
+        return false;
+    }
+    #[cfg(all(feature = "dbus", feature = "zbus"))]
+    let running = if std::env::var(DBUS_SWITCH_VAR).is_ok() {
+        dbus_rs::is_server_running()
+    } else {
+        block_on(zbus_rs::is_server_running())
+    };
+    #[cfg(all(feature = "dbus", not(feature = "zbus")))]
+    let running = dbus_rs::is_server_running();
+    #[cfg(all(feature = "zbus", not(feature = "dbus")))]
+    let running = block_on(zbus_rs::is_server_running());
+    running.unwrap_or(false)
+}
+
+/// Checks whether a notification server is running on the session bus.
+///
+/// neither zbus nor dbus-rs are configured, always `false`
+#[cfg(not(any(feature = "dbus", feature = "zbus")))]
+pub fn is_server_running() -> bool {
+    false
+}
+
+static CALL_TIMEOUT: RwLock<Option<Duration>> = RwLock::new(None);
+
+/// Sets how long to wait for the notification server to answer calls like `Notify` or `GetCapabilities`.
+///
+/// By default dbus-rs gives up after 2 seconds and zbus waits as long as it takes,
+/// which may not be enough or too long for servers that are slow to wake up after a suspend.
+/// `None` restores the default, the timeout applies to all connections opened afterwards.
+pub fn set_call_timeout(timeout: Option<Duration>) {
+    if let Ok(mut call_timeout) = CALL_TIMEOUT.write() {
+        *call_timeout = timeout;
+    }
+}
+
+#[cfg(any(feature = "dbus", feature = "zbus"))]
+pub(crate) fn call_timeout() -> Option<Duration> {
+    CALL_TIMEOUT.read().ok().and_then(|timeout| *timeout)
+}
+
+/// How to retry calls to the notification server that failed because it or the bus went away,
+/// see [`set_retry_policy`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RetryPolicy {
+    /// How often to try again, `0` gives up right away.
+    pub attempts: u32,
+    /// How long to wait before each attempt, e.g. for a restarted server to come up again.
+    pub delay: Duration,
+}
+
+impl Default for RetryPolicy {
+    /// Reconnects once right away.
+    fn default() -> Self {
+        RetryPolicy {
+            attempts: 1,
+            delay: Duration::ZERO,
+        }
+    }
+}
+
+static RETRY_POLICY: RwLock<Option<RetryPolicy>> = RwLock::new(None);
+
+/// Sets how showing, updating and querying the server retry when the server or the bus went away,
+/// e.g. while `dunst` restarts.
+///
+/// Broken connections are replaced before trying again,
+/// if all attempts fail the last error is returned.
+pub fn set_retry_policy(policy: RetryPolicy) {
+    if let Ok(mut retry_policy) = RETRY_POLICY.write() {
+        *retry_policy = Some(policy);
+    }
+}
+
+#[cfg(any(feature = "dbus", feature = "zbus"))]
+pub(crate) fn retry_policy() -> RetryPolicy {
+    RETRY_POLICY
+        .read()
+        .ok()
+        .and_then(|policy| *policy)
+        .unwrap_or_default()
+}
+
+/// Errors from the bus when the server isn't there (yet), e.g. because it is being restarted.
+#[cfg(any(feature = "dbus", feature = "zbus"))]
+const SERVER_GONE_ERRORS: [&str; 2] = [
+    "org.freedesktop.DBus.Error.ServiceUnknown",
+    "org.freedesktop.DBus.Error.NameHasNoOwner",
+];
+
+/// Only the session bus can be looked for without connecting, other buses fail when connecting.
+#[cfg(any(feature = "dbus", feature = "zbus"))]
+fn ensure_available(notification: &Notification) -> Result<()> {
+    if notification.message_bus != MessageBus::Session {
+        return Ok(());
+    }
+    if !has_session_bus() {
+        return Err(ErrorKind::NoNotificationService.into());
+    }
+    #[cfg(all(feature = "embedded_server", feature = "zbus"))]
+    server_zbus::spawn_if_needed();
+    adapt::query_capabilities_if_needed(notification);
+    notification.check_supported(unsupported)
+}
+
+/// What `notification` asks for that the server said it can't do, nothing if it can't be asked.
+#[cfg(any(feature = "dbus", feature = "zbus"))]
+fn unsupported(notification: &Notification) -> Vec<&'static str> {
+    let capabilities = match capabilities::queried() {
+        Some(capabilities) => capabilities,
+        None => return Vec::new(),
+    };
+    let mut unsupported = Vec::new();
+    if !notification.actions.is_empty() && !capabilities.supports(Capability::Actions) {
+        unsupported.push("actions");
+    }
+    if notification.uses_action_icons() && !capabilities.supports(Capability::ActionIcons) {
+        unsupported.push("action icons");
+    }
+    if !notification.body.is_empty() && !capabilities.supports(Capability::Body) {
+        unsupported.push("body");
+    } else if notification.markup().is_none()
+        && notification.body.contains('<')
+        && !capabilities.supports(Capability::BodyMarkup)
+    {
+        unsupported.push("body markup");
+    }
+    let has_sound = notification
+        .hints
+        .iter()
+        .any(|hint| matches!(hint, crate::Hint::SoundFile(_) | crate::Hint::SoundName(_)));
+    if has_sound && !capabilities.supports(Capability::Sound) {
+        unsupported.push("sound");
+    }
+    unsupported
+}
+
 /// Get the currently used [`DbusStack`]
 ///
 /// (zbus only)
@@ -391,30 +1409,38 @@ pub fn dbus_stack() -> Option<DbusStack> {
 ///
 /// (zbus only)
 #[cfg(all(feature = "zbus", not(feature = "dbus")))]
-pub fn get_capabilities() -> Result<Vec<String>> {
-    block_on(zbus_rs::get_capabilities())
+pub fn get_capabilities() -> Result<ServerCapabilities> {
+    block_on(zbus_rs::get_capabilities()).map(ServerCapabilities::from)
 }
 
 /// Get list of all capabilities of the running notification server.
 ///
 /// (dbus-rs only)
 #[cfg(all(feature = "dbus", not(feature = "zbus")))]
-pub fn get_capabilities() -> Result<Vec<String>> {
-    dbus_rs::get_capabilities()
+pub fn get_capabilities() -> Result<ServerCapabilities> {
+    dbus_rs::get_capabilities().map(ServerCapabilities::from)
 }
 
 /// Get list of all capabilities of the running notification server.
 ///
 /// both dbus-rs and zbus, switch via `$ZBUS_NOTIFICATION`
 #[cfg(all(feature = "dbus", feature = "zbus"))]
-pub fn get_capabilities() -> Result<Vec<String>> {
+pub fn get_capabilities() -> Result<ServerCapabilities> {
     if std::env::var(DBUS_SWITCH_VAR).is_ok() {
-        dbus_rs::get_capabilities()
+        dbus_rs::get_capabilities().map(ServerCapabilities::from)
     } else {
-        block_on(zbus_rs::get_capabilities())
+        block_on(zbus_rs::get_capabilities()).map(ServerCapabilities::from)
     }
 }
 
+/// Get list of all capabilities of the running notification server.
+///
+/// neither zbus nor dbus-rs are configured, always fails
+#[cfg(not(any(feature = "dbus", feature = "zbus")))]
+pub fn get_capabilities() -> Result<ServerCapabilities> {
+    Err(ErrorKind::ImplementationMissing.into())
+}
+
 /// Returns a struct containing `ServerInformation`.
 ///
 /// This struct contains `name`, `vendor`, `version` and `spec_version` of the notification server
@@ -452,6 +1478,14 @@ pub fn get_server_information() -> Result<ServerInformation> {
     }
 }
 
+/// Returns a struct containing `ServerInformation`.
+///
+/// neither zbus nor dbus-rs are configured, always fails
+#[cfg(not(any(feature = "dbus", feature = "zbus")))]
+pub fn get_server_information() -> Result<ServerInformation> {
+    Err(ErrorKind::ImplementationMissing.into())
+}
+
 /// Return value of `get_server_information()`.
 #[derive(Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Deserialize))]
@@ -520,86 +1554,12 @@ where
     }
 }
 
-/// Reason passed to `NotificationClosed` Signal
+/// Listens for the `ActionInvoked(UInt32, String)` Signal.
 ///
-/// ## Specification
-/// As listed under [Table 8. `NotificationClosed` Parameters](https://specifications.freedesktop.org/notification-spec/latest/ar01s09.html#idm46350804042704)
-#[derive(Copy, Clone, Debug)]
-pub enum CloseReason {
-    /// The notification expired
-    Expired,
-    /// The notification was dismissed by the user
-    Dismissed,
-    /// The notification was closed by a call to `CloseNotification`
-    CloseAction,
-    /// Undefined/Reserved reason
-    Other(u32),
-}
-
-impl From<u32> for CloseReason {
-    fn from(raw_reason: u32) -> Self {
-        match raw_reason {
-            1 => CloseReason::Expired,
-            2 => CloseReason::Dismissed,
-            3 => CloseReason::CloseAction,
-            other => CloseReason::Other(other),
-        }
-    }
-}
-
-/// Helper Trait implemented by `Fn()`
-pub trait ActionResponseHandler {
-    fn call(self, response: &ActionResponse);
-}
-
-// impl<F: Send + Sync + 'static> ActionResponseHandler for F
-impl<F> ActionResponseHandler for F
+/// neither zbus nor dbus-rs are configured, there is nothing to listen to
+#[cfg(not(any(feature = "dbus", feature = "zbus")))]
+pub fn handle_action<F>(_id: u32, _func: F)
 where
     F: FnOnce(&ActionResponse),
 {
-    fn call(self, res: &ActionResponse) {
-        (self)(res);
-    }
-}
-
-/// Response to an action
-pub enum ActionResponse<'a> {
-    /// Custom Action configured by the Notification.
-    Custom(&'a str),
-
-    /// The Notification was closed.
-    Closed(CloseReason),
-}
-
-impl<'a> From<&'a str> for ActionResponse<'a> {
-    fn from(raw: &'a str) -> Self {
-        Self::Custom(raw)
-    }
-}
-
-/// Your handy callback for the `Close` signal of your Notification.
-///
-/// This is implemented by `Fn()` and `Fn(CloseReason)`, so there is probably no good reason for you to manually implement this trait.
-/// Should you find one anyway, please notify me and I'll gladly remove this obviously redundant comment.
-pub trait CloseHandler<T> {
-    /// This is called with the [`CloseReason`].
-    fn call(&self, reason: CloseReason);
-}
-
-impl<F> CloseHandler<CloseReason> for F
-where
-    F: Fn(CloseReason),
-{
-    fn call(&self, reason: CloseReason) {
-        self(reason);
-    }
-}
-
-impl<F> CloseHandler<()> for F
-where
-    F: Fn(),
-{
-    fn call(&self, _: CloseReason) {
-        self();
-    }
 }
@@ -0,0 +1,106 @@
+//! Where on the bus to find the notification server, see [`set_dbus_namespace`].
+
+use super::{NOTIFICATION_DEFAULT_BUS, NOTIFICATION_OBJECTPATH};
+
+use std::sync::RwLock;
+
+/// Overrides the bus name, read by [`DbusNamespace::default`].
+const BUS_NAME_VAR: &str = "NOTIFY_RUST_BUS_NAME";
+
+/// Overrides the object path, read by [`DbusNamespace::default`].
+const OBJECT_PATH_VAR: &str = "NOTIFY_RUST_OBJECT_PATH";
+
+static NAMESPACE: RwLock<Option<DbusNamespace>> = RwLock::new(None);
+
+/// The name and object path the notification server is called at, see [`set_dbus_namespace`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DbusNamespace {
+    /// The well-known name the server owns on the bus.
+    pub bus_name: String,
+    /// The path of the object implementing `org.freedesktop.Notifications`.
+    pub object_path: String,
+}
+
+impl Default for DbusNamespace {
+    /// Taken from `$NOTIFY_RUST_BUS_NAME` and `$NOTIFY_RUST_OBJECT_PATH` if they are set,
+    /// otherwise `"org.freedesktop.Notifications"` (`"de.hoodie.Notifications"` with the `debug_namespace` feature)
+    /// and `"/org/freedesktop/Notifications"`.
+    fn default() -> Self {
+        DbusNamespace {
+            bus_name: std::env::var(BUS_NAME_VAR)
+                .unwrap_or_else(|_| NOTIFICATION_DEFAULT_BUS.into()),
+            object_path: std::env::var(OBJECT_PATH_VAR)
+                .unwrap_or_else(|_| NOTIFICATION_OBJECTPATH.into()),
+        }
+    }
+}
+
+/// Sends notifications to a server at another bus name and object path,
+/// e.g. a private server started by integration tests, without rebuilding with the `debug_namespace` feature.
+///
+/// ```no_run
+/// # use notify_rust::*;
+/// set_dbus_namespace(Some(DbusNamespace {
+///     bus_name: "org.example.TestNotifications".into(),
+///     object_path: "/org/example/TestNotifications".into(),
+/// }));
+/// Notification::new().summary("only the test server sees this").show()?;
+/// # Ok::<(), notify_rust::error::Error>(())
+/// ```
+///
+/// `None` goes back to the [default](`DbusNamespace::default`), which can also be set from the environment.
+/// This applies to notifications shown afterwards, an invalid bus name falls back to the default one.
+pub fn set_dbus_namespace(namespace: Option<DbusNamespace>) {
+    store(&NAMESPACE, namespace);
+}
+
+fn store(current: &RwLock<Option<DbusNamespace>>, namespace: Option<DbusNamespace>) {
+    if let Ok(mut current) = current.write() {
+        *current = namespace;
+    }
+}
+
+#[cfg(any(feature = "dbus", feature = "zbus"))]
+pub(crate) fn dbus_namespace() -> DbusNamespace {
+    load(&NAMESPACE)
+}
+
+#[cfg(any(feature = "dbus", feature = "zbus"))]
+fn load(current: &RwLock<Option<DbusNamespace>>) -> DbusNamespace {
+    current
+        .read()
+        .ok()
+        .and_then(|namespace| namespace.clone())
+        .unwrap_or_default()
+}
+
+/// The bus name of [`dbus_namespace`].
+#[cfg(any(feature = "dbus", feature = "zbus"))]
+pub(crate) fn bus_name() -> String {
+    dbus_namespace().bus_name
+}
+
+/// The object path of [`dbus_namespace`].
+#[cfg(any(feature = "dbus", feature = "zbus"))]
+pub(crate) fn object_path() -> String {
+    dbus_namespace().object_path
+}
+
+#[cfg(all(test, any(feature = "dbus", feature = "zbus")))]
+mod tests {
+    use super::*;
+
+    // on a namespace of its own, other tests show notifications meanwhile
+    #[test]
+    fn overrides_namespace() {
+        let current = RwLock::new(None);
+        let namespace = DbusNamespace {
+            bus_name: "org.example.TestNotifications".into(),
+            object_path: "/org/example/TestNotifications".into(),
+        };
+        store(&current, Some(namespace.clone()));
+        assert_eq!(load(&current), namespace);
+        store(&current, None);
+        assert_eq!(load(&current), DbusNamespace::default());
+    }
+}
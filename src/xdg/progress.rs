@@ -0,0 +1,104 @@
+//! Notifications that report the progress of a long running task, see [`ProgressNotification`].
+
+use super::NotificationHandle;
+use crate::{error::*, notification::Notification};
+
+use std::{
+    ops::{Deref, DerefMut},
+    time::{Duration, Instant},
+};
+
+/// A shown notification with a progress bar, which is moved by [`set_progress`](`ProgressNotification::set_progress`).
+///
+/// Updates are rate limited, so a copy loop can report every chunk without flooding the notification server.
+/// Reaching 100% is always shown.
+///
+/// ```no_run
+/// # use notify_rust::*;
+/// let mut download = ProgressNotification::show(Notification::new().summary("downloading"))?;
+/// for percent in 0..=100 {
+///     download.set_progress(percent)?;
+/// }
+/// download.body("done");
+/// download.update()?;
+/// # Ok::<(), notify_rust::error::Error>(())
+/// ```
+///
+/// Derefs to its [`NotificationHandle`], to change the text or close it. (XDG only)
+#[derive(Debug)]
+pub struct ProgressNotification {
+    handle: NotificationHandle,
+    percent: u8,
+    interval: Duration,
+    last_update: Option<Instant>,
+}
+
+impl ProgressNotification {
+    /// Shows `notification` with its progress bar at 0%.
+    pub fn show(notification: &Notification) -> Result<ProgressNotification> {
+        let handle = notification.clone().progress(0).show()?;
+        Ok(ProgressNotification::from(handle))
+    }
+
+    /// Sets how long to wait between two updates, 250 milliseconds by default.
+    pub fn min_interval(&mut self, interval: Duration) -> &mut ProgressNotification {
+        self.interval = interval;
+        self
+    }
+
+    /// Moves the progress bar to `percent`, which is capped at 100.
+    ///
+    /// Does nothing if the bar is there already or the last update was too recent.
+    pub fn set_progress(&mut self, percent: u8) -> Result<()> {
+        let percent = percent.min(100);
+        if percent == self.percent {
+            return Ok(());
+        }
+        let too_soon = self
+            .last_update
+            .map_or(false, |last_update| last_update.elapsed() < self.interval);
+        if too_soon && percent < 100 {
+            return Ok(());
+        }
+        self.handle.progress(percent);
+        self.handle.update()?;
+        self.percent = percent;
+        self.last_update = Some(Instant::now());
+        Ok(())
+    }
+
+    /// The progress shown right now.
+    pub fn progress(&self) -> u8 {
+        self.percent
+    }
+
+    /// Gives back the handle, e.g. to wait for an action.
+    pub fn into_handle(self) -> NotificationHandle {
+        self.handle
+    }
+}
+
+impl From<NotificationHandle> for ProgressNotification {
+    fn from(handle: NotificationHandle) -> Self {
+        ProgressNotification {
+            handle,
+            percent: 0,
+            interval: Duration::from_millis(250),
+            last_update: None,
+        }
+    }
+}
+
+impl Deref for ProgressNotification {
+    type Target = NotificationHandle;
+
+    fn deref(&self) -> &NotificationHandle {
+        &self.handle
+    }
+}
+
+impl DerefMut for ProgressNotification {
+    fn deref_mut(&mut self) -> &mut NotificationHandle {
+        &mut self.handle
+    }
+}
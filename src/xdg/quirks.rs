@@ -0,0 +1,137 @@
+//! Telling notification servers apart and working around their known quirks, see [`set_server_quirks`].
+#![cfg_attr(not(any(feature = "dbus", feature = "zbus")), allow(dead_code))]
+
+use super::ServerInformation;
+
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    RwLock,
+};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// The server identified before, see [`running_server`].
+static IDENTIFIED: RwLock<Option<ServerKind>> = RwLock::new(None);
+
+/// A notification server implementation, as told by its [`ServerInformation`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ServerKind {
+    /// KDE Plasma
+    Plasma,
+    /// GNOME Shell
+    Gnome,
+    /// Xfce's notification daemon
+    Xfce,
+    /// [dunst](https://dunst-project.org)
+    Dunst,
+    /// [mako](https://github.com/emersion/mako)
+    Mako,
+    /// Ubuntu's old notify-osd
+    NotifyOsd,
+    /// Any other server, by its name.
+    Other(String),
+}
+
+impl From<&ServerInformation> for ServerKind {
+    fn from(information: &ServerInformation) -> Self {
+        let name = information.name.to_lowercase();
+        if name.contains("plasma") || information.vendor == "KDE" {
+            ServerKind::Plasma
+        } else if name.contains("gnome") {
+            ServerKind::Gnome
+        } else if name.contains("xfce") {
+            ServerKind::Xfce
+        } else if name.contains("dunst") {
+            ServerKind::Dunst
+        } else if name.contains("mako") {
+            ServerKind::Mako
+        } else if name.contains("notify-osd") {
+            ServerKind::NotifyOsd
+        } else {
+            ServerKind::Other(information.name.clone())
+        }
+    }
+}
+
+impl ServerInformation {
+    /// Which implementation this server is.
+    pub fn kind(&self) -> ServerKind {
+        ServerKind::from(self)
+    }
+}
+
+/// Makes notify-rust work around known quirks of the running notification server.
+///
+/// The server is identified from [`get_server_information`](`super::get_server_information`)
+/// the first time a workaround might apply. So far these are known:
+/// * Plasma amends notifications on [`update`](`super::NotificationHandle::update`) instead of replacing them,
+///   so they are closed and shown anew.
+///
+/// Off by default.
+pub fn set_server_quirks(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// The running server, if working around quirks is enabled, identified only the first time.
+fn running_server() -> Option<ServerKind> {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return None;
+    }
+    if let Some(server) = IDENTIFIED
+        .read()
+        .ok()
+        .and_then(|identified| identified.clone())
+    {
+        return Some(server);
+    }
+    match super::get_server_information() {
+        Ok(information) => {
+            let server = information.kind();
+            if let Ok(mut identified) = IDENTIFIED.write() {
+                *identified = Some(server.clone());
+            }
+            Some(server)
+        }
+        Err(error) => {
+            log::warn!("can't identify the notification server, {}", error);
+            None
+        }
+    }
+}
+
+/// Whether updating has to close the notification and show it again.
+pub(crate) fn update_by_reshowing() -> bool {
+    running_server() == Some(ServerKind::Plasma)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn information(name: &str, vendor: &str) -> ServerInformation {
+        ServerInformation {
+            name: name.into(),
+            vendor: vendor.into(),
+            version: "1.0".into(),
+            spec_version: "1.2".into(),
+        }
+    }
+
+    #[test]
+    fn identify() {
+        assert_eq!(information("Plasma", "KDE").kind(), ServerKind::Plasma);
+        assert_eq!(
+            information("gnome-shell", "GNOME").kind(),
+            ServerKind::Gnome
+        );
+        assert_eq!(
+            information("Xfce Notify Daemon", "Xfce").kind(),
+            ServerKind::Xfce
+        );
+        assert_eq!(information("dunst", "knopwob").kind(), ServerKind::Dunst);
+        assert_eq!(
+            information("mockd", "test").kind(),
+            ServerKind::Other("mockd".into())
+        );
+    }
+}
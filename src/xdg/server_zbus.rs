@@ -0,0 +1,137 @@
+//! A minimal notification server started in-process when there is none,
+//! see [`set_embedded_server`].
+//!
+//! It prints notifications to the terminal, which beats losing them in bare window manager setups.
+
+use crate::{action::Action, notification::Notification, terminal::TerminalStyle, xdg};
+
+use zbus::{block_on, interface, object_server::SignalEmitter, zvariant::OwnedValue};
+
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Mutex,
+    },
+};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Keeps the servers' connections, and thereby the servers, alive, by the bus name they serve.
+static SERVERS: Mutex<Vec<(String, zbus::Connection)>> = Mutex::new(Vec::new());
+
+/// Makes [`Notification::show`] start a minimal notification server inside the application
+/// if nobody else serves notifications on the session bus, e.g. in bare window manager setups.
+///
+/// The server prints notifications to the terminal, like the
+/// [terminal fallback](`crate::set_terminal_fallback`), but other applications
+/// sending notifications while it runs end up there too.
+/// It can't show actions, so it reports no `"actions"` capability.
+/// The server stays up until the application exits,
+/// after [`set_dbus_namespace`](`crate::set_dbus_namespace`) another one may start for the new bus name.
+///
+/// Off by default. (zbus only)
+pub fn set_embedded_server(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// Starts the server if it is enabled and nobody serves notifications yet.
+pub(crate) fn spawn_if_needed() {
+    if !ENABLED.load(Ordering::Relaxed) {
+        return;
+    }
+    let mut servers = match SERVERS.lock() {
+        Ok(servers) => servers,
+        Err(_) => return,
+    };
+    let namespace = xdg::namespace::dbus_namespace();
+    if servers.iter().any(|(name, _)| *name == namespace.bus_name) || xdg::is_server_running() {
+        return;
+    }
+    match block_on(serve(&namespace)) {
+        Ok(connection) => servers.push((namespace.bus_name, connection)),
+        Err(error) => log::warn!("can't start the embedded notification server, {}", error),
+    }
+}
+
+async fn serve(namespace: &xdg::DbusNamespace) -> zbus::Result<zbus::Connection> {
+    zbus::connection::Builder::session()?
+        .name(namespace.bus_name.as_str())?
+        .serve_at(
+            namespace.object_path.as_str(),
+            EmbeddedServer { last_id: 0 },
+        )?
+        .build()
+        .await
+}
+
+struct EmbeddedServer {
+    last_id: u32,
+}
+
+#[interface(name = "org.freedesktop.Notifications")]
+impl EmbeddedServer {
+    #[allow(clippy::too_many_arguments)]
+    fn notify(
+        &mut self,
+        app_name: String,
+        replaces_id: u32,
+        _app_icon: String,
+        summary: String,
+        body: String,
+        actions: Vec<String>,
+        _hints: HashMap<String, OwnedValue>,
+        _expire_timeout: i32,
+    ) -> u32 {
+        let mut notification = Notification::new();
+        notification
+            .appname(&app_name)
+            .summary(&summary)
+            .body(&body);
+        notification.actions = Action::from_pairs(&actions);
+        if let Err(error) = notification.show_in_terminal(TerminalStyle::default()) {
+            log::warn!("can't print notification, {}", error);
+        }
+
+        let id = if replaces_id != 0 {
+            replaces_id
+        } else {
+            self.last_id = self.last_id.wrapping_add(1).max(1);
+            self.last_id
+        };
+        crate::trace::request("Notify", id, &app_name);
+        id
+    }
+
+    /// Printed notifications can't be taken back, but senders still want to hear they are gone.
+    async fn close_notification(
+        &self,
+        id: u32,
+        #[zbus(signal_emitter)] emitter: SignalEmitter<'_>,
+    ) -> zbus::fdo::Result<()> {
+        crate::trace::request("CloseNotification", id, "");
+        // 3: closed by a call to CloseNotification
+        Self::notification_closed(&emitter, id, 3).await?;
+        Ok(())
+    }
+
+    fn get_capabilities(&self) -> Vec<&str> {
+        vec!["body"]
+    }
+
+    fn get_server_information(&self) -> (&str, &str, &str, &str) {
+        (
+            "notify-rust",
+            "notify-rust",
+            env!("CARGO_PKG_VERSION"),
+            "1.2",
+        )
+    }
+
+    #[zbus(signal)]
+    async fn notification_closed(
+        emitter: &SignalEmitter<'_>,
+        id: u32,
+        reason: u32,
+    ) -> zbus::Result<()>;
+}
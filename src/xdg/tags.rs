@@ -0,0 +1,51 @@
+//! Servers identify notifications by the ids they hand out, see [`Notification::tag`](`crate::Notification::tag`).
+//! The id each tag was last shown with is kept here, so the next notification with that tag replaces it.
+
+use crate::notification::Notification;
+
+use std::{collections::HashMap, sync::Mutex};
+
+/// Ids by tag, for every notification shown with one.
+static SHOWN: Mutex<Option<HashMap<String, u32>>> = Mutex::new(None);
+
+/// The id to send `notification` with: its own [`id`](`Notification::id`),
+/// the one its tag was last shown with, or 0 for a new notification.
+pub(crate) fn replaces_id(notification: &Notification) -> u32 {
+    notification
+        .id
+        .or_else(|| {
+            let tag = notification.tag.as_ref()?;
+            SHOWN.lock().ok()?.as_ref()?.get(tag).copied()
+        })
+        .unwrap_or(0)
+}
+
+/// Remembers the id the server gave `notification`, if it has a tag.
+pub(crate) fn remember(id: u32, notification: &Notification) {
+    if let (Some(tag), Ok(mut shown)) = (&notification.tag, SHOWN.lock()) {
+        shown
+            .get_or_insert_with(HashMap::new)
+            .insert(tag.clone(), id);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn replaces_by_tag() {
+        let mut download = Notification::new();
+        download.tag("tags-test-download");
+        assert_eq!(replaces_id(&download), 0);
+        remember(17, &download);
+        assert_eq!(replaces_id(&download), 17);
+        remember(18, &download);
+        assert_eq!(replaces_id(&download), 18);
+
+        assert_eq!(replaces_id(download.clone().id(3)), 3);
+        assert_eq!(replaces_id(Notification::new().tag("tags-test-other")), 0);
+        remember(19, &Notification::new());
+        assert_eq!(replaces_id(&Notification::new()), 0);
+    }
+}
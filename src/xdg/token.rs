@@ -0,0 +1,96 @@
+//! Serializes what it takes to reach a shown notification again, see [`NotificationHandle::to_token`](`super::NotificationHandle::to_token`).
+//!
+//! A token looks like `notify-rust:42:session:backup`, the fields are the id, the bus and the appname,
+//! `%` and `:` inside them are percent-encoded.
+
+use super::MessageBus;
+use crate::error::*;
+
+const PREFIX: &str = "notify-rust";
+
+pub(crate) fn encode(id: u32, appname: &str, bus: &MessageBus) -> String {
+    let bus = match bus {
+        MessageBus::Session => "session".to_owned(),
+        MessageBus::System => "system".to_owned(),
+        MessageBus::Address(address) => format!("address={}", escape(address)),
+    };
+    format!("{}:{}:{}:{}", PREFIX, id, bus, escape(appname))
+}
+
+pub(crate) fn decode(token: &str) -> Result<(u32, String, MessageBus)> {
+    let invalid = || Error::from(format!("invalid notification token {:?}", token).as_str());
+    let mut fields = token.split(':');
+    let (id, bus, appname) = match (
+        fields.next(),
+        fields.next(),
+        fields.next(),
+        fields.next(),
+        fields.next(),
+    ) {
+        (Some(PREFIX), Some(id), Some(bus), Some(appname), None) => (id, bus, appname),
+        _ => return Err(invalid()),
+    };
+    let id = id.parse().map_err(|_| invalid())?;
+    let bus = match bus {
+        "session" => MessageBus::Session,
+        "system" => MessageBus::System,
+        _ => match bus.strip_prefix("address=") {
+            Some(address) => MessageBus::Address(unescape(address).ok_or_else(invalid)?),
+            None => return Err(invalid()),
+        },
+    };
+    Ok((id, unescape(appname).ok_or_else(invalid)?, bus))
+}
+
+fn escape(field: &str) -> String {
+    field.replace('%', "%25").replace(':', "%3A")
+}
+
+fn unescape(field: &str) -> Option<String> {
+    let mut unescaped = String::with_capacity(field.len());
+    let mut rest = field;
+    while let Some(percent) = rest.find('%') {
+        unescaped.push_str(&rest[..percent]);
+        let escaped = rest.get(percent..percent + 3)?;
+        unescaped.push(match escaped {
+            "%25" => '%',
+            "%3A" => ':',
+            _ => return None,
+        });
+        rest = &rest[percent + 3..];
+    }
+    unescaped.push_str(rest);
+    Some(unescaped)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn round_trips() {
+        let bus = MessageBus::Address("tcp:host=10.0.0.2,port=55556".into());
+        let token = encode(42, "update 100%: stage 2", &bus);
+        assert_eq!(
+            token,
+            "notify-rust:42:address=tcp%3Ahost=10.0.0.2,port=55556:update 100%25%3A stage 2"
+        );
+        assert_eq!(
+            decode(&token).unwrap(),
+            (42, "update 100%: stage 2".to_owned(), bus)
+        );
+        assert_eq!(
+            decode(&encode(7, "backup", &MessageBus::System)).unwrap(),
+            (7, "backup".to_owned(), MessageBus::System)
+        );
+    }
+
+    #[test]
+    fn rejects_garbage() {
+        assert!(decode("").is_err());
+        assert!(decode("notify-rust:x:session:backup").is_err());
+        assert!(decode("notify-rust:1:tcp:backup").is_err());
+        assert!(decode("notify-rust:1:session:back:up").is_err());
+        assert!(decode("notify-rust:1:session:100%").is_err());
+    }
+}
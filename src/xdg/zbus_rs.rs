@@ -1,11 +1,29 @@
-use crate::{error::*, notification::Notification, xdg};
-use zbus::{export::futures_util::TryStreamExt, MatchRule};
-
-use super::{bus::NotificationBus, ActionResponse, ActionResponseHandler, CloseReason};
+use crate::{
+    action::{ActionResponse, ActionResponseHandler, ActionResult, CloseReason},
+    error::*,
+    notification::Notification,
+    xdg::{self, action_icons, tags, MessageBus},
+};
+use futures_lite::{future, StreamExt};
+use zbus::{zvariant, MatchRule};
+
+use std::{
+    borrow::Cow,
+    collections::HashMap,
+    future::Future,
+    sync::{
+        mpsc::{self, Receiver, Sender},
+        Mutex,
+    },
+    thread,
+    time::Duration,
+};
+
+use super::bus::NotificationBus;
 
 pub mod bus {
 
-    use crate::xdg::NOTIFICATION_DEFAULT_BUS;
+    use crate::xdg::{namespace, NOTIFICATION_DEFAULT_BUS};
 
     fn skip_first_slash(s: &str) -> &str {
         if let Some('/') = s.chars().next() {
@@ -19,15 +37,9 @@ pub mod bus {
 
     type BusNameType = zbus::names::WellKnownName<'static>;
 
-    #[derive(Clone, Debug)]
-    pub struct NotificationBus(BusNameType);
-
-    impl Default for NotificationBus {
-        #[cfg(feature = "zbus")]
-        fn default() -> Self {
-            Self(zbus::names::WellKnownName::from_static_str(NOTIFICATION_DEFAULT_BUS).unwrap())
-        }
-    }
+    /// `None` stands for the bus name of the [namespace](`crate::set_dbus_namespace`) at the time of sending.
+    #[derive(Clone, Debug, Default, PartialEq, Eq)]
+    pub struct NotificationBus(Option<BusNameType>);
 
     impl NotificationBus {
         fn namespaced_custom(custom_path: &str) -> Option<String> {
@@ -44,11 +56,15 @@ pub mod bus {
         pub fn custom(custom_path: &str) -> Option<Self> {
             let name =
                 zbus::names::WellKnownName::try_from(Self::namespaced_custom(custom_path)?).ok()?;
-            Some(Self(name))
+            Some(Self(Some(name)))
         }
 
         pub fn into_name(self) -> BusNameType {
-            self.0
+            self.0.unwrap_or_else(|| {
+                zbus::names::WellKnownName::try_from(namespace::bus_name()).unwrap_or_else(|_| {
+                    zbus::names::WellKnownName::from_static_str(NOTIFICATION_DEFAULT_BUS).unwrap()
+                })
+            })
         }
     }
 }
@@ -69,6 +85,8 @@ impl ZbusNotificationHandle {
         connection: zbus::Connection,
         notification: Notification,
     ) -> ZbusNotificationHandle {
+        action_icons::remember(id, &notification);
+        tags::remember(id, &notification);
         ZbusNotificationHandle {
             id,
             connection,
@@ -80,11 +98,46 @@ impl ZbusNotificationHandle {
         wait_for_action_signal(&self.connection, self.id, invocation_closure).await;
     }
 
-    pub async fn close_fallible(self) -> Result<()> {
+    pub async fn wait_for_action_with_timeout(
+        self,
+        timeout: Duration,
+        invocation_closure: impl ActionResponseHandler,
+    ) {
+        future::or(
+            wait_for_action_signal(&self.connection, self.id, invocation_closure),
+            async {
+                async_io::Timer::after(timeout).await;
+            },
+        )
+        .await;
+    }
+
+    pub async fn wait_for_reply(self) -> Option<String> {
+        let mut stream = match signal_stream(&self.connection).await {
+            Ok(stream) => stream,
+            Err(error) => {
+                log::warn!("can't listen for replies, {}", error);
+                return None;
+            }
+        };
+        while let Some(Ok(msg)) = stream.next().await {
+            match parse_reply(&msg) {
+                Some((id, reply)) if id == self.id => return Some(reply),
+                _ => {}
+            }
+            match parse_signal(&msg) {
+                Some((id, _)) if id == self.id => return None,
+                _ => {}
+            }
+        }
+        None
+    }
+
+    pub async fn close(self) -> Result<()> {
         self.connection
             .call_method(
                 Some(self.notification.bus.clone().into_name()),
-                xdg::NOTIFICATION_OBJECTPATH,
+                xdg::namespace::object_path(),
                 Some(xdg::NOTIFICATION_INTERFACE),
                 "CloseNotification",
                 &(self.id),
@@ -93,10 +146,6 @@ impl ZbusNotificationHandle {
         Ok(())
     }
 
-    pub async fn close(self) {
-        self.close_fallible().await.unwrap();
-    }
-
     pub fn on_close<F>(self, closure: F)
     where
         F: FnOnce(CloseReason),
@@ -108,17 +157,63 @@ impl ZbusNotificationHandle {
         }));
     }
 
-    pub fn update_fallible(&mut self) -> Result<()> {
-        self.id = zbus::block_on(send_notification_via_connection(
-            &self.notification,
-            self.id,
+    pub fn update(&mut self) -> Result<()> {
+        let (notification, id) = (&self.notification, self.id);
+        let sent = zbus::block_on(send_notification_via_connection(
+            notification,
+            id,
             &self.connection,
-        ))?;
+        ));
+        self.id = match sent {
+            // the connection broke or the server restarted, retry like a new notification would
+            Err(error) if is_transient(&error) => {
+                let (id, connection) = zbus::block_on(on_shared_connection(
+                    &notification.message_bus,
+                    |connection| async move {
+                        send_notification_via_connection(notification, id, &connection)
+                            .await
+                            .map(|id| (id, connection))
+                    },
+                ))?;
+                self.connection = connection;
+                id
+            }
+            sent => sent?,
+        };
         Ok(())
     }
 
-    pub fn update(&mut self) {
-        self.update_fallible().unwrap();
+    pub fn listen(&self) -> Receiver<ActionResult> {
+        let (sender, receiver) = mpsc::channel();
+        let id = self.id;
+        // subscribe before returning, so nothing that happens right after is missed
+        let stream = zbus::block_on(signal_stream(&self.connection));
+        let spawned = stream.map(|stream| {
+            thread::Builder::new()
+                .name("notify-rust listener".into())
+                .spawn(move || zbus::block_on(forward_signals(stream, id, sender)))
+        });
+        match spawned {
+            Ok(Ok(_)) => {}
+            Ok(Err(error)) => log::warn!("can't listen for actions, {}", error),
+            Err(error) => log::warn!("can't listen for actions, {}", error),
+        }
+        receiver
+    }
+}
+
+/// Sends everything that happens to notification `id` to `sender`, until it is closed or nobody listens anymore.
+async fn forward_signals(mut stream: zbus::MessageStream, id: u32, sender: Sender<ActionResult>) {
+    while let Some(Ok(msg)) = stream.next().await {
+        match parse_signal(&msg) {
+            Some((nid, event)) if nid == id => {
+                let closed = matches!(event, ActionResult::Closed(_));
+                if sender.send(event).is_err() || closed {
+                    break;
+                }
+            }
+            _ => {}
+        }
     }
 }
 
@@ -139,19 +234,10 @@ async fn send_notification_via_connection_at_bus(
     let reply: u32 = connection
         .call_method(
             Some(bus.into_name()),
-            xdg::NOTIFICATION_OBJECTPATH,
+            xdg::namespace::object_path(),
             Some(xdg::NOTIFICATION_INTERFACE),
             "Notify",
-            &(
-                &notification.appname,
-                id,
-                &notification.icon,
-                &notification.summary,
-                &notification.body,
-                &notification.actions,
-                crate::hints::hints_to_map(notification),
-                i32::from(notification.timeout),
-            ),
+            &notify_args(notification, id),
         )
         .await?
         .body()
@@ -159,6 +245,45 @@ async fn send_notification_via_connection_at_bus(
     Ok(reply)
 }
 
+type NotifyArgs<'a> = (
+    &'a str,
+    u32,
+    &'a str,
+    Cow<'a, str>,
+    Cow<'a, str>,
+    Vec<&'a str>,
+    HashMap<&'a str, zvariant::Value<'a>>,
+    i32,
+);
+
+fn notify_args(notification: &Notification, id: u32) -> NotifyArgs<'_> {
+    let (summary, body) = xdg::adapt::adapt(notification);
+    (
+        &notification.appname,
+        id,
+        &notification.icon,
+        summary,
+        body,
+        notification.actions_to_pairs(),
+        crate::hints::hints_to_map(notification),
+        i32::from(notification.timeout),
+    )
+}
+
+/// Sends `Notify` without asking the server for a reply.
+pub async fn connect_and_send_notification_no_wait(notification: &Notification) -> Result<()> {
+    let message = zbus::Message::method_call(xdg::namespace::object_path(), "Notify")?
+        .destination(notification.bus.clone().into_name())?
+        .interface(xdg::NOTIFICATION_INTERFACE)?
+        .with_flags(zbus::message::Flags::NoReplyExpected)?
+        .build(&notify_args(notification, tags::replaces_id(notification)))?;
+    on_shared_connection(&notification.message_bus, |connection| {
+        let message = &message;
+        async move { Ok(connection.send(message).await?) }
+    })
+    .await
+}
+
 pub async fn connect_and_send_notification(
     notification: &Notification,
 ) -> Result<ZbusNotificationHandle> {
@@ -170,11 +295,228 @@ pub(crate) async fn connect_and_send_notification_at_bus(
     notification: &Notification,
     bus: NotificationBus,
 ) -> Result<ZbusNotificationHandle> {
-    let connection = zbus::Connection::session().await?;
-    let inner_id = notification.id.unwrap_or(0);
+    let inner_id = tags::replaces_id(notification);
+    let (id, connection) = on_shared_connection(&notification.message_bus, |connection| {
+        let bus = bus.clone();
+        async move {
+            send_notification_via_connection_at_bus(notification, inner_id, &connection, bus)
+                .await
+                .map(|id| (id, connection))
+        }
+    })
+    .await?;
+
+    Ok(ZbusNotificationHandle::new(
+        id,
+        connection,
+        notification.clone(),
+    ))
+}
+
+/// Connections shared by all notifications, by bus and call timeout.
+static CONNECTIONS: Mutex<Vec<(MessageBus, Option<Duration>, zbus::Connection)>> =
+    Mutex::new(Vec::new());
+
+/// Returns the shared connection to `bus`, connecting first if there is none yet.
+pub(super) async fn connect(bus: &MessageBus) -> Result<zbus::Connection> {
+    let timeout = xdg::call_timeout();
+    let shared = CONNECTIONS.lock().ok().and_then(|connections| {
+        connections
+            .iter()
+            .find(|(b, t, _)| b == bus && *t == timeout)
+            .map(|(_, _, connection)| connection.clone())
+    });
+    if let Some(connection) = shared {
+        return Ok(connection);
+    }
+
+    let builder = match bus {
+        MessageBus::Session => zbus::connection::Builder::session()?,
+        MessageBus::System => zbus::connection::Builder::system()?,
+        MessageBus::Address(address) => zbus::connection::Builder::address(address.as_str())?,
+    };
+    let builder = match timeout {
+        Some(timeout) => builder.method_timeout(timeout),
+        None => builder,
+    };
+    let connection = builder.build().await?;
+    if let Ok(mut connections) = CONNECTIONS.lock() {
+        connections.retain(|(b, t, _)| b != bus || *t != timeout);
+        connections.push((bus.clone(), timeout, connection.clone()));
+    }
+    Ok(connection)
+}
+
+/// Runs `call` on the shared connection to `bus`, retrying according to the [`RetryPolicy`](`xdg::RetryPolicy`)
+/// if the server or the bus went away, a broken connection is replaced first.
+async fn on_shared_connection<T, F, Fut>(bus: &MessageBus, call: F) -> Result<T>
+where
+    F: Fn(zbus::Connection) -> Fut,
+    Fut: Future<Output = Result<T>>,
+{
+    let policy = xdg::retry_policy();
+    let mut attempt = 0;
+    loop {
+        let result = match connect(bus).await {
+            Ok(connection) => call(connection).await,
+            Err(error) => Err(error),
+        };
+        match result {
+            Err(error) if attempt < policy.attempts && is_transient(&error) => {
+                attempt += 1;
+                before_retry(bus, &error, &policy).await;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Drops the shared connection to `bus` if `error` broke it, then waits as long as `policy` says.
+async fn before_retry(bus: &MessageBus, error: &Error, policy: &xdg::RetryPolicy) {
+    if matches!(error.kind(), ErrorKind::Zbus(zbus::Error::InputOutput(_))) {
+        if let Ok(mut connections) = CONNECTIONS.lock() {
+            connections.retain(|(b, _, _)| b != bus);
+        }
+    }
+    if !policy.delay.is_zero() {
+        async_io::Timer::after(policy.delay).await;
+    }
+}
+
+fn is_transient(error: &Error) -> bool {
+    match error.kind() {
+        ErrorKind::Zbus(zbus::Error::InputOutput(_)) => true,
+        ErrorKind::Zbus(zbus::Error::MethodError(name, _, _)) => {
+            xdg::SERVER_GONE_ERRORS.contains(&name.as_str())
+        }
+        ErrorKind::Zbus(zbus::Error::FDO(error)) => matches!(
+            **error,
+            zbus::fdo::Error::ServiceUnknown(_) | zbus::fdo::Error::NameHasNoOwner(_)
+        ),
+        _ => false,
+    }
+}
+
+/// Sends `Notify` for all `notifications` before waiting for the first reply.
+///
+/// All notifications go via the bus the first one is set to.
+/// Retrying according to the [`RetryPolicy`](`xdg::RetryPolicy`) sends only those again that got no id,
+/// so the others aren't shown twice.
+pub async fn send_notifications(
+    notifications: &[Notification],
+) -> Result<Vec<ZbusNotificationHandle>> {
+    let message_bus = match notifications.first() {
+        Some(first) => &first.message_bus,
+        None => return Ok(Vec::new()),
+    };
+    let policy = xdg::retry_policy();
+    let mut sent = vec![None; notifications.len()];
+    let mut attempt = 0;
+    loop {
+        let result = match connect(message_bus).await {
+            Ok(connection) => send_batch(&connection, notifications, &mut sent).await,
+            Err(error) => Err(error),
+        };
+        match result {
+            Err(error) if attempt < policy.attempts && is_transient(&error) => {
+                attempt += 1;
+                before_retry(message_bus, &error, &policy).await;
+            }
+            Err(error) => return Err(error),
+            Ok(()) => break,
+        }
+    }
+
+    Ok(sent
+        .into_iter()
+        .zip(notifications)
+        .filter_map(|(sent, notification)| {
+            let (id, connection) = sent?;
+            Some(ZbusNotificationHandle::new(
+                id,
+                connection,
+                notification.clone(),
+            ))
+        })
+        .collect())
+}
+
+/// Sends `Notify` for every notification without an entry in `sent` yet, and fills in the ids replied.
+///
+/// Waits for all replies even if one is an error, so `sent` tells exactly which notifications were shown.
+async fn send_batch(
+    connection: &zbus::Connection,
+    notifications: &[Notification],
+    sent: &mut [Option<(u32, zbus::Connection)>],
+) -> Result<()> {
+    // subscribe before sending, so no reply slips through
+    let mut replies = zbus::MessageStream::from(connection);
+    let mut serials = Vec::new();
+    for (index, notification) in notifications.iter().enumerate() {
+        if sent[index].is_some() {
+            continue;
+        }
+        let message = zbus::Message::method_call(xdg::namespace::object_path(), "Notify")?
+            .destination(notification.bus.clone().into_name())?
+            .interface(xdg::NOTIFICATION_INTERFACE)?
+            .build(&notify_args(notification, tags::replaces_id(notification)))?;
+        serials.push((index, message.primary_header().serial_num()));
+        connection.send(&message).await?;
+    }
+
+    let mut failed = None;
+    let collect_ids = async {
+        while !serials.is_empty() {
+            let reply = match replies.try_next().await? {
+                Some(reply) => reply,
+                None => return Err(Error::from("connection closed before all replies arrived")),
+            };
+            let header = reply.header();
+            let position = match header
+                .reply_serial()
+                .and_then(|serial| serials.iter().position(|(_, sent)| *sent == serial))
+            {
+                Some(position) => position,
+                None => continue,
+            };
+            match header.message_type() {
+                zbus::message::Type::MethodReturn => {
+                    let (index, _) = serials.swap_remove(position);
+                    sent[index] = Some((reply.body().deserialize::<u32>()?, connection.clone()));
+                }
+                zbus::message::Type::Error => {
+                    serials.swap_remove(position);
+                    failed.get_or_insert_with(|| Error::from(zbus::Error::from(reply)));
+                }
+                _ => {}
+            }
+        }
+        Ok(())
+    };
+    match xdg::call_timeout() {
+        Some(timeout) => {
+            future::or(collect_ids, async {
+                async_io::Timer::after(timeout).await;
+                Err(Error::from("timed out waiting for the notification server"))
+            })
+            .await
+        }
+        None => collect_ids.await,
+    }?;
+    match failed {
+        Some(error) => Err(error),
+        None => Ok(()),
+    }
+}
+
+pub async fn send_notification_with_connection(
+    notification: &Notification,
+    connection: zbus::Connection,
+) -> Result<ZbusNotificationHandle> {
+    let inner_id = tags::replaces_id(notification);
+    let bus = notification.bus.clone();
     let id =
         send_notification_via_connection_at_bus(notification, inner_id, &connection, bus).await?;
-
     Ok(ZbusNotificationHandle::new(
         id,
         connection,
@@ -183,19 +525,24 @@ pub(crate) async fn connect_and_send_notification_at_bus(
 }
 
 pub async fn get_capabilities_at_bus(bus: NotificationBus) -> Result<Vec<String>> {
-    let connection = zbus::Connection::session().await?;
-    let info: Vec<String> = connection
-        .call_method(
-            Some(bus.into_name()),
-            xdg::NOTIFICATION_OBJECTPATH,
-            Some(xdg::NOTIFICATION_INTERFACE),
-            "GetCapabilities",
-            &(),
-        )
-        .await?
-        .body()
-        .deserialize()?;
-    Ok(info)
+    on_shared_connection(&MessageBus::Session, |connection| {
+        let bus = bus.clone();
+        async move {
+            let info: Vec<String> = connection
+                .call_method(
+                    Some(bus.into_name()),
+                    xdg::namespace::object_path(),
+                    Some(xdg::NOTIFICATION_INTERFACE),
+                    "GetCapabilities",
+                    &(),
+                )
+                .await?
+                .body()
+                .deserialize()?;
+            Ok(info)
+        }
+    })
+    .await
 }
 
 pub async fn get_capabilities() -> Result<Vec<String>> {
@@ -203,32 +550,52 @@ pub async fn get_capabilities() -> Result<Vec<String>> {
 }
 
 pub async fn get_server_information_at_bus(bus: NotificationBus) -> Result<xdg::ServerInformation> {
-    let connection = zbus::Connection::session().await?;
-    let info: xdg::ServerInformation = connection
-        .call_method(
-            Some(bus.into_name()),
-            xdg::NOTIFICATION_OBJECTPATH,
-            Some(xdg::NOTIFICATION_INTERFACE),
-            "GetServerInformation",
-            &(),
-        )
-        .await?
-        .body()
-        .deserialize()?;
-
-    Ok(info)
+    on_shared_connection(&MessageBus::Session, |connection| {
+        let bus = bus.clone();
+        async move {
+            let info: xdg::ServerInformation = connection
+                .call_method(
+                    Some(bus.into_name()),
+                    xdg::namespace::object_path(),
+                    Some(xdg::NOTIFICATION_INTERFACE),
+                    "GetServerInformation",
+                    &(),
+                )
+                .await?
+                .body()
+                .deserialize()?;
+            Ok(info)
+        }
+    })
+    .await
 }
 
 pub async fn get_server_information() -> Result<xdg::ServerInformation> {
     get_server_information_at_bus(Default::default()).await
 }
 
+pub async fn is_server_running() -> Result<bool> {
+    on_shared_connection(&MessageBus::Session, |connection| async move {
+        let name =
+            zbus::names::BusName::try_from(NotificationBus::default().into_name().to_string())
+                .map_err(zbus::Error::from)?;
+        let proxy = zbus::fdo::DBusProxy::new(&connection).await?;
+        Ok(proxy
+            .name_has_owner(name)
+            .await
+            .map_err(zbus::Error::from)?)
+    })
+    .await
+}
+
 /// Listens for the `ActionInvoked(UInt32, String)` Signal.
 ///
 /// No need to use this, check out `Notification::show_and_wait_for_action(FnOnce(action:&str))`
 pub async fn handle_action(id: u32, func: impl ActionResponseHandler) {
-    let connection = zbus::Connection::session().await.unwrap();
-    wait_for_action_signal(&connection, id, func).await;
+    match connect(&MessageBus::Session).await {
+        Ok(connection) => wait_for_action_signal(&connection, id, func).await,
+        Err(error) => log::warn!("can't listen for actions, {}", error),
+    }
 }
 
 async fn wait_for_action_signal(
@@ -236,50 +603,84 @@ async fn wait_for_action_signal(
     id: u32,
     handler: impl ActionResponseHandler,
 ) {
-    let action_signal_rule = MatchRule::builder()
-        .msg_type(zbus::message::Type::Signal)
-        .interface(xdg::NOTIFICATION_INTERFACE)
-        .unwrap()
-        .member("ActionInvoked")
-        .unwrap()
-        .build();
-
-    let proxy = zbus::fdo::DBusProxy::new(connection).await.unwrap();
-    proxy.add_match_rule(action_signal_rule).await.unwrap();
+    let mut stream = match signal_stream(connection).await {
+        Ok(stream) => stream,
+        Err(error) => {
+            log::warn!("can't listen for actions, {}", error);
+            return;
+        }
+    };
 
-    let close_signal_rule = MatchRule::builder()
-        .msg_type(zbus::message::Type::Signal)
-        .interface(xdg::NOTIFICATION_INTERFACE)
-        .unwrap()
-        .member("NotificationClosed")
-        .unwrap()
-        .build();
-    proxy.add_match_rule(close_signal_rule).await.unwrap();
-
-    while let Ok(Some(msg)) = zbus::MessageStream::from(connection).try_next().await {
-        let header = msg.header();
-        if let zbus::message::Type::Signal = header.message_type() {
-            match header.member() {
-                Some(name) if name == "ActionInvoked" => {
-                    match msg.body().deserialize::<(u32, String)>() {
-                        Ok((nid, action)) if nid == id => {
-                            handler.call(&ActionResponse::Custom(&action));
-                            break;
-                        }
-                        _ => {}
-                    }
-                }
-                Some(name) if name == "NotificationClosed" => {
-                    match msg.body().deserialize::<(u32, u32)>() {
-                        Ok((nid, reason)) if nid == id => {
-                            handler.call(&ActionResponse::Closed(reason.into()));
-                            break;
-                        }
-                        _ => {}
-                    }
-                }
-                _ => {}
+    while let Ok(Some(msg)) = stream.try_next().await {
+        match parse_signal(&msg) {
+            Some((nid, ActionResult::Action(action))) if nid == id => {
+                handler.call(&ActionResponse::Custom(&action));
+                break;
+            }
+            Some((nid, ActionResult::Closed(reason))) if nid == id => {
+                handler.call(&ActionResponse::Closed(reason));
+                break;
             }
+            _ => {}
+        }
+    }
+}
+
+/// Reads the notification id and what happened from an `ActionInvoked`, `NotificationClosed` or `NotificationReplied` signal,
+/// a reply counts as the `"reply"` action.
+pub(super) fn parse_signal(msg: &zbus::Message) -> Option<(u32, ActionResult)> {
+    let header = msg.header();
+    if header.message_type() != zbus::message::Type::Signal {
+        return None;
+    }
+    let parsed = match header.member()?.as_str() {
+        "ActionInvoked" => {
+            let (id, action) = msg.body().deserialize::<(u32, String)>().ok()?;
+            Some((
+                id,
+                action_icons::translate(id, ActionResult::Action(action)),
+            ))
         }
+        "NotificationClosed" => {
+            let (id, reason) = msg.body().deserialize::<(u32, u32)>().ok()?;
+            Some((
+                id,
+                action_icons::translate(id, ActionResult::Closed(reason.into())),
+            ))
+        }
+        "NotificationReplied" => {
+            let (id, _) = msg.body().deserialize::<(u32, String)>().ok()?;
+            Some((id, ActionResult::Action("reply".into())))
+        }
+        _ => None,
+    };
+    if let Some((id, result)) = &parsed {
+        crate::trace::signal(*id, result);
     }
+    parsed
+}
+
+/// Reads the notification id and the typed text from a `NotificationReplied` signal.
+fn parse_reply(msg: &zbus::Message) -> Option<(u32, String)> {
+    let header = msg.header();
+    if header.message_type() != zbus::message::Type::Signal
+        || header.member()?.as_str() != "NotificationReplied"
+    {
+        return None;
+    }
+    msg.body().deserialize::<(u32, String)>().ok()
+}
+
+/// Subscribes to the signals of the notification interface, the server only sends us signals we asked for.
+///
+/// The match rule is shared by all streams on `connection` and removed once the last one is dropped,
+/// so waiting on the shared connection again and again doesn't pile up rules at the bus.
+pub(super) async fn signal_stream(
+    connection: &zbus::Connection,
+) -> zbus::Result<zbus::MessageStream> {
+    let rule = MatchRule::builder()
+        .msg_type(zbus::message::Type::Signal)
+        .interface(xdg::NOTIFICATION_INTERFACE)?
+        .build();
+    zbus::MessageStream::for_match_rule(rule, connection, None).await
 }
@@ -0,0 +1,36 @@
+//! Escaping for the toast XML built by the windows backend and the WSL bridge,
+//! and for text in notification bodies, which XDG servers may render as markup.
+
+use std::borrow::Cow;
+
+/// Escapes `raw` for use in XML text and attribute values.
+#[cfg(any(target_os = "windows", target_os = "linux"))]
+pub(crate) fn escape(raw: &str) -> Cow<'_, str> {
+    if !raw.contains(['&', '<', '>', '"', '\'']) {
+        return Cow::Borrowed(raw);
+    }
+    let mut escaped = String::with_capacity(raw.len());
+    for c in raw.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            c => escaped.push(c),
+        }
+    }
+    Cow::Owned(escaped)
+}
+
+/// Escapes `raw` for use in markup text, leaving quotes alone.
+pub(crate) fn escape_text(raw: &str) -> Cow<'_, str> {
+    if !raw.contains(['&', '<', '>']) {
+        return Cow::Borrowed(raw);
+    }
+    Cow::Owned(
+        raw.replace('&', "&amp;")
+            .replace('<', "&lt;")
+            .replace('>', "&gt;"),
+    )
+}
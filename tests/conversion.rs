@@ -36,4 +36,111 @@ mod conversion {
         assert_eq!(Urgency::try_from("Hi").ok(), Some(Urgency::Critical));
         assert_eq!(Urgency::try_from("Critical").ok(), Some(Urgency::Critical));
     }
+
+    #[test]
+    #[cfg(all(unix, not(target_os = "macos")))]
+    fn text_as_it_is() {
+        // escaping happens when showing, and only for servers rendering markup
+        let notification = notify_rust::Notification::new()
+            .summary_text("Tom & Jerry")
+            .body_text(r#"<a href="https://example.com">"click"</a>"#)
+            .finalize();
+        assert_eq!(notification.summary, "Tom & Jerry");
+        assert_eq!(
+            notification.body,
+            r#"<a href="https://example.com">"click"</a>"#
+        );
+    }
+
+    #[test]
+    fn notification_display() {
+        use notify_rust::{Notification, Priority, Timeout};
+
+        let mut notification = Notification::new();
+        notification.appname("backup").summary("done");
+        assert_eq!(notification.to_string(), "[backup] done");
+
+        notification
+            .body("42 files\n  copied")
+            .priority(Priority::High)
+            .timeout(Timeout::Never);
+        #[cfg(all(unix, not(target_os = "macos")))]
+        assert_eq!(
+            notification.to_string(),
+            "[backup] done — 42 files copied (critical, never)"
+        );
+        #[cfg(not(all(unix, not(target_os = "macos"))))]
+        assert_eq!(
+            notification.to_string(),
+            "[backup] done — 42 files copied (high, never)"
+        );
+        assert_eq!(
+            Timeout::Milliseconds(250).to_string().parse(),
+            Ok(Timeout::Milliseconds(250))
+        );
+    }
+
+    #[test]
+    fn notification_equality() {
+        use notify_rust::Notification;
+        use std::collections::HashSet;
+
+        let build = |body: &str| {
+            let mut notification = Notification::new();
+            notification
+                .summary("backup")
+                .body(body)
+                .action("open", "Open");
+            #[cfg(all(unix, not(target_os = "macos")))]
+            notification.hint(notify_rust::Hint::Transient(true));
+            notification
+        };
+        assert_eq!(build("done"), build("done"));
+        assert_ne!(build("done"), build("failed"));
+
+        let unique = [build("done"), build("failed"), build("done")]
+            .into_iter()
+            .collect::<HashSet<_>>();
+        assert_eq!(unique.len(), 2);
+    }
+
+    #[test]
+    #[cfg(feature = "serde")]
+    fn serde_round_trip() {
+        use notify_rust::{Notification, Timeout};
+
+        let mut notification = Notification::new();
+        notification
+            .summary("backup")
+            .body("done")
+            .timeout(Timeout::Milliseconds(5000))
+            .action("open", "Open");
+        #[cfg(all(unix, not(target_os = "macos")))]
+        notification
+            .urgency(Urgency::Critical)
+            .hint(notify_rust::Hint::Custom("x-example".into(), "1".into()));
+
+        let json = serde_json::to_value(&notification).unwrap();
+        assert_eq!(json["timeout"], serde_json::json!({ "milliseconds": 5000 }));
+        #[cfg(all(unix, not(target_os = "macos")))]
+        {
+            assert_eq!(
+                json["hints"],
+                serde_json::json!([{ "urgency": "critical" }])
+            );
+            assert_eq!(
+                json["custom_hints"],
+                serde_json::json!([{ "custom": ["x-example", "1"] }])
+            );
+        }
+
+        let parsed: Notification = serde_json::from_value(json.clone()).unwrap();
+        assert_eq!(parsed.summary, "backup");
+        assert_eq!(parsed.actions, notification.actions);
+        assert_eq!(serde_json::to_value(&parsed).unwrap(), json);
+
+        let partial: Notification = serde_json::from_str(r#"{ "summary": "partial" }"#).unwrap();
+        assert_eq!(partial.summary, "partial");
+        assert_eq!(partial.timeout, Timeout::Default);
+    }
 }
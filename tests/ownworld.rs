@@ -1,3 +1,4 @@
+#![allow(unexpected_cfgs)]
 #![cfg(feature = "server")]
 #![allow(unused_must_use)]
 #![cfg(all(unix, not(target_os = "macos")))]
@@ -28,10 +29,10 @@ mod ownworld {
         let thread_handle = thread::spawn(move || {
             let server = NotificationServer::create();
             NotificationServer::start(&server, |notification| {
-                assert_eq!(notification.actions[0], "actions_vec0");
-                assert_eq!(notification.actions[1], "actions_vec1");
-                assert_eq!(notification.actions[2], "actions_vec2");
-                assert_eq!(notification.actions[3], "actions_vec3");
+                assert_eq!(notification.actions[0].identifier, "actions_vec0");
+                assert_eq!(notification.actions[0].label, "actions_vec1");
+                assert_eq!(notification.actions[1].identifier, "actions_vec2");
+                assert_eq!(notification.actions[1].label, "actions_vec3");
             })
         });
 
@@ -58,10 +59,10 @@ mod ownworld {
         let server = NotificationServer::create();
         let thread_handle = thread::spawn(move || {
             NotificationServer::start(&server, |notification| {
-                assert_eq!(notification.actions[0], "actions_built0");
-                assert_eq!(notification.actions[1], "actions_built1");
-                assert_eq!(notification.actions[2], "actions_built2");
-                assert_eq!(notification.actions[3], "actions_built3");
+                assert_eq!(notification.actions[0].identifier, "actions_built0");
+                assert_eq!(notification.actions[0].label, "actions_built1");
+                assert_eq!(notification.actions[1].identifier, "actions_built2");
+                assert_eq!(notification.actions[1].label, "actions_built3");
                 assert_eq!(notification.timeout, Timeout::Milliseconds(6000));
             })
         });
@@ -104,7 +105,7 @@ mod ownworld {
             let server = NotificationServer::create();
             NotificationServer::start(&server, |notification| {
                 assert_eq!(notification.timeout, Timeout::Milliseconds(6000));
-                assert_eq!(notification.actions[0], "this is no action");
+                assert_eq!(notification.actions[0].identifier, "this is no action");
             })
         });
 
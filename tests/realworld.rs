@@ -17,6 +17,197 @@ mod realworld {
         color_backtrace::install();
     }
 
+    #[test]
+    fn burst_no_wait() {
+        for msg in &["1%", "50%", "100%"] {
+            Notification::new()
+                .summary("progress")
+                .body(*msg)
+                .show_no_wait()
+                .unwrap();
+        }
+    }
+
+    #[test]
+    #[cfg(all(feature = "z", unix, not(target_os = "macos")))]
+    fn with_connection() {
+        let connection = zbus::block_on(zbus::Connection::session()).unwrap();
+        let handle = Notification::new()
+            .summary("own connection")
+            .show_with_connection(&connection)
+            .unwrap();
+        handle.close();
+    }
+
+    #[test]
+    #[cfg(all(feature = "z", unix, not(target_os = "macos")))]
+    fn dispatcher() {
+        let dispatcher = ActionDispatcher::new().unwrap();
+        let (first, first_events) = dispatcher
+            .show(Notification::new().summary("dispatched").body("first"))
+            .unwrap();
+        let (second, second_events) = dispatcher
+            .show(Notification::new().summary("dispatched").body("second"))
+            .unwrap();
+        second.close();
+        first.close();
+        let timeout = Duration::from_secs(5);
+        assert_eq!(
+            second_events.recv_timeout(timeout),
+            Ok(ActionResult::Closed(CloseReason::CloseAction))
+        );
+        assert_eq!(
+            first_events.recv_timeout(timeout),
+            Ok(ActionResult::Closed(CloseReason::CloseAction))
+        );
+    }
+
+    #[test]
+    #[cfg(all(unix, not(target_os = "macos")))]
+    fn on_close_with_reason() {
+        use std::sync::mpsc;
+        let handle = Notification::new().summary("closing").show().unwrap();
+        let id = handle.id();
+        let closer = std::thread::spawn(move || {
+            std::thread::sleep(Duration::from_millis(500));
+            Notification::new().id(id).show().unwrap().close();
+        });
+        let (sender, reasons) = mpsc::channel();
+        handle.on_close(move |reason: CloseReason| sender.send(reason).unwrap());
+        closer.join().unwrap();
+        assert_eq!(reasons.try_recv(), Ok(CloseReason::CloseAction));
+    }
+
+    #[test]
+    #[cfg(all(unix, not(target_os = "macos")))]
+    fn listen() {
+        let mut handle = Notification::new().summary("listening").show().unwrap();
+        let events = handle.listen();
+        handle.body("still mine");
+        handle.update().unwrap();
+        handle.close();
+        assert_eq!(
+            events.recv_timeout(Duration::from_secs(5)),
+            Ok(ActionResult::Closed(CloseReason::CloseAction))
+        );
+    }
+
+    #[test]
+    #[cfg(all(unix, not(target_os = "macos")))]
+    fn rich_body() {
+        let mut notification = Notification::new();
+        notification
+            .summary("rich")
+            .rich_body(Body::new().bold("bold").link("https://example.com", "link"));
+        // markup is built when showing, the body holds the plain text
+        assert_eq!(notification.body, "boldlink (https://example.com)");
+        notification.show().unwrap();
+    }
+
+    #[test]
+    #[cfg(all(unix, not(target_os = "macos")))]
+    fn hyperlink() {
+        let mut notification = Notification::new();
+        notification
+            .summary("build failed")
+            .body("see")
+            .hyperlink("https://ci.example.com/?build=42&log", "the log");
+        // markup is built when showing, the body holds the plain text
+        assert_eq!(
+            notification.body,
+            "see https://ci.example.com/?build=42&log"
+        );
+        notification.show().unwrap();
+    }
+
+    #[test]
+    #[cfg(all(unix, not(target_os = "macos")))]
+    fn action_with_icon() {
+        let notification = Notification::new()
+            .summary("Now playing")
+            .action_with_icon("pause", "Pause", "media-playback-pause")
+            .finalize();
+        // whether icons are sent is decided when showing, the notification stays as it was built
+        assert_eq!(
+            notification.actions,
+            [Action::with_icon("pause", "Pause", "media-playback-pause")]
+        );
+        assert!(!notification.hints.contains(&Hint::ActionIcons(true)));
+        notification.show().unwrap();
+    }
+
+    #[test]
+    #[cfg(all(unix, not(target_os = "macos")))]
+    fn progress() {
+        let mut copy = ProgressNotification::show(Notification::new().summary("copying")).unwrap();
+        copy.min_interval(Duration::from_secs(60));
+        copy.set_progress(10).unwrap();
+        // too soon after the last update
+        copy.set_progress(20).unwrap();
+        assert_eq!(copy.progress(), 10);
+        copy.set_progress(200).unwrap();
+        assert_eq!(copy.progress(), 100);
+        copy.into_handle().close();
+    }
+
+    #[test]
+    #[cfg(all(unix, not(target_os = "macos")))]
+    fn reply_input_needs_inline_reply() {
+        let handle = Notification::new()
+            .summary("reply")
+            .reply_input("type here")
+            .show()
+            .unwrap();
+        // the test server offers no inline replies, so no input was added
+        assert!(handle.actions.is_empty());
+        handle.close();
+    }
+
+    #[test]
+    fn burst_all_at_once() {
+        let notifications = ["first", "second", "third"].map(|msg| {
+            let mut notification = Notification::new();
+            notification.summary("batch").body(msg);
+            notification
+        });
+        let handles = Notification::show_all(&notifications).unwrap();
+        assert_eq!(handles.len(), 3);
+        assert_eq!(handles[1].body, "second");
+    }
+
+    #[test]
+    fn manager() {
+        let mut notifications = NotificationManager::new();
+        let first = notifications
+            .show(
+                "download",
+                Notification::new().summary("download").body("0%"),
+            )
+            .unwrap()
+            .id();
+        let second = notifications
+            .show(
+                "download",
+                Notification::new().summary("download").body("100%"),
+            )
+            .unwrap()
+            .id();
+        notifications
+            .show("upload", Notification::new().summary("upload"))
+            .unwrap();
+        assert_eq!(first, second);
+        assert_eq!(notifications.len(), 2);
+        assert_eq!(notifications.get("download").unwrap().body, "100%");
+        notifications.close_all().unwrap();
+        assert!(notifications.is_empty());
+    }
+
+    #[test]
+    #[cfg(all(unix, not(target_os = "macos")))]
+    fn server_is_running() {
+        assert!(is_server_running());
+    }
+
     #[test]
     fn burst() {
         for msg in &[
@@ -27,8 +218,8 @@ mod realworld {
         ] {
             Notification::new()
                 .summary("burst")
-                .appname(msg)
-                .body(msg)
+                .appname(*msg)
+                .body(*msg)
                 .icon("media-floppy")
                 .show()
                 .unwrap();
@@ -42,7 +233,7 @@ mod realworld {
         ] {
             Notification::new()
                 .summary("merged burst")
-                .body(msg)
+                .body(*msg)
                 .icon("applications-toys")
                 .show()
                 .unwrap();
@@ -57,17 +248,19 @@ mod realworld {
             .body("you don't see me!")
             .show()
             .unwrap()
-            .close();
+            .close()
+            .unwrap();
     }
 
     #[test]
     #[cfg(all(unix, not(target_os = "macos")))]
     fn capabilities() {
-        let capabilities: Vec<String> = get_capabilities().unwrap();
+        let capabilities = get_capabilities().unwrap();
+        assert!(capabilities.supports(Capability::Body));
         for capability in capabilities {
             Notification::new()
                 .summary("capability")
-                .body(&capability)
+                .body(capability.as_str())
                 .show()
                 .unwrap();
         }
@@ -135,13 +328,73 @@ mod realworld {
             Hint::Urgency(Critical),
         ] {
             Notification::new()
-                .summary(&format!("Urgency {:?}", urgency))
+                .summary(format!("Urgency {:?}", urgency))
                 .hint(urgency.clone())
                 .show()
                 .unwrap();
         }
     }
 
+    #[test]
+    #[cfg(all(unix, not(target_os = "macos")))]
+    fn sound_replaces_previous_sound() {
+        let mut notification = Notification::new();
+        notification
+            .sound(Sound::Silent)
+            .sound(Sound::File("/usr/share/sounds/bell.oga".into()))
+            .sound(Sound::Named("message-new-instant".into()));
+        assert_eq!(
+            notification.hints.iter().collect::<Vec<_>>(),
+            [&Hint::SoundName("message-new-instant".into())]
+        );
+
+        notification.sound(Sound::Default);
+        assert!(notification.hints.is_empty());
+    }
+
+    #[test]
+    #[cfg(all(unix, not(target_os = "macos")))]
+    fn sound_file() {
+        let mut notification = Notification::new();
+        assert!(notification.sound_file("no/such/sound.oga").is_err());
+        notification
+            .summary("sound file")
+            .sound_file("Cargo.toml")
+            .unwrap()
+            .suppress_sound(false)
+            .suppress_sound(true);
+        assert!(notification.hints.iter().any(
+            |hint| matches!(hint, Hint::SoundFile(path) if path.starts_with('/') && path.ends_with("Cargo.toml"))
+        ));
+        assert!(notification.hints.contains(&Hint::SuppressSound(true)));
+        assert_eq!(notification.hints.len(), 2);
+        notification.show().unwrap();
+    }
+
+    #[test]
+    #[cfg(all(unix, not(target_os = "macos")))]
+    fn on_unsupported() {
+        let mut notification = Notification::new();
+        notification
+            .summary("unsupported")
+            .sound_name("message-new-instant")
+            .on_unsupported(Unsupported::Warn);
+        notification.show().unwrap();
+
+        let error = notification
+            .on_unsupported(Unsupported::Strict)
+            .show()
+            .unwrap_err();
+        assert!(
+            matches!(error.kind(), error::ErrorKind::Unsupported(features) if features == "sound")
+        );
+    }
+
+    #[test]
+    fn available_with_session_bus() {
+        assert!(is_available());
+    }
+
     #[test]
     #[cfg(all(unix, not(target_os = "macos")))]
     fn category() {
@@ -152,6 +405,35 @@ mod realworld {
             .hint(Hint::Category("email".to_string()))
             .show()
             .unwrap();
+
+        Notification::new()
+            .summary("Category:im.received")
+            .category(Category::ImReceived)
+            .show()
+            .unwrap();
+    }
+
+    #[test]
+    #[cfg(all(unix, not(target_os = "macos")))]
+    fn desktop_entry() {
+        let notification = Notification::new()
+            .summary("desktop entry")
+            .desktop_entry("org.example.App")
+            .finalize();
+        assert!(notification
+            .hints
+            .contains(&Hint::DesktopEntry("org.example.App".into())));
+        notification.show().unwrap();
+    }
+
+    #[test]
+    #[cfg(all(unix, not(target_os = "macos")))]
+    fn urls() {
+        Notification::new()
+            .summary("Screenshot taken")
+            .hint(Hint::Urls(vec!["file:///tmp/screenshot.png".to_string()]))
+            .show()
+            .unwrap();
     }
 
     #[test]
@@ -173,6 +455,17 @@ mod realworld {
             .timeout(0)
             .show()
             .unwrap();
+
+        let notification = Notification::new()
+            .summary("Incoming Call: Your Mom!")
+            .body("resident() replaces Resident:False")
+            .resident(false)
+            .resident(true)
+            .transient(true)
+            .finalize();
+        assert_eq!(notification.hints.len(), 2);
+        assert!(notification.hints.contains(&Hint::Resident(true)));
+        notification.show().unwrap();
     }
 
     #[test]